@@ -0,0 +1,141 @@
+//! # Assignments
+//!
+//! A dense, three-valued assignment of propositional variables, used by the solvers and by the
+//! evaluation of formulas instead of a [`HashMap`].
+//!
+//! * [`Assignment`] - Maps every variable to `true`, `false` or unassigned.
+use crate::notation::Literal;
+use std::collections::HashMap;
+
+/// Dense assignment of the variables `1..=len()`
+///
+/// Values are stored in a vector indexed by variable, so reading, assigning or flipping a
+/// variable takes constant time. A variable is either `Some(true)`, `Some(false)` or unassigned
+/// (`None`). Variables beyond [`Assignment::len`] are unassigned, and assigning one grows the
+/// assignment.
+///
+/// Converts from and into the `HashMap<i32, bool>` form of an interpretation, which lists the
+/// assigned variables only.
+///
+/// # Examples
+/// ```rust
+/// use sat_rs::assignment::Assignment;
+/// use std::collections::HashMap;
+///
+/// let mut assignment = Assignment::new(3);
+/// assignment.set(1, true);
+/// assignment.set(3, false);
+///
+/// assert_eq!(assignment.get(1), Some(true));
+/// assert_eq!(assignment.get(2), None);
+///
+/// assignment.flip(1);
+/// assert_eq!(assignment.get(1), Some(false));
+///
+/// let interpretation: HashMap<i32, bool> = assignment.clone().into();
+/// assert_eq!(interpretation, HashMap::from([(1, false), (3, false)]));
+/// assert_eq!(Assignment::from(interpretation), assignment);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Assignment {
+    /// Value of variable `v` at index `v - 1`
+    values: Vec<Option<bool>>,
+}
+
+impl Assignment {
+    /// Creates an [`Assignment`] of `num_vars` variables, all unassigned
+    pub fn new(num_vars: usize) -> Assignment {
+        Assignment {
+            values: vec![None; num_vars],
+        }
+    }
+
+    /// Returns the number of variables covered by the assignment, assigned or not
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    /// Returns `true` if the assignment covers no variable
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    /// Returns the value of a variable, or `None` if it is unassigned
+    pub fn get(&self, var: i32) -> Option<bool> {
+        let index = usize::try_from(var).ok()?.checked_sub(1)?;
+        self.values.get(index).copied().flatten()
+    }
+
+    /// Returns `true` if the variable is assigned
+    pub fn is_assigned(&self, var: i32) -> bool {
+        self.get(var).is_some()
+    }
+
+    /// Assigns a value to a variable
+    ///
+    /// # Panics
+    /// If the variable is not positive.
+    pub fn set(&mut self, var: i32, value: bool) {
+        assert!(var > 0, "variables are positive, got: {}", var);
+        let index = var as usize - 1;
+        if self.values.len() <= index {
+            self.values.resize(index + 1, None);
+        }
+        self.values[index] = Some(value);
+    }
+
+    /// Unassigns a variable, returning its previous value
+    pub fn unassign(&mut self, var: i32) -> Option<bool> {
+        let index = usize::try_from(var).ok()?.checked_sub(1)?;
+        self.values.get_mut(index)?.take()
+    }
+
+    /// Negates the value of a variable, unassigned variables stay unassigned
+    pub fn flip(&mut self, var: i32) {
+        if let Some(value) = self.get(var) {
+            self.values[var as usize - 1] = Some(!value);
+        }
+    }
+
+    /// Returns the value of a literal, or `None` if its variable is unassigned
+    pub fn literal_value(&self, literal: &Literal) -> Option<bool> {
+        self.get(literal.value)
+            .map(|value| value != literal.negated)
+    }
+
+    /// Returns the assigned variables with their values, in increasing order of variables
+    pub fn iter(&self) -> impl Iterator<Item = (i32, bool)> + '_ {
+        self.values
+            .iter()
+            .enumerate()
+            .filter_map(|(index, value)| Some((index as i32 + 1, (*value)?)))
+    }
+
+    /// Returns the number of assigned variables
+    pub fn num_assigned(&self) -> usize {
+        self.values.iter().filter(|value| value.is_some()).count()
+    }
+}
+
+impl From<HashMap<i32, bool>> for Assignment {
+    fn from(interpretation: HashMap<i32, bool>) -> Self {
+        Assignment::from(&interpretation)
+    }
+}
+
+impl From<&HashMap<i32, bool>> for Assignment {
+    fn from(interpretation: &HashMap<i32, bool>) -> Self {
+        let num_vars = interpretation.keys().copied().max().unwrap_or(0).max(0);
+        let mut assignment = Assignment::new(num_vars as usize);
+        for (var, value) in interpretation {
+            assignment.set(*var, *value);
+        }
+        assignment
+    }
+}
+
+impl From<Assignment> for HashMap<i32, bool> {
+    fn from(assignment: Assignment) -> Self {
+        assignment.iter().collect()
+    }
+}
@@ -53,13 +53,17 @@ pub fn parse_cnf(_buffer: &str) -> Result<Formula, String> {
 
             // Iterate over tokens in the line
             for token in line.split_whitespace() {
-                let _lit = token.parse::<i32>().unwrap();
+                let _lit = token
+                    .parse::<i32>()
+                    .map_err(|e| format!("Failed to parse literal {}: {}", token, e))?;
                 if _lit == 0 {
                     // If the token is 0, then it is the end of the clause
                     break;
                 }
                 let literal = Literal {
-                    value: _lit.abs(),
+                    value: _lit
+                        .checked_abs()
+                        .ok_or(format!("Literal out of range: {}", token))?,
                     negated: _lit < 0,
                 };
 
@@ -69,36 +73,26 @@ pub fn parse_cnf(_buffer: &str) -> Result<Formula, String> {
         }
     }
 
-    // Get unique literals from the formula
-    let mut literals: Vec<Literal> = Vec::new();
-    for clause in &clauses {
-        for literal in &clause.literals {
-            if !literals.contains(literal) {
-                literals.push(literal.clone());
-            }
-        }
+    // Collect the unique literals and variables while adding the clauses
+    let mut formula = Formula::new();
+    for clause in clauses {
+        formula.add_clause(clause);
     }
 
-    // Get unique variables from the formula
-    let mut vars: Vec<i32> = Vec::new();
-    for clause in &clauses {
-        for literal in &clause.literals {
-            if !vars.contains(&literal.value) {
-                vars.push(literal.value);
-            }
-        }
+    if num_clauses != formula.num_clauses {
+        return Err(format!(
+            "The header declares {} clauses but the file has {}",
+            num_clauses, formula.num_clauses
+        ));
+    }
+    // The header gives the number of variables, or the largest one if some are unused
+    let max_var = formula.vars.iter().copied().max().unwrap_or(0);
+    if num_vars != formula.num_vars && num_vars != max_var {
+        return Err(format!(
+            "The header declares {} variables but the file has {}",
+            num_vars, formula.num_vars
+        ));
     }
-
-    assert_eq!(num_clauses, clauses.len() as i32);
-    assert_eq!(num_vars, vars.len() as i32);
-
-    let formula: Formula = Formula {
-        clauses,
-        literals,
-        vars,
-        num_clauses,
-        num_vars,
-    };
 
     Ok(formula)
 }
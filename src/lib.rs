@@ -6,11 +6,27 @@
 //! * [`crate::solvers::chaos`] - A solver based on the CHAOS algorithm.
 //! * [`crate::solvers::wsat`] - A solver based on the WSAT algorithm.
 //! * [`crate::solvers::gsat`] - A solver based on the GSAT algorithm.
+//! * [`crate::solvers::probsat`] - A solver based on the probSAT algorithm.
+//! * [`crate::solvers::novelty`] - Solvers based on the Novelty, Novelty+ and AdaptNovelty+ algorithms.
+//! * [`crate::solvers::weighting`] - Clause weighting solvers based on the DDFW and SAPS algorithms.
+//! * [`crate::solvers::ccanr`] - A configuration checking solver based on the CCAnr algorithm.
+//! * [`crate::solvers::annealing`] - A solver based on simulated annealing.
+//! * [`crate::solvers::dpll`] - A complete solver based on the DPLL algorithm.
+//! * [`crate::solvers::cdcl`] - A complete solver based on conflict-driven clause learning.
+//!
+//! Each solver implements the [`crate::solvers::solver::Solver`] trait, and can be built by name
+//! with [`crate::solvers::registry::build_solver`].
 //!
 //! This crate also contains some useful structs for working with propositional variables and formulas, viz:
 //! * [`crate::notation::Formula`] - A struct for working with propositional formulas
 //! * [`crate::notation::Clause`] - A struct for working with propositional clauses
 //! * [`crate::notation::Literal`] - A struct for working with propositional literals (atoms)
+//! * [`crate::notation::Lit`] and [`crate::notation::ClauseArena`] - A compact representation of clauses for large instances
+//! * [`crate::assignment::Assignment`] - A dense assignment of truth values to variables
+//! * [`crate::verify::verify_model`] - An independent check of the models returned by the solvers
+//!
+//! The complete solvers can write DRAT proofs of unsatisfiability, and CDCL also LRAT proofs, see [`crate::proof`].
+//! DRAT proofs from any solver can be checked, and trimmed to an LRAT proof and an unsatisfiable core, with [`crate::proof::drat`].
 //!
 //! # Usage
 //! The crate can be used as a library or as a binary. To use it as a binary, run the following command:
@@ -21,6 +37,17 @@
 //! ```text
 //! sat-rs <CNF_FILE> <SOLVER>
 //! ```
+//! A DRAT proof of unsatisfiability is checked with:
+//! ```text
+//! sat-rs check-proof <CNF_FILE> <PROOF_FILE>
+//! ```
+//! and a solution printed by any SAT solver with:
+//! ```text
+//! sat-rs verify <CNF_FILE> <SOLUTION_FILE>
+//! ```
+pub mod assignment;
 pub mod cnfparser;
 pub mod notation;
+pub mod proof;
 pub mod solvers;
+pub mod verify;
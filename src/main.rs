@@ -1,40 +1,210 @@
-use clap::Parser;
+use clap::error::ErrorKind;
+use clap::{CommandFactory, Parser, Subcommand};
 use clap_verbosity_flag::Verbosity;
+use std::path::{Path, PathBuf};
 
-mod cnfparser;
-mod notation;
-mod solvers;
 mod utils;
 
-use solvers::chaos::chaos_algorithm;
-use solvers::gsat::gsat_algorithm;
-use solvers::interactive::interactive_algorithm;
-use solvers::wsat::wsat_algorithm;
+use sat_rs::cnfparser;
+use sat_rs::notation::Formula;
+use sat_rs::proof::drat::{check_drat, is_binary, parse_drat};
+use sat_rs::proof::lrat::LratStep;
+use sat_rs::proof::{ProofFormat, ProofWriter};
+use sat_rs::solvers::annealing::{AnnealingConfig, Cooling};
+use sat_rs::solvers::branching::Branching;
+use sat_rs::solvers::ccanr::CcanrConfig;
+use sat_rs::solvers::clausedb::ClauseDbConfig;
+use sat_rs::solvers::novelty::parse_noise;
+use sat_rs::solvers::probsat::BreakFunction;
+use sat_rs::solvers::registry::{build_solver, is_randomized, SolverConfig};
+use sat_rs::solvers::restart::Restart;
+use sat_rs::solvers::result::SolveResult;
+use sat_rs::solvers::weighting::{DdfwConfig, SapsConfig};
+use sat_rs::solvers::wsat::WsatMode;
+use sat_rs::verify::{parse_solution, verify_model, verify_solution};
 use utils::read_file;
 
 #[derive(Parser, Debug)]
+#[command(args_conflicts_with_subcommands = true, subcommand_negates_reqs = true)]
 struct Cli {
+    #[command(subcommand)]
+    command: Option<Command>,
+
     // The path to the CNF file
-    path: std::path::PathBuf,
+    #[clap(required = true)]
+    path: Option<PathBuf>,
 
-    // which solver to use
+    // which solver to use (interactive, chaos, wsat, gsat, probsat, novelty, novelty+,
+    // adaptnovelty+, ddfw, saps, ccanr, annealing, dpll or cdcl)
     #[clap(short, long, default_value = "interactive")]
     solver: String,
 
+    // number of random interpretations tried by the local search solvers
+    #[clap(long, default_value_t = 100)]
+    max_tries: u32,
+
+    // number of flips per try of the local search solvers
+    #[clap(long, default_value_t = 100)]
+    max_flips: u32,
+
+    // probability of a greedy flip in GSAT, always greedy if not given
+    #[clap(long)]
+    walk_probability: Option<f32>,
+
+    // variable selection of WalkSAT: skc[:noise], with a noise of 0.5 by default, or random-walk
+    #[clap(long, default_value = "skc")]
+    wsat_mode: WsatMode,
+
+    // number of steps during which a flipped variable is tabu in GSAT and WalkSAT, 0 to disable
+    #[clap(long, default_value_t = 0)]
+    tabu_tenure: u64,
+
+    // break count function of probSAT: poly:cb[:eps] or exp:cb, chosen from the clause width if
+    // not given
+    #[clap(long)]
+    break_function: Option<BreakFunction>,
+
+    // noise of Novelty and Novelty+, AdaptNovelty+ adapts it during the search
+    #[clap(long, default_value_t = 0.5, value_parser = parse_noise)]
+    novelty_noise: f64,
+
+    // weight of every clause at the start of a DDFW try, at least 2
+    #[clap(long, default_value_t = 8.0)]
+    ddfw_initial_weight: f64,
+
+    // probability of a DDFW flip which leaves the weighted score unchanged, between 0 and 1
+    #[clap(long, default_value_t = 0.15)]
+    ddfw_sideways_probability: f64,
+
+    // factor by which SAPS scales the weights of the unsatisfied clauses, greater than 1
+    #[clap(long, default_value_t = 1.3)]
+    saps_alpha: f64,
+
+    // share of its own weight a clause keeps when SAPS smooths the weights, between 0 and 1
+    #[clap(long, default_value_t = 0.8)]
+    saps_rho: f64,
+
+    // probability of SAPS smoothing the weights after scaling them, between 0 and 1
+    #[clap(long, default_value_t = 0.05)]
+    saps_smooth_probability: f64,
+
+    // probability of a SAPS random walk in a local minimum, between 0 and 1
+    #[clap(long, default_value_t = 0.01)]
+    saps_walk_probability: f64,
+
+    // mean clause weight above which CCAnr smooths the weights, at least 1
+    #[clap(long, default_value_t = 50.0)]
+    ccanr_threshold: f64,
+
+    // share of its own weight a clause keeps when CCAnr smooths the weights, between 0 and 1
+    #[clap(long, default_value_t = 0.3)]
+    ccanr_rho: f64,
+
+    // cooling schedule of simulated annealing: geometric, linear or adaptive[:patience], which
+    // reheats after patience steps without a worsening flip nor an improvement (1000 by default)
+    #[clap(long, default_value = "geometric")]
+    cooling: Cooling,
+
+    // temperature of simulated annealing at the start of a try, at least the minimum one
+    #[clap(long, default_value_t = 2.0)]
+    max_temperature: f64,
+
+    // temperature of simulated annealing at the end of a try, greater than 0
+    #[clap(long, default_value_t = 0.05)]
+    min_temperature: f64,
+
+    // seed of the randomized solvers, so that a run can be reproduced, random if not given and
+    // printed as a comment by the randomized solvers either way
+    #[clap(long)]
+    seed: Option<u64>,
+
+    // branching heuristic of the complete solvers (vsids, vmtf or static)
+    #[clap(short, long, default_value = "vsids")]
+    branching: Branching,
+
+    // restart policy of the CDCL solver, with optional colon-separated parameters:
+    // none, luby[:unit], geometric[:initial[:factor]] or glucose[:margin[:min_conflicts]]
+    #[clap(short, long, default_value = "luby")]
+    restart: Restart,
+
+    // number of conflicts before the first reduction of the learnt clauses of the CDCL solver
+    #[clap(long, default_value_t = 2000)]
+    reduce_interval: u64,
+
+    // file to write a DRAT proof to when the formula is unsatisfiable, for the complete solvers
+    #[clap(long)]
+    proof: Option<PathBuf>,
+
+    // write the proof in the binary format instead of the textual one
+    #[clap(long, requires = "proof")]
+    binary_proof: bool,
+
+    // write an LRAT proof, with the antecedents of every clause, instead of a DRAT proof, which
+    // only the CDCL solver can do
+    #[clap(
+        long,
+        requires = "proof",
+        help = "Write an LRAT proof instead of a DRAT proof, only supported by --solver cdcl"
+    )]
+    lrat: bool,
+
+    // print SAT without checking the model against the clauses of the CNF file first
+    #[clap(long)]
+    no_verify: bool,
+
     // verbosity level
     #[command(flatten)]
     verbosity: Verbosity,
 }
 
+#[derive(Subcommand, Debug)]
+enum Command {
+    // check a textual or binary DRAT proof of unsatisfiability of a CNF file
+    CheckProof {
+        // The path to the CNF file
+        cnf: PathBuf,
+
+        // The path to the DRAT proof
+        proof: PathBuf,
+
+        // file to write the checked part of the proof to, in the LRAT format
+        #[clap(long)]
+        lrat: Option<PathBuf>,
+
+        // file to write the clauses the proof depends on to, in the DIMACS format
+        #[clap(long)]
+        core: Option<PathBuf>,
+    },
+
+    // check the model of a solution printed by any SAT solver against a CNF file
+    Verify {
+        // The path to the CNF file
+        cnf: PathBuf,
+
+        // The path to the solution, with an `s SATISFIABLE` line and `v` lines
+        solution: PathBuf,
+    },
+}
+
 fn main() {
     // SAT Solver written in Rust
     // Usage: ./sat-rs <CNF_FILE> <SOLVER>
 
     let args = Cli::parse();
+    match &args.command {
+        Some(Command::CheckProof {
+            cnf,
+            proof,
+            lrat,
+            core,
+        }) => return check_proof(cnf, proof, lrat.as_deref(), core.as_deref()),
+        Some(Command::Verify { cnf, solution }) => return verify(cnf, solution),
+        None => (),
+    }
+    let path = args.path.as_ref().unwrap();
 
     // Check file extension
-    let extension = args
-        .path
+    let extension = path
         .extension()
         .unwrap_or_default()
         .to_str()
@@ -43,18 +213,215 @@ fn main() {
         panic!("File extension must be .cnf, got: {}", extension);
     }
 
-    let buffer: String = read_file(&args.path);
+    let buffer: String = read_file(path);
 
     // Parse the CNF file
-    let formula = cnfparser::parse_cnf(&buffer);
-
-    let result = match args.solver.as_str() {
-        "interactive" => interactive_algorithm(&mut formula.unwrap()),
-        "chaos" => chaos_algorithm(&mut formula.unwrap(), 100),
-        "wsat" => wsat_algorithm(&mut formula.unwrap(), 100, 100),
-        "gsat" => gsat_algorithm(&mut formula.unwrap(), 100, 100, None),
-        &_ => panic!("Unknown solver: {}", args.solver),
+    let formula = cnfparser::parse_cnf(&buffer).unwrap();
+
+    // Draw a seed if none is given, so that it can be reported and the run reproduced
+    let seed = args.seed.unwrap_or_else(rand::random);
+
+    let config = SolverConfig {
+        max_tries: args.max_tries,
+        max_flips: args.max_flips,
+        walk_probability: args.walk_probability,
+        wsat_mode: args.wsat_mode,
+        tabu_tenure: args.tabu_tenure,
+        break_function: args.break_function,
+        novelty_noise: args.novelty_noise,
+        ddfw: DdfwConfig {
+            initial_weight: args.ddfw_initial_weight,
+            sideways_probability: args.ddfw_sideways_probability,
+        },
+        saps: SapsConfig {
+            alpha: args.saps_alpha,
+            rho: args.saps_rho,
+            smooth_probability: args.saps_smooth_probability,
+            walk_probability: args.saps_walk_probability,
+        },
+        ccanr: CcanrConfig {
+            smooth_threshold: args.ccanr_threshold,
+            rho: args.ccanr_rho,
+        },
+        annealing: AnnealingConfig {
+            cooling: args.cooling,
+            max_temperature: args.max_temperature,
+            min_temperature: args.min_temperature,
+        },
+        seed: Some(seed),
+        branching: args.branching,
+        restart: args.restart,
+        clause_db: ClauseDbConfig {
+            reduce_interval: args.reduce_interval,
+            ..Default::default()
+        },
     };
+    let mut solver = build_solver(&args.solver, &config)
+        .unwrap_or_else(|err| Cli::command().error(ErrorKind::ValueValidation, err).exit());
+    if let Some(path) = &args.proof {
+        let format = match (args.lrat, args.binary_proof) {
+            (false, false) => ProofFormat::Drat,
+            (false, true) => ProofFormat::BinaryDrat,
+            (true, false) => ProofFormat::Lrat,
+            (true, true) => ProofFormat::BinaryLrat,
+        };
+        let proof = ProofWriter::create(path, format)
+            .unwrap_or_else(|err| panic!("couldn't create {}: {}", path.display(), err));
+        if !solver.set_proof(proof) {
+            // The proof file is already created, do not leave it behind empty
+            let _ = std::fs::remove_file(path);
+            let kind = if format.is_lrat() { "LRAT" } else { "DRAT" };
+            let message = format!("solver {} cannot write {} proofs", args.solver, kind);
+            Cli::command()
+                .error(ErrorKind::ArgumentConflict, message)
+                .exit();
+        }
+    }
+    if is_randomized(&args.solver) {
+        println!("c seed: {}", seed);
+    }
+    solver.add_formula(&formula);
+    let result = solver.solve();
+    if let Err(err) = solver.finish_proof() {
+        panic!("couldn't write the proof: {}", err);
+    }
+
+    // Report the work done by the solver when asked to be verbose
+    if args.verbosity.is_present() && !args.verbosity.is_silent() {
+        println!("{}", solver.stats());
+    }
 
-    println!("Result: {}", result);
+    // Check the model against the clauses as parsed, so that a wrong model is never printed
+    if let SolveResult::Sat(model) = &result {
+        if !args.no_verify {
+            if let Err(falsified) = verify_model(&formula, model) {
+                panic!(
+                    "Solver {} returned a wrong model, {}",
+                    args.solver, falsified
+                );
+            }
+        }
+    }
+
+    // Print the result in the SAT competition output format
+    println!("s {}", result);
+    match &result {
+        SolveResult::Sat(model) => println!("{}", model),
+        SolveResult::Unknown(reason) => println!("c {}", reason),
+        SolveResult::Unsat => (),
+    }
+}
+
+/// Checks a DRAT proof of unsatisfiability, printing VERIFIED or exiting with a failure
+///
+/// # Arguments
+/// * `cnf` - The path to the CNF file
+/// * `proof` - The path to the DRAT proof, textual or binary
+/// * `lrat` - The path to write the trimmed proof to, if any
+/// * `core` - The path to write the unsatisfiable core to, if any
+fn check_proof(cnf: &PathBuf, proof: &PathBuf, lrat: Option<&Path>, core: Option<&Path>) {
+    let formula = cnfparser::parse_cnf(&read_file(cnf))
+        .unwrap_or_else(|err| not_verified(format!("couldn't parse {}: {}", cnf.display(), err)));
+    let bytes = std::fs::read(proof)
+        .unwrap_or_else(|err| not_verified(format!("couldn't read {}: {}", proof.display(), err)));
+
+    let trimmed = match parse_drat(&bytes, is_binary(&bytes))
+        .and_then(|steps| check_drat(&formula, &steps))
+    {
+        Ok(trimmed) => trimmed,
+        Err(err) => not_verified(err),
+    };
+
+    if let Some(path) = lrat {
+        let mut out = ProofWriter::create(path, ProofFormat::Lrat)
+            .unwrap_or_else(|err| panic!("couldn't create {}: {}", path.display(), err));
+        for step in &trimmed.lrat {
+            match step {
+                LratStep::Add { id, clause, hints } => out.add(*id, clause, hints),
+                LratStep::Delete { ids } => ids.iter().for_each(|id| out.delete(*id, &[])),
+            }
+        }
+        if let Err(err) = out.finish() {
+            panic!("couldn't write {}: {}", path.display(), err);
+        }
+    }
+    if let Some(path) = core {
+        let mut core_formula = Formula::new();
+        for index in &trimmed.core {
+            core_formula.add_clause(formula.clauses[*index].clone());
+        }
+        if let Err(err) = std::fs::write(path, dimacs(&core_formula)) {
+            panic!("couldn't write {}: {}", path.display(), err);
+        }
+    }
+
+    println!(
+        "c core: {} of {} clauses",
+        trimmed.core.len(),
+        formula.clauses.len()
+    );
+    println!("s VERIFIED");
+}
+
+/// Checks the model of a solution, printing VERIFIED or exiting with a failure
+///
+/// # Arguments
+/// * `cnf` - The path to the CNF file
+/// * `solution` - The path to the solution, in the SAT competition output format
+fn verify(cnf: &PathBuf, solution: &PathBuf) {
+    let formula = cnfparser::parse_cnf(&read_file(cnf))
+        .unwrap_or_else(|err| not_verified(format!("couldn't parse {}: {}", cnf.display(), err)));
+
+    let literals = match parse_solution(&read_file(solution)) {
+        Ok(literals) => literals,
+        Err(err) => not_verified(err),
+    };
+
+    let report = verify_solution(&formula, &literals);
+    for falsified in &report.falsified {
+        println!("c {}", falsified);
+    }
+    for var in &report.unassigned {
+        println!("c variable {} is unassigned", var);
+    }
+    for var in &report.contradictory {
+        println!("c variable {} is both true and false", var);
+    }
+    if !report.is_valid() {
+        println!("s NOT VERIFIED");
+        std::process::exit(1);
+    }
+    println!("s VERIFIED");
+}
+
+/// Prints why a check failed and NOT VERIFIED, and exits with a failure
+fn not_verified(reason: impl std::fmt::Display) -> ! {
+    println!("c {}", reason);
+    println!("s NOT VERIFIED");
+    std::process::exit(1);
+}
+
+/// Returns a formula in the DIMACS CNF format
+fn dimacs(formula: &Formula) -> String {
+    // The header gives the largest variable, which the core may use without using all below it
+    let max_var = formula
+        .clauses
+        .iter()
+        .flat_map(|clause| &clause.literals)
+        .map(|literal| literal.value)
+        .max()
+        .unwrap_or(0);
+    let mut text = format!("p cnf {} {}\n", max_var, formula.num_clauses);
+    for clause in &formula.clauses {
+        for literal in &clause.literals {
+            let lit = if literal.negated {
+                -literal.value
+            } else {
+                literal.value
+            };
+            text.push_str(&format!("{} ", lit));
+        }
+        text.push_str("0\n");
+    }
+    text
 }
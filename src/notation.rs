@@ -3,7 +3,14 @@
 //! * [`Literal`] - A struct representing a literal (atom)
 //! * [`Clause`] - A struct representing a clause
 //! * [`Formula`] - A struct representing a propositional formula
-use std::collections::HashMap;
+//!
+//! Solvers working on large instances use a compact representation instead, for which
+//! [`Formula`] is a convenience layer:
+//!
+//! * [`Lit`] - A literal packed into a single [`u32`]
+//! * [`ClauseArena`] - Clauses stored contiguously, referred to by [`ClauseRef`]
+use crate::assignment::Assignment;
+use std::ops::Not;
 
 /// Struct representing a Literal.
 ///
@@ -37,10 +44,10 @@ impl Literal {
     /// ```
     #[allow(dead_code)]
     pub fn new() -> Literal {
-        return Literal {
+        Literal {
             value: 0,
             negated: false,
-        };
+        }
     }
 
     /// Creates a new [`Literal`] from a given value. Negated is set to `false`
@@ -56,10 +63,10 @@ impl Literal {
     /// ```
     #[allow(dead_code)]
     pub fn from_value(value: i32) -> Literal {
-        return Literal {
+        Literal {
             value,
             negated: false,
-        };
+        }
     }
 
     /// Evaluates a [`Literal`] given a boolean value
@@ -71,26 +78,34 @@ impl Literal {
     /// ```rust
     /// use sat_rs::notation::Literal;
     ///
-    /// let mut literal = Literal{ value: 1, negated: false};
+    /// let literal = Literal{ value: 1, negated: false};
     /// assert_eq!(literal.evaluate(true), true);
     ///
-    /// let mut literal = Literal{ value: 1, negated: true};
+    /// let literal = Literal{ value: 1, negated: true};
     /// assert_eq!(literal.evaluate(true), false);
     /// ```
-    pub fn evaluate(&mut self, given_value: bool) -> bool {
+    pub fn evaluate(&self, given_value: bool) -> bool {
         if self.negated {
-            return !given_value;
+            !given_value
         } else {
-            return given_value;
+            given_value
         }
     }
 }
 
+impl Default for Literal {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 /// Struct representing a Clause
 ///
 /// Derives from [`Debug`] and [`Clone`].
 ///
-/// Contains a vector of [`Literal`]s
+/// Contains a vector of [`Literal`]s and whether the clause is learnt. Learnt (redundant) clauses
+/// are implied by the other clauses of a formula and may be deleted by a solver, while
+/// irredundant clauses are part of the problem.
 ///
 /// # Examples
 /// ```rust
@@ -108,11 +123,11 @@ impl Literal {
 #[derive(Debug, Clone)]
 pub struct Clause {
     pub literals: Vec<Literal>,
-    pub is_satisfied: bool,
+    pub learnt: bool,
 }
 
 impl Clause {
-    /// Creates a new [`Clause`] with an empty vector of [`Literal`]s
+    /// Creates a new irredundant [`Clause`] with an empty vector of [`Literal`]s
     ///
     /// # Examples
     /// ```rust
@@ -123,61 +138,67 @@ impl Clause {
     pub fn new() -> Clause {
         Clause {
             literals: Vec::new(),
-            is_satisfied: false,
+            learnt: false,
         }
     }
 
-    /// Evaluates a [`Clause`] given an interpretation
+    /// Evaluates a [`Clause`] given a possibly partial interpretation
+    ///
+    /// Uses Kleene's three-valued logic: the clause is true if one of its literals is true, false
+    /// if all of its literals are false, and undetermined otherwise. The empty clause is false.
     ///
     /// # Arguments
-    /// * `interpretation` - A [`HashMap`] of [`i32`] and [`bool`] representing the interpretation
+    /// * `interpretation` - An [`Assignment`] representing the interpretation
     ///
     /// # Returns
-    /// * `bool` - Returns `true` if the clause is true in the `interpretation`, otherwise `false`
+    /// * `Option<bool>` - The value of the clause, or `None` if it is undetermined
     ///
     /// # Examples
     /// ```rust
+    /// use sat_rs::assignment::Assignment;
     /// use sat_rs::notation::{Literal, Clause};
-    /// use std::collections::HashMap;
     ///
     /// let mut clause = Clause::new();
-    /// let p = Literal{ value: 1, negated: false};
-    /// clause.literals.push(p); // Clause: p
+    /// let p = Literal{ value: 1, negated: true};
+    /// let q = Literal{ value: 2, negated: false};
+    /// clause.literals.push(p);
+    /// clause.literals.push(q); // Clause: -p v q
     ///
-    /// let mut interpretation: HashMap<i32, bool> = HashMap::new();
-    /// interpretation.insert(1, true);
+    /// let mut interpretation = Assignment::new(2);
+    /// assert_eq!(clause.evaluate(&interpretation), None);
     ///
-    /// assert_eq!(clause.evaluate(&interpretation), true);
+    /// interpretation.set(1, true);
+    /// assert_eq!(clause.evaluate(&interpretation), None);
     ///
-    /// let mut clause = Clause::new();
-    /// let p = Literal{ value: 1, negated: true};
-    /// let q = Literal{ value: 1, negated: false};
-    /// clause.literals.push(p);
-    /// clause.literals.push(q); // Clause: -p v p
+    /// interpretation.set(2, false);
+    /// assert_eq!(clause.evaluate(&interpretation), Some(false));
     ///
-    /// assert_eq!(clause.evaluate(&interpretation), false);
+    /// interpretation.set(1, false);
+    /// assert_eq!(clause.evaluate(&interpretation), Some(true));
     /// ```
-    pub fn evaluate(&mut self, interpretation: &HashMap<i32, bool>) -> bool {
-        // TODO(SauravMaheshkar): Remove monkeypatch
-        let mut temp = None;
-        let mut clausal_value: bool = false;
-
-        for literal in &mut self.literals {
-            // Disjunction of Literals
-            if temp == None {
-                clausal_value = literal.evaluate(interpretation[&literal.value]);
-                temp = Some(clausal_value);
-            } else if temp == Some(true) {
-                clausal_value = clausal_value || literal.evaluate(interpretation[&literal.value]);
+    pub fn evaluate(&self, interpretation: &Assignment) -> Option<bool> {
+        let mut undetermined = false;
+
+        // Disjunction of Literals
+        for literal in &self.literals {
+            match interpretation.literal_value(literal) {
+                Some(true) => return Some(true),
+                Some(false) => (),
+                None => undetermined = true,
             }
         }
 
-        // If clause is satisfied, set is_satisfied to true
-        if clausal_value {
-            self.is_satisfied = true;
+        if undetermined {
+            None
+        } else {
+            Some(false)
         }
+    }
+}
 
-        return clausal_value;
+impl Default for Clause {
+    fn default() -> Self {
+        Self::new()
     }
 }
 
@@ -194,8 +215,15 @@ pub struct Formula {
     pub vars: Vec<i32>,
     pub num_clauses: i32,
     pub num_vars: i32,
+    /// Flags of the literals added so far, indexed by variable
+    occurs: Vec<u8>,
 }
 
+/// Flag of a variable whose positive literal occurs in the formula
+const POSITIVE: u8 = 1;
+/// Flag of a variable whose negative literal occurs in the formula
+const NEGATIVE: u8 = 2;
+
 impl Formula {
     /// Creates a new [`Formula`] with an empty vector of [`Clause`]s and [`Literal`]s
     ///
@@ -213,16 +241,64 @@ impl Formula {
             vars: Vec::new(),
             num_clauses: 0,
             num_vars: 0,
+            occurs: Vec::new(),
+        }
+    }
+
+    /// Appends a [`Clause`] to the [`Formula`], keeping its literals and variables up to date
+    ///
+    /// Literals and variables are looked up in a table indexed by variable, so adding a clause
+    /// takes time linear in its length.
+    ///
+    /// # Arguments
+    /// * `clause` - The [`Clause`] to add
+    ///
+    /// # Examples
+    /// ```rust
+    /// use sat_rs::notation::{Literal, Clause, Formula};
+    ///
+    /// let mut clause = Clause::new();
+    /// clause.literals.push(Literal{ value: 2, negated: true});
+    ///
+    /// let mut formula = Formula::new();
+    /// formula.add_clause(clause.clone());
+    /// formula.add_clause(clause);
+    ///
+    /// assert_eq!(formula.vars, vec![2]);
+    /// assert_eq!(formula.literals.len(), 1);
+    /// assert_eq!(formula.num_clauses, 2);
+    /// assert_eq!(formula.num_vars, 1);
+    /// ```
+    pub fn add_clause(&mut self, clause: Clause) {
+        for literal in &clause.literals {
+            let var = literal.value as usize;
+            if var >= self.occurs.len() {
+                self.occurs.resize(var + 1, 0);
+            }
+            let flag = if literal.negated { NEGATIVE } else { POSITIVE };
+            if self.occurs[var] == 0 {
+                self.vars.push(literal.value);
+            }
+            if self.occurs[var] & flag == 0 {
+                self.occurs[var] |= flag;
+                self.literals.push(literal.clone());
+            }
         }
+        self.clauses.push(clause);
+        self.num_clauses = self.clauses.len() as i32;
+        self.num_vars = self.vars.len() as i32;
     }
 
-    /// Evaluates a [`Formula`] given an interpretation
+    /// Evaluates a [`Formula`] given a possibly partial interpretation
+    ///
+    /// Uses Kleene's three-valued logic: the formula is false if one of its clauses is false, true
+    /// if all of its clauses are true, and undetermined otherwise. The empty formula is true.
     ///
     /// # Arguments
-    /// * `interpretation` - A [`HashMap`] of [`i32`] and [`bool`] representing the interpretation
+    /// * `interpretation` - An [`Assignment`] representing the interpretation
     ///
     /// # Returns
-    /// * `bool` - Returns `true` if the formula is true in the `interpretation`, otherwise `false`
+    /// * `Option<bool>` - The value of the formula, or `None` if it is undetermined
     ///
     /// # Examples
     /// Assuming the CNF file is in `/bin/problem.cnf` and contains the following:
@@ -233,58 +309,93 @@ impl Formula {
     /// ```
     ///
     /// ```rust
+    /// use sat_rs::assignment::Assignment;
     /// use sat_rs::notation::{Literal, Clause, Formula};
-    /// use std::collections::HashMap;
     ///
     /// let mut clause = Clause::new();
     /// let p = Literal{ value: 1, negated: false};
     /// clause.literals.push(p);
     ///
-    /// let mut formula = Formula {
-    ///   clauses: vec![clause],
-    ///   literals: vec![Literal{ value: 1, negated: false}],
-    ///   vars: vec![1],
-    ///   num_clauses: 1,
-    ///   num_vars: 1,
-    /// };
+    /// let mut formula = Formula::new();
+    /// formula.add_clause(clause);
     ///
-    /// let mut interpretation: HashMap<i32, bool> = HashMap::new();
-    /// interpretation.insert(1, true);
+    /// let mut interpretation = Assignment::new(1);
+    /// assert_eq!(formula.evaluate(&interpretation), None);
     ///
-    /// assert_eq!(formula.evaluate(&interpretation), true);
+    /// interpretation.set(1, true);
+    /// assert_eq!(formula.evaluate(&interpretation), Some(true));
     /// ```
-    pub fn evaluate(&mut self, interpretation: &HashMap<i32, bool>) -> bool {
-        // TODO(SauravMaheshkar): Remove monkeypatch
-        let mut temp = None;
-        let mut value = false;
-
-        // Evaluate the formula
-        for clause in &mut self.clauses {
-            // Conjunction of Clauses
-            if temp == None {
-                value = clause.evaluate(interpretation);
-                temp = Some(value);
-            } else if temp == Some(true) {
-                value = value && clause.evaluate(interpretation);
+    pub fn evaluate(&self, interpretation: &Assignment) -> Option<bool> {
+        let mut undetermined = false;
+
+        // Conjunction of Clauses
+        for clause in &self.clauses {
+            match clause.evaluate(interpretation) {
+                Some(true) => (),
+                Some(false) => return Some(false),
+                None => undetermined = true,
             }
         }
-        return value;
+
+        if undetermined {
+            None
+        } else {
+            Some(true)
+        }
     }
 
-    /// Returns a vector of unsatisfied clauses
+    /// Returns the indices of the clauses which are not true under an interpretation
+    ///
+    /// These are the clauses which are either false or undetermined, so under a total
+    /// interpretation they are exactly the falsified clauses.
     ///
-    /// This is intended to be used after the formula has been evaluated
+    /// # Arguments
+    /// * `interpretation` - An [`Assignment`] representing the interpretation
     ///
     /// # Returns
-    /// * [`Vec<Clause>`] - A vector of unsatisfied clauses
-    pub fn get_unsatisfied_clauses(&mut self) -> Vec<Clause> {
-        let mut unsatisfied_clauses: Vec<Clause> = Vec::new();
-        for clause in &self.clauses {
-            if !clause.is_satisfied {
-                unsatisfied_clauses.push(clause.clone());
-            }
-        }
-        return unsatisfied_clauses;
+    /// * [`Vec<usize>`] - The indices of the unsatisfied clauses in [`Formula::clauses`]
+    ///
+    /// # Examples
+    /// ```rust
+    /// use sat_rs::assignment::Assignment;
+    /// use sat_rs::notation::{Literal, Clause, Formula};
+    ///
+    /// let mut formula = Formula::new();
+    /// for value in [1, 2] {
+    ///     let mut clause = Clause::new();
+    ///     clause.literals.push(Literal{ value, negated: false});
+    ///     formula.add_clause(clause);
+    /// }
+    ///
+    /// let mut interpretation = Assignment::new(2);
+    /// interpretation.set(1, false);
+    ///
+    /// assert_eq!(formula.unsatisfied_clauses(&interpretation), vec![0, 1]);
+    /// assert_eq!(formula.falsified_clauses(&interpretation), vec![0]);
+    /// ```
+    pub fn unsatisfied_clauses(&self, interpretation: &Assignment) -> Vec<usize> {
+        self.clauses
+            .iter()
+            .enumerate()
+            .filter(|(_, clause)| clause.evaluate(interpretation) != Some(true))
+            .map(|(index, _)| index)
+            .collect()
+    }
+
+    /// Returns the indices of the clauses which are false under an interpretation
+    ///
+    /// # Arguments
+    /// * `interpretation` - An [`Assignment`] representing the interpretation
+    ///
+    /// # Returns
+    /// * [`Vec<usize>`] - The indices of the falsified clauses in [`Formula::clauses`]
+    pub fn falsified_clauses(&self, interpretation: &Assignment) -> Vec<usize> {
+        self.clauses
+            .iter()
+            .enumerate()
+            .filter(|(_, clause)| clause.evaluate(interpretation) == Some(false))
+            .map(|(index, _)| index)
+            .collect()
     }
 
     /// Returns a vector of variables in a clause
@@ -294,7 +405,7 @@ impl Formula {
     ///
     /// # Returns
     /// * [`Vec<i32>`] - A vector of variables in the clause
-    pub fn get_clausal_variables(&mut self, clause: &Clause) -> Vec<i32> {
+    pub fn get_clausal_variables(&self, clause: &Clause) -> Vec<i32> {
         let mut clausal_variables: Vec<i32> = Vec::new();
 
         for var in &self.vars {
@@ -305,6 +416,306 @@ impl Formula {
             }
         }
 
-        return clausal_variables;
+        clausal_variables
+    }
+}
+
+impl Default for Formula {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// A literal packed into a [`u32`] as `2 * var + negated`
+///
+/// Variables are zero-based indices, the DIMACS variable `v` has index `v - 1`. A literal and its
+/// negation differ only in the lowest bit, so literals can directly index per-literal arrays such
+/// as watch lists.
+///
+/// # Examples
+/// ```rust
+/// use sat_rs::notation::{Lit, Literal};
+///
+/// let lit = Lit::from_dimacs(-3);
+/// assert_eq!(lit.var(), 2);
+/// assert!(lit.is_negated());
+/// assert_eq!(lit.index(), 5);
+/// assert_eq!(!lit, Lit::new(2, false));
+/// assert_eq!(lit.to_dimacs(), -3);
+///
+/// let literal = Literal{ value: 3, negated: true};
+/// assert_eq!(Lit::from(&literal), lit);
+/// assert_eq!(Literal::from(lit), literal);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Lit(u32);
+
+impl Lit {
+    /// Creates the literal of a zero-based variable index
+    pub fn new(var: usize, negated: bool) -> Lit {
+        Lit(2 * var as u32 + negated as u32)
+    }
+
+    /// Creates a literal from a non-zero signed DIMACS literal
+    pub fn from_dimacs(lit: i32) -> Lit {
+        Lit::new(lit.unsigned_abs() as usize - 1, lit < 0)
+    }
+
+    /// Returns the signed DIMACS literal
+    pub fn to_dimacs(self) -> i32 {
+        let var = self.var() as i32 + 1;
+        if self.is_negated() {
+            -var
+        } else {
+            var
+        }
+    }
+
+    /// Returns the zero-based index of the variable
+    pub fn var(self) -> usize {
+        (self.0 >> 1) as usize
+    }
+
+    /// Returns `true` if the literal is the negation of its variable
+    pub fn is_negated(self) -> bool {
+        self.0 & 1 == 1
+    }
+
+    /// Returns the packed encoding, for indexing per-literal arrays
+    pub fn index(self) -> usize {
+        self.0 as usize
+    }
+}
+
+impl Not for Lit {
+    type Output = Lit;
+
+    fn not(self) -> Lit {
+        Lit(self.0 ^ 1)
+    }
+}
+
+impl From<&Literal> for Lit {
+    fn from(literal: &Literal) -> Self {
+        Lit::new(literal.value as usize - 1, literal.negated)
+    }
+}
+
+impl From<Lit> for Literal {
+    fn from(lit: Lit) -> Self {
+        Literal {
+            value: lit.var() as i32 + 1,
+            negated: lit.is_negated(),
+        }
+    }
+}
+
+/// Reference to a clause in a [`ClauseArena`]
+///
+/// References stay valid until the clause is deleted and the garbage of the arena is collected,
+/// see [`ClauseArena::collect_garbage`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ClauseRef(u32);
+
+impl ClauseRef {
+    /// Creates a reference from the position returned by [`ClauseRef::index`]
+    pub fn new(index: usize) -> ClauseRef {
+        ClauseRef(index as u32)
+    }
+
+    /// Returns the position of the clause in the arena, usable as a key
+    pub fn index(self) -> usize {
+        self.0 as usize
+    }
+}
+
+/// Header flag of a deleted clause
+const DELETED: u32 = 1;
+/// Header flag of a learnt clause
+const LEARNT: u32 = 2;
+/// Number of header bits used by flags, the remaining bits hold the length
+const FLAG_BITS: u32 = 2;
+
+/// Clauses stored one after the other in a single vector
+///
+/// Every clause is a header word holding its length and flags, followed by its literals. Deleting
+/// a clause only marks it, the space it occupies is reclaimed by
+/// [`ClauseArena::collect_garbage`], which moves the remaining clauses to the front and reports
+/// their new references.
+///
+/// # Examples
+/// ```rust
+/// use sat_rs::notation::{ClauseArena, Lit};
+///
+/// let mut arena = ClauseArena::new();
+/// let a = arena.alloc(&[Lit::from_dimacs(1), Lit::from_dimacs(-2)], false);
+/// let b = arena.alloc(&[Lit::from_dimacs(2), Lit::from_dimacs(3)], true);
+/// let c = arena.alloc(&[Lit::from_dimacs(-1), Lit::from_dimacs(-3)], false);
+/// assert_eq!(arena.lits(b), &[Lit::from_dimacs(2), Lit::from_dimacs(3)]);
+/// assert!(arena.is_learnt(b));
+///
+/// arena.delete(b);
+/// assert_eq!(arena.iter().collect::<Vec<_>>(), vec![a, c]);
+/// assert_eq!(arena.wasted(), 3);
+///
+/// let relocation = arena.collect_garbage();
+/// assert_eq!(relocation.get(b), None);
+/// let c = relocation.get(c).unwrap();
+/// assert_eq!(arena.lits(c), &[Lit::from_dimacs(-1), Lit::from_dimacs(-3)]);
+/// assert_eq!(arena.wasted(), 0);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct ClauseArena {
+    /// Headers and literals, headers are stored as raw words in the literal storage
+    data: Vec<Lit>,
+    /// Number of words occupied by deleted clauses
+    wasted: usize,
+    /// Number of clauses which are not deleted
+    num_clauses: usize,
+}
+
+impl ClauseArena {
+    /// Creates an empty [`ClauseArena`]
+    pub fn new() -> ClauseArena {
+        ClauseArena::default()
+    }
+
+    /// Stores a clause and returns its reference
+    pub fn alloc(&mut self, lits: &[Lit], learnt: bool) -> ClauseRef {
+        let cref = ClauseRef(self.data.len() as u32);
+        let flags = if learnt { LEARNT } else { 0 };
+        self.data
+            .push(Lit((lits.len() as u32) << FLAG_BITS | flags));
+        self.data.extend_from_slice(lits);
+        self.num_clauses += 1;
+        cref
+    }
+
+    fn header(&self, cref: ClauseRef) -> u32 {
+        self.data[cref.index()].0
+    }
+
+    /// Returns the number of literals of a clause
+    pub fn clause_len(&self, cref: ClauseRef) -> usize {
+        (self.header(cref) >> FLAG_BITS) as usize
+    }
+
+    /// Returns the literals of a clause
+    pub fn lits(&self, cref: ClauseRef) -> &[Lit] {
+        let start = cref.index() + 1;
+        &self.data[start..start + self.clause_len(cref)]
+    }
+
+    /// Returns the literals of a clause for reordering
+    pub fn lits_mut(&mut self, cref: ClauseRef) -> &mut [Lit] {
+        let start = cref.index() + 1;
+        let end = start + self.clause_len(cref);
+        &mut self.data[start..end]
+    }
+
+    /// Returns `true` if the clause is learnt
+    pub fn is_learnt(&self, cref: ClauseRef) -> bool {
+        self.header(cref) & LEARNT != 0
+    }
+
+    /// Returns `true` if the clause has been deleted
+    pub fn is_deleted(&self, cref: ClauseRef) -> bool {
+        self.header(cref) & DELETED != 0
+    }
+
+    /// Marks a clause as deleted, its space is reclaimed by the next garbage collection
+    pub fn delete(&mut self, cref: ClauseRef) {
+        if self.is_deleted(cref) {
+            return;
+        }
+        self.data[cref.index()].0 |= DELETED;
+        self.wasted += 1 + self.clause_len(cref);
+        self.num_clauses -= 1;
+    }
+
+    /// Returns the number of clauses which are not deleted
+    pub fn len(&self) -> usize {
+        self.num_clauses
+    }
+
+    /// Returns `true` if the arena holds no clause which is not deleted
+    pub fn is_empty(&self) -> bool {
+        self.num_clauses == 0
+    }
+
+    /// Returns the number of words used by the arena, including deleted clauses
+    pub fn size(&self) -> usize {
+        self.data.len()
+    }
+
+    /// Returns the number of words occupied by deleted clauses
+    pub fn wasted(&self) -> usize {
+        self.wasted
+    }
+
+    /// Returns the references of the clauses which are not deleted, in order of allocation
+    pub fn iter(&self) -> impl Iterator<Item = ClauseRef> + '_ {
+        let mut position = 0;
+        std::iter::from_fn(move || {
+            while position < self.data.len() {
+                let cref = ClauseRef(position as u32);
+                position += 1 + self.clause_len(cref);
+                if !self.is_deleted(cref) {
+                    return Some(cref);
+                }
+            }
+            None
+        })
+    }
+
+    /// Removes the deleted clauses, moving the others to the front of the arena
+    ///
+    /// The order of the clauses is kept. References held elsewhere must be updated with the
+    /// returned [`Relocation`].
+    pub fn collect_garbage(&mut self) -> Relocation {
+        let mut forward: Vec<u32> = vec![u32::MAX; self.data.len()];
+        let mut read = 0;
+        let mut write = 0;
+
+        while read < self.data.len() {
+            let len = 1 + self.clause_len(ClauseRef(read as u32));
+            if !self.is_deleted(ClauseRef(read as u32)) {
+                forward[read] = write as u32;
+                self.data.copy_within(read..read + len, write);
+                write += len;
+            }
+            read += len;
+        }
+
+        self.data.truncate(write);
+        self.wasted = 0;
+        Relocation { forward }
+    }
+}
+
+/// New references of the clauses moved by [`ClauseArena::collect_garbage`]
+#[derive(Debug, Clone)]
+pub struct Relocation {
+    forward: Vec<u32>,
+}
+
+impl Relocation {
+    /// Returns the new reference of a clause, or `None` if it was deleted
+    pub fn get(&self, cref: ClauseRef) -> Option<ClauseRef> {
+        match self.forward.get(cref.index()) {
+            Some(&index) if index != u32::MAX => Some(ClauseRef(index)),
+            _ => None,
+        }
+    }
+}
+
+impl From<&Formula> for ClauseArena {
+    fn from(formula: &Formula) -> Self {
+        let mut arena = ClauseArena::new();
+        for clause in &formula.clauses {
+            let lits: Vec<Lit> = clause.literals.iter().map(Lit::from).collect();
+            arena.alloc(&lits, clause.learnt);
+        }
+        arena
     }
 }
@@ -0,0 +1,528 @@
+//! # DRAT Checker
+//!
+//! Checks DRAT proofs of unsatisfiability against a [`Formula`], in the manner of DRAT-trim. The
+//! proof is first replayed up to the empty clause, then checked backwards from it: only the
+//! lemmas which a later check depended on are checked, each against the clauses which were not
+//! deleted before it. A lemma is accepted if assuming the negation of its literals leads to a
+//! conflict by unit propagation (RUP), or if it is a resolution asymmetric tautology (RAT) on
+//! its first literal `p`, every resolvent with a clause containing `-p` being RUP.
+//!
+//! Unit propagation prefers the clauses already known to be needed (core-first), and only falls
+//! back to the other clauses once those are exhausted, so that the checks depend on as few
+//! clauses as possible. The clauses which were depended on give an unsatisfiable core of the
+//! formula, and the checked lemmas with the clauses they depended on give an LRAT proof, which
+//! [`crate::proof::lrat::check_lrat`] checks without search.
+//!
+//! ## Contents
+//!
+//! * [`DratStep`] - An addition or deletion of a DRAT proof.
+//! * [`parse_drat`] - Parses a textual or binary DRAT proof.
+//! * [`is_binary`] - Tells binary proofs from textual ones.
+//! * [`TrimmedProof`] - The unsatisfiable core and LRAT proof of a verified DRAT proof.
+//! * [`check_drat`] - Checks a DRAT proof against a formula.
+use crate::notation::{Formula, Lit};
+use crate::proof::lrat::LratStep;
+use crate::proof::{decode, dimacs_clauses, ProofError};
+use std::collections::HashMap;
+
+/// A step of a DRAT proof
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DratStep {
+    /// Adds a lemma
+    Add(Vec<i32>),
+    /// Deletes a clause
+    Delete(Vec<i32>),
+}
+
+/// Parses a DRAT proof
+///
+/// # Arguments
+/// * `input` - The contents of the proof file
+/// * `binary` - `true` if the proof is in the binary DRAT format, see [`is_binary`]
+///
+/// # Returns
+/// * [`Vec<DratStep>`] - The steps of the proof, or the step which could not be parsed
+///
+/// # Examples
+/// ```rust
+/// use sat_rs::proof::drat::{parse_drat, DratStep};
+///
+/// let steps = parse_drat(b"1 -2 0\nd 1 2 0\n", false).unwrap();
+/// assert_eq!(steps, vec![DratStep::Add(vec![1, -2]), DratStep::Delete(vec![1, 2])]);
+///
+/// let steps = parse_drat(&[b'a', 2, 5, 0, b'd', 2, 4, 0], true).unwrap();
+/// assert_eq!(steps, vec![DratStep::Add(vec![1, -2]), DratStep::Delete(vec![1, 2])]);
+/// ```
+pub fn parse_drat(input: &[u8], binary: bool) -> Result<Vec<DratStep>, ProofError> {
+    if binary {
+        parse_binary(input)
+    } else {
+        parse_text(input)
+    }
+}
+
+/// Returns `true` if a proof is in the binary format, which ends every step with a zero byte
+/// that never occurs in a textual proof
+pub fn is_binary(input: &[u8]) -> bool {
+    input.contains(&0)
+}
+
+fn parse_text(input: &[u8]) -> Result<Vec<DratStep>, ProofError> {
+    let text = String::from_utf8_lossy(input);
+    let mut tokens = text
+        .lines()
+        .filter(|line| !line.trim_start().starts_with('c'))
+        .flat_map(str::split_whitespace)
+        .peekable();
+    let mut steps = Vec::new();
+
+    while tokens.peek().is_some() {
+        let delete = tokens.next_if_eq(&"d").is_some();
+        let mut clause = Vec::new();
+        loop {
+            let token = tokens.next().ok_or_else(|| {
+                ProofError::new(steps.len() + 1, "the last clause does not end with 0")
+            })?;
+            let lit: i32 = token.parse().map_err(|_| {
+                ProofError::new(steps.len() + 1, format!("invalid literal: {}", token))
+            })?;
+            if lit == 0 {
+                break;
+            }
+            clause.push(lit);
+        }
+        steps.push(if delete {
+            DratStep::Delete(clause)
+        } else {
+            DratStep::Add(clause)
+        });
+    }
+
+    Ok(steps)
+}
+
+fn parse_binary(input: &[u8]) -> Result<Vec<DratStep>, ProofError> {
+    let mut steps = Vec::new();
+    let mut position = 0;
+
+    while position < input.len() {
+        let error = |message: String| ProofError::new(steps.len() + 1, message);
+        let kind = input[position];
+        position += 1;
+        if kind != b'a' && kind != b'd' {
+            return Err(error(format!("unknown step kind {:#04x}", kind)));
+        }
+
+        let mut clause = Vec::new();
+        loop {
+            match decode(input, &mut position) {
+                Some(0) => break,
+                Some(lit) => clause.push(lit as i32),
+                None => return Err(error("the last clause does not end with 0".to_string())),
+            }
+        }
+        steps.push(if kind == b'a' {
+            DratStep::Add(clause)
+        } else {
+            DratStep::Delete(clause)
+        });
+    }
+
+    Ok(steps)
+}
+
+/// What remains of a verified DRAT proof once the steps which the refutation does not depend on
+/// are dropped
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TrimmedProof {
+    /// Indices of the clauses of the formula which the refutation depends on, in order, which
+    /// form an unsatisfiable formula on their own
+    pub core: Vec<usize>,
+    /// The checked lemmas with their antecedents, as an LRAT proof of the formula whose
+    /// clauses keep their ids `1` to `n`
+    pub lrat: Vec<LratStep>,
+}
+
+/// State of the checker: every clause of the formula and the proof, and the assignment built
+/// while checking a lemma
+#[derive(Default)]
+struct Checker {
+    /// Literals of every clause as written, without duplicates, the formula first
+    dimacs: Vec<Vec<i32>>,
+    /// Literals of every clause, the first two of which are watched
+    clauses: Vec<Vec<Lit>>,
+    /// Clauses which were added and not deleted at the current step
+    active: Vec<bool>,
+    /// Clauses which a check depended on, and lemmas which must be checked
+    marked: Vec<bool>,
+    /// Clauses watching each literal, by the index of the literal
+    watches: Vec<Vec<usize>>,
+    /// Clauses of a single literal, which cannot be watched
+    units: Vec<usize>,
+    values: Vec<Option<bool>>,
+    /// Clause which implied each variable, `None` for the assumptions
+    reason: Vec<Option<usize>>,
+    trail: Vec<Lit>,
+    /// Variables of the conflict analysis
+    seen: Vec<bool>,
+}
+
+impl Checker {
+    /// Stores a clause as active, returning its index
+    fn insert(&mut self, lits: &[i32]) -> usize {
+        let mut dimacs = Vec::with_capacity(lits.len());
+        for lit in lits {
+            if !dimacs.contains(lit) {
+                dimacs.push(*lit);
+            }
+        }
+        let clause: Vec<Lit> = dimacs.iter().map(|lit| Lit::from_dimacs(*lit)).collect();
+        if let Some(max_var) = clause.iter().map(|lit| lit.var() + 1).max() {
+            if max_var > self.values.len() {
+                self.values.resize(max_var, None);
+                self.reason.resize(max_var, None);
+                self.seen.resize(max_var, false);
+                self.watches.resize(2 * max_var, Vec::new());
+            }
+        }
+
+        let index = self.clauses.len();
+        match clause.len() {
+            0 => (),
+            1 => self.units.push(index),
+            _ => {
+                self.watches[clause[0].index()].push(index);
+                self.watches[clause[1].index()].push(index);
+            }
+        }
+        self.dimacs.push(dimacs);
+        self.clauses.push(clause);
+        self.active.push(true);
+        self.marked.push(false);
+        index
+    }
+
+    fn value(&self, lit: Lit) -> Option<bool> {
+        self.values[lit.var()].map(|value| value != lit.is_negated())
+    }
+
+    fn assign(&mut self, lit: Lit, reason: Option<usize>) {
+        self.values[lit.var()] = Some(!lit.is_negated());
+        self.reason[lit.var()] = reason;
+        self.trail.push(lit);
+    }
+
+    fn undo(&mut self) {
+        for lit in self.trail.drain(..) {
+            self.values[lit.var()] = None;
+        }
+    }
+
+    /// Propagates the active unit clauses, then the assignments of the trail, core-first
+    ///
+    /// Returns the falsified clause, if any.
+    fn propagate(&mut self) -> Option<usize> {
+        for i in 0..self.units.len() {
+            let index = self.units[i];
+            if !self.active[index] {
+                continue;
+            }
+            let lit = self.clauses[index][0];
+            match self.value(lit) {
+                Some(true) => (),
+                Some(false) => return Some(index),
+                None => self.assign(lit, Some(index)),
+            }
+        }
+
+        // The marked clauses see every assignment before the others see the next one
+        let mut core_head = 0;
+        let mut head = 0;
+        loop {
+            let (lit, core) = if core_head < self.trail.len() {
+                core_head += 1;
+                (self.trail[core_head - 1], true)
+            } else if head < self.trail.len() {
+                head += 1;
+                (self.trail[head - 1], false)
+            } else {
+                return None;
+            };
+            if let Some(conflict) = self.visit(!lit, core) {
+                return Some(conflict);
+            }
+        }
+    }
+
+    /// Visits the active clauses watching a literal which became false, either the marked ones
+    /// or the others, moving their watch or propagating them
+    fn visit(&mut self, false_lit: Lit, core: bool) -> Option<usize> {
+        let mut watchers = std::mem::take(&mut self.watches[false_lit.index()]);
+        let mut conflict = None;
+        let mut i = 0;
+        while i < watchers.len() {
+            let index = watchers[i];
+            if !self.active[index] || self.marked[index] != core {
+                i += 1;
+                continue;
+            }
+
+            let clause = &mut self.clauses[index];
+            if clause[0] == false_lit {
+                clause.swap(0, 1);
+            }
+            let first = clause[0];
+            let values = &self.values;
+            let value = |lit: Lit| values[lit.var()].map(|value| value != lit.is_negated());
+            if value(first) == Some(true) {
+                i += 1;
+                continue;
+            }
+
+            if let Some(k) = (2..clause.len()).find(|k| value(clause[*k]) != Some(false)) {
+                clause.swap(1, k);
+                self.watches[clause[1].index()].push(index);
+                watchers.swap_remove(i);
+                continue;
+            }
+            if value(first) == Some(false) {
+                conflict = Some(index);
+                break;
+            }
+            self.assign(first, Some(index));
+            i += 1;
+        }
+        self.watches[false_lit.index()] = watchers;
+        conflict
+    }
+
+    /// Returns the clauses unit propagation went through from a conflict, in trail order and
+    /// ending with the falsified clause
+    fn analyze(&mut self, conflict: usize) -> Vec<usize> {
+        for lit in &self.clauses[conflict] {
+            self.seen[lit.var()] = true;
+        }
+        let mut chain = Vec::new();
+        for lit in self.trail.iter().rev() {
+            if !self.seen[lit.var()] {
+                continue;
+            }
+            if let Some(reason) = self.reason[lit.var()] {
+                chain.push(reason);
+                for other in &self.clauses[reason] {
+                    self.seen[other.var()] = true;
+                }
+            }
+        }
+        for lit in &self.trail {
+            self.seen[lit.var()] = false;
+        }
+
+        chain.reverse();
+        chain.push(conflict);
+        chain
+    }
+
+    /// Checks that the negation of the given literals leads to a conflict by unit propagation
+    ///
+    /// Returns the clauses the conflict depends on, none if the literals are a tautology.
+    fn rup(&mut self, lits: &[i32]) -> Option<Vec<usize>> {
+        for lit in lits {
+            let lit = Lit::from_dimacs(*lit);
+            match self.value(lit) {
+                Some(true) => {
+                    self.undo();
+                    return Some(Vec::new());
+                }
+                Some(false) => (),
+                None => self.assign(!lit, None),
+            }
+        }
+        let chain = self.propagate().map(|conflict| self.analyze(conflict));
+        self.undo();
+        chain
+    }
+
+    /// Checks a lemma against the active clauses, and marks the clauses it depends on
+    ///
+    /// Returns the LRAT hints of the lemma, with the index of each clause plus one.
+    fn verify(&mut self, index: usize) -> Result<Vec<i64>, String> {
+        let lemma = self.dimacs[index].clone();
+        let mut hints = Vec::new();
+        if let Some(chain) = self.rup(&lemma) {
+            self.mark(&chain, &mut hints);
+            return Ok(hints);
+        }
+        let pivot = *lemma
+            .first()
+            .ok_or("the empty clause is not implied by unit propagation")?;
+
+        for other in 0..self.clauses.len() {
+            if !self.active[other] || !self.dimacs[other].contains(&-pivot) {
+                continue;
+            }
+            let mut resolvent = lemma.clone();
+            resolvent.extend(self.dimacs[other].iter().filter(|lit| **lit != -pivot));
+            let chain = self
+                .rup(&resolvent)
+                .ok_or_else(|| format!("the lemma is neither RUP nor RAT on {}", pivot))?;
+            self.marked[other] = true;
+            hints.push(-(other as i64 + 1));
+            self.mark(&chain, &mut hints);
+        }
+        Ok(hints)
+    }
+
+    fn mark(&mut self, chain: &[usize], hints: &mut Vec<i64>) {
+        for &index in chain {
+            self.marked[index] = true;
+            hints.push(index as i64 + 1);
+        }
+    }
+}
+
+/// Checks a DRAT proof of unsatisfiability, and trims it
+///
+/// The proof is checked backwards from its first empty clause, or from its end if it has none,
+/// where the clauses left must then be refuted by unit propagation. Deletions of clauses which
+/// do not exist are ignored.
+///
+/// # Arguments
+/// * `formula` - The [`Formula`] refuted by the proof
+/// * `steps` - The steps of the proof, e.g. from [`parse_drat`]
+///
+/// # Returns
+/// * `Result<TrimmedProof, ProofError>` - The core and LRAT proof if the proof is valid,
+///   otherwise the step of the first lemma which could not be checked
+///
+/// # Examples
+/// ```rust
+/// use sat_rs::cnfparser;
+/// use sat_rs::proof::drat::{check_drat, parse_drat};
+/// use sat_rs::proof::lrat::check_lrat;
+///
+/// // Clauses: 1 v 2, 1 v -2, -1 v 2, -1 v -2 and 3
+/// let formula =
+///     cnfparser::parse_cnf("p cnf 3 5\n1 2 0\n1 -2 0\n-1 2 0\n-1 -2 0\n3 0\n").unwrap();
+///
+/// let proof = parse_drat(b"1 0\nd 1 2 0\n0\n", false).unwrap();
+/// let trimmed = check_drat(&formula, &proof).unwrap();
+/// assert_eq!(trimmed.core, vec![0, 1, 2, 3]);
+/// assert!(check_lrat(&formula, &trimmed.lrat).is_ok());
+///
+/// let proof = parse_drat(b"-3 0\n0\n", false).unwrap();
+/// assert_eq!(check_drat(&formula, &proof).unwrap_err().step, 1);
+/// ```
+pub fn check_drat(formula: &Formula, steps: &[DratStep]) -> Result<TrimmedProof, ProofError> {
+    let mut checker = Checker::default();
+    let clauses = dimacs_clauses(formula);
+    if let Some(index) = clauses.iter().position(Vec::is_empty) {
+        return Ok(TrimmedProof {
+            core: vec![index],
+            lrat: Vec::new(),
+        });
+    }
+
+    // Active clauses by their sorted literals, for matching deletions
+    let mut live: HashMap<Vec<i32>, Vec<usize>> = HashMap::new();
+    let key = |lits: &[i32]| {
+        let mut key = lits.to_vec();
+        key.sort_unstable();
+        key.dedup();
+        key
+    };
+    for clause in &clauses {
+        let index = checker.insert(clause);
+        live.entry(key(clause)).or_default().push(index);
+    }
+
+    // Replays the proof up to the empty clause, as (step, clause, added) events
+    let mut events = Vec::new();
+    let mut refuted = false;
+    for (step, proof_step) in steps.iter().enumerate() {
+        match proof_step {
+            DratStep::Add(lits) => {
+                let index = checker.insert(lits);
+                live.entry(key(lits)).or_default().push(index);
+                events.push((step + 1, index, true));
+                if lits.is_empty() {
+                    refuted = true;
+                    break;
+                }
+            }
+            DratStep::Delete(lits) => {
+                if let Some(index) = live.get_mut(&key(lits)).and_then(Vec::pop) {
+                    checker.active[index] = false;
+                    events.push((step + 1, index, false));
+                }
+            }
+        }
+    }
+    if !refuted {
+        let index = checker.insert(&[]);
+        events.push((steps.len(), index, true));
+    }
+
+    // Checks the lemmas backwards, restoring the deleted clauses on the way
+    let mut hints = HashMap::new();
+    let (_, empty, _) = events[events.len() - 1];
+    checker.marked[empty] = true;
+    for &(step, index, added) in events.iter().rev() {
+        checker.active[index] = !added;
+        if added && checker.marked[index] {
+            let lemma_hints = checker.verify(index).map_err(|message| {
+                if index != empty {
+                    let lemma: Vec<String> = checker.dimacs[index]
+                        .iter()
+                        .map(|lit| lit.to_string())
+                        .collect();
+                    ProofError::new(step, format!("lemma {} 0: {}", lemma.join(" "), message))
+                } else if refuted {
+                    ProofError::new(step, message)
+                } else {
+                    ProofError::new(step, "the proof does not derive the empty clause")
+                }
+            })?;
+            hints.insert(index, lemma_hints);
+        }
+    }
+
+    // Numbers the checked lemmas after the clauses of the formula
+    let mut ids: Vec<u64> = (1..=clauses.len() as u64).collect();
+    ids.resize(checker.clauses.len(), 0);
+    let mut last_id = clauses.len() as u64;
+    let mut lrat = Vec::new();
+    for &(_, index, added) in &events {
+        if added && checker.marked[index] {
+            last_id += 1;
+            ids[index] = last_id;
+            let lemma_hints = hints[&index]
+                .iter()
+                .map(|hint| {
+                    let id = ids[hint.unsigned_abs() as usize - 1] as i64;
+                    if *hint < 0 {
+                        -id
+                    } else {
+                        id
+                    }
+                })
+                .collect();
+            lrat.push(LratStep::Add {
+                id: last_id,
+                clause: checker.dimacs[index].clone(),
+                hints: lemma_hints,
+            });
+        } else if !added && ids[index] != 0 {
+            lrat.push(LratStep::Delete {
+                ids: vec![ids[index]],
+            });
+        }
+    }
+
+    Ok(TrimmedProof {
+        core: (0..clauses.len())
+            .filter(|index| checker.marked[*index])
+            .collect(),
+        lrat,
+    })
+}
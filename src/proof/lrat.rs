@@ -0,0 +1,358 @@
+//! # LRAT Checker
+//!
+//! Checks LRAT proofs of unsatisfiability against a [`Formula`]. Every derived clause lists its
+//! antecedents, so checking a step only propagates through the listed clauses, which must each
+//! be unit or falsified in turn, and the check takes time linear in the size of the proof.
+//!
+//! A clause is accepted if the negation of its literals together with its antecedents leads to
+//! a conflict (RUP). Otherwise it must be a resolution asymmetric tautology (RAT) on its first
+//! literal `p`: for every clause `D` containing `-p`, the hints give a group `-id(D)` followed by
+//! the antecedents which refute the resolvent of the clause and `D`.
+//!
+//! ## Contents
+//!
+//! * [`LratStep`] - An addition or deletion of an LRAT proof.
+//! * [`parse_lrat`] - Parses a textual or binary LRAT proof.
+//! * [`check_lrat`] - Checks an LRAT proof against a formula.
+use crate::notation::Formula;
+use crate::proof::{decode, dimacs_clauses, ProofError};
+use std::collections::HashMap;
+
+/// A step of an LRAT proof
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LratStep {
+    /// Adds the clause with the given id, literals and hints
+    Add {
+        id: u64,
+        clause: Vec<i32>,
+        hints: Vec<i64>,
+    },
+    /// Deletes the clauses with the given ids
+    Delete { ids: Vec<u64> },
+}
+
+/// Parses an LRAT proof
+///
+/// # Arguments
+/// * `input` - The contents of the proof file
+/// * `binary` - `true` if the proof is in the binary LRAT format
+///
+/// # Returns
+/// * [`Vec<LratStep>`] - The steps of the proof, or the step which could not be parsed
+///
+/// # Examples
+/// ```rust
+/// use sat_rs::proof::lrat::{parse_lrat, LratStep};
+///
+/// let steps = parse_lrat(b"5 1 0 2 3 0\n5 d 2 0\n", false).unwrap();
+/// assert_eq!(steps[0], LratStep::Add { id: 5, clause: vec![1], hints: vec![2, 3] });
+/// assert_eq!(steps[1], LratStep::Delete { ids: vec![2] });
+/// ```
+pub fn parse_lrat(input: &[u8], binary: bool) -> Result<Vec<LratStep>, ProofError> {
+    if binary {
+        parse_binary(input)
+    } else {
+        parse_text(input)
+    }
+}
+
+fn parse_text(input: &[u8]) -> Result<Vec<LratStep>, ProofError> {
+    let text = String::from_utf8_lossy(input);
+    let mut steps = Vec::new();
+
+    for line in text.lines() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('c') {
+            continue;
+        }
+        let step = steps.len() + 1;
+        let error = |message: &str| ProofError::new(step, format!("{}: {}", message, line));
+        let mut tokens = line.split_whitespace();
+        let id: u64 = tokens
+            .next()
+            .and_then(|token| token.parse().ok())
+            .ok_or_else(|| error("invalid clause id"))?;
+
+        let mut tokens = tokens.peekable();
+        if tokens.peek() == Some(&"d") {
+            tokens.next();
+            let ids = read_list::<u64>(&mut tokens).ok_or_else(|| error("invalid deletion"))?;
+            steps.push(LratStep::Delete { ids });
+        } else {
+            let clause = read_list::<i32>(&mut tokens).ok_or_else(|| error("invalid clause"))?;
+            let hints = read_list::<i64>(&mut tokens).ok_or_else(|| error("invalid hints"))?;
+            steps.push(LratStep::Add { id, clause, hints });
+        }
+    }
+
+    Ok(steps)
+}
+
+/// Reads numbers up to a terminating `0`, returns `None` if there is none or a token is invalid
+fn read_list<'a, T: std::str::FromStr + Default + PartialEq>(
+    tokens: &mut impl Iterator<Item = &'a str>,
+) -> Option<Vec<T>> {
+    let mut list = Vec::new();
+    loop {
+        let number: T = tokens.next()?.parse().ok()?;
+        if number == T::default() {
+            return Some(list);
+        }
+        list.push(number);
+    }
+}
+
+fn parse_binary(input: &[u8]) -> Result<Vec<LratStep>, ProofError> {
+    let mut steps = Vec::new();
+    let mut position = 0;
+
+    // Reads the numbers up to the zero byte ending a list
+    let read = |position: &mut usize| -> Option<Vec<i64>> {
+        let mut list = Vec::new();
+        loop {
+            match decode(input, position)? {
+                0 => return Some(list),
+                number => list.push(number),
+            }
+        }
+    };
+
+    while position < input.len() {
+        let step = steps.len() + 1;
+        let error = |message: &str| ProofError::new(step, message.to_string());
+        let kind = input[position];
+        position += 1;
+        match kind {
+            b'a' => {
+                let id = decode(input, &mut position)
+                    .filter(|id| *id > 0)
+                    .ok_or_else(|| error("invalid clause id"))?;
+                let clause = read(&mut position).ok_or_else(|| error("invalid clause"))?;
+                let hints = read(&mut position).ok_or_else(|| error("invalid hints"))?;
+                steps.push(LratStep::Add {
+                    id: id as u64,
+                    clause: clause.into_iter().map(|lit| lit as i32).collect(),
+                    hints,
+                });
+            }
+            b'd' => {
+                let ids = read(&mut position).ok_or_else(|| error("invalid deletion"))?;
+                steps.push(LratStep::Delete {
+                    ids: ids.into_iter().map(|id| id.unsigned_abs()).collect(),
+                });
+            }
+            _ => return Err(error(&format!("unknown step kind {:#04x}", kind))),
+        }
+    }
+
+    Ok(steps)
+}
+
+/// State of the checker: the clauses by id, and the assignment built while checking a step
+struct Checker {
+    clauses: HashMap<u64, Vec<i32>>,
+    /// Value of each variable, indexed by the DIMACS variable
+    values: Vec<Option<bool>>,
+    /// Literals assigned while checking the current step
+    trail: Vec<i32>,
+}
+
+impl Checker {
+    fn value(&self, lit: i32) -> Option<bool> {
+        self.values[lit.unsigned_abs() as usize].map(|value| value == (lit > 0))
+    }
+
+    fn assign(&mut self, lit: i32) {
+        self.values[lit.unsigned_abs() as usize] = Some(lit > 0);
+        self.trail.push(lit);
+    }
+
+    /// Undoes the assignments made after the trail had the given length
+    fn undo(&mut self, length: usize) {
+        for lit in self.trail.drain(length..) {
+            self.values[lit.unsigned_abs() as usize] = None;
+        }
+    }
+
+    /// Makes room for the variables of a clause
+    fn reserve(&mut self, clause: &[i32]) {
+        let max_var = clause.iter().map(|lit| lit.unsigned_abs() as usize).max();
+        if let Some(max_var) = max_var.filter(|var| *var >= self.values.len()) {
+            self.values.resize(max_var + 1, None);
+        }
+    }
+
+    /// Propagates through the given clauses, each of which must be unit or falsified
+    ///
+    /// Returns `true` once a clause is falsified.
+    fn propagate(&mut self, hints: &[i64]) -> Result<bool, String> {
+        for hint in hints {
+            let clause = self
+                .clauses
+                .get(&hint.unsigned_abs())
+                .ok_or_else(|| format!("unknown clause {}", hint))?;
+
+            let mut unit = None;
+            for &lit in clause {
+                match self.value(lit) {
+                    Some(true) => return Err(format!("hint {} is satisfied", hint)),
+                    Some(false) => (),
+                    None if unit.is_none_or(|unit| unit == lit) => unit = Some(lit),
+                    None => return Err(format!("hint {} is not unit", hint)),
+                }
+            }
+            match unit {
+                Some(lit) => self.assign(lit),
+                None => return Ok(true),
+            }
+        }
+        Ok(false)
+    }
+
+    /// Checks that a clause is implied by its hints, as RUP or as RAT on its first literal
+    fn check(&mut self, clause: &[i32], hints: &[i64]) -> Result<(), String> {
+        // Assume the negation of the clause, a tautology is always implied
+        for &lit in clause {
+            match self.value(lit) {
+                Some(true) => return Ok(()),
+                Some(false) => (),
+                None => self.assign(-lit),
+            }
+        }
+
+        let split = hints
+            .iter()
+            .position(|hint| *hint < 0)
+            .unwrap_or(hints.len());
+        if self.propagate(&hints[..split])? {
+            return Ok(());
+        }
+        let pivot = *clause
+            .first()
+            .ok_or("the hints do not lead to a conflict")?;
+
+        // Hint groups of the RAT check, by the id of the clause resolved with
+        let mut groups: HashMap<u64, &[i64]> = HashMap::new();
+        let mut start = split;
+        while start < hints.len() {
+            let end = hints[start + 1..]
+                .iter()
+                .position(|hint| *hint < 0)
+                .map_or(hints.len(), |end| start + 1 + end);
+            groups.insert(hints[start].unsigned_abs(), &hints[start + 1..end]);
+            start = end;
+        }
+
+        let mut candidates: Vec<(u64, Vec<i32>)> = self
+            .clauses
+            .iter()
+            .filter(|(_, other)| other.contains(&-pivot))
+            .map(|(id, other)| (*id, other.clone()))
+            .collect();
+        candidates.sort_unstable();
+        if split == hints.len() && !candidates.is_empty() {
+            return Err("the hints do not lead to a conflict".to_string());
+        }
+
+        for (id, other) in candidates {
+            let group = groups
+                .get(&id)
+                .ok_or_else(|| format!("no hints for the resolvent with clause {}", id))?;
+
+            // Assume the negation of the resolvent, which may already be falsified
+            let length = self.trail.len();
+            let mut falsified = false;
+            for &lit in other.iter().filter(|lit| **lit != -pivot) {
+                match self.value(lit) {
+                    Some(true) => falsified = true,
+                    Some(false) => (),
+                    None => self.assign(-lit),
+                }
+            }
+            let conflict = falsified || self.propagate(group)?;
+            self.undo(length);
+            if !conflict {
+                return Err(format!("the resolvent with clause {} is not implied", id));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Checks an LRAT proof of unsatisfiability
+///
+/// The clauses of the formula have the ids `1` to `n` in order. Every added clause must be
+/// implied by its hints, and the proof must add the empty clause, which ends the check.
+///
+/// # Arguments
+/// * `formula` - The [`Formula`] refuted by the proof
+/// * `steps` - The steps of the proof, e.g. from [`parse_lrat`]
+///
+/// # Returns
+/// * `Result<(), ProofError>` - `Ok` if the proof is valid, otherwise the first invalid step
+///
+/// # Examples
+/// ```rust
+/// use sat_rs::cnfparser;
+/// use sat_rs::proof::lrat::{check_lrat, parse_lrat};
+///
+/// // Clauses: 1 v 2, 1 v -2, -1 v 2 and -1 v -2
+/// let formula = cnfparser::parse_cnf("p cnf 2 4\n1 2 0\n1 -2 0\n-1 2 0\n-1 -2 0\n").unwrap();
+///
+/// let proof = parse_lrat(b"5 1 0 1 2 0\n6 0 5 3 4 0\n", false).unwrap();
+/// assert!(check_lrat(&formula, &proof).is_ok());
+///
+/// let proof = parse_lrat(b"5 1 0 1 3 0\n", false).unwrap();
+/// assert_eq!(check_lrat(&formula, &proof).unwrap_err().step, 1);
+/// ```
+pub fn check_lrat(formula: &Formula, steps: &[LratStep]) -> Result<(), ProofError> {
+    let mut checker = Checker {
+        clauses: HashMap::new(),
+        values: vec![None; 1],
+        trail: Vec::new(),
+    };
+    for (index, lits) in dimacs_clauses(formula).into_iter().enumerate() {
+        checker.reserve(&lits);
+        checker.clauses.insert(index as u64 + 1, lits);
+    }
+    if formula
+        .clauses
+        .iter()
+        .any(|clause| clause.literals.is_empty())
+    {
+        return Ok(());
+    }
+
+    for (index, step) in steps.iter().enumerate() {
+        match step {
+            LratStep::Add { id, clause, hints } => {
+                if checker.clauses.contains_key(id) {
+                    return Err(ProofError::new(
+                        index + 1,
+                        format!("clause {} already exists", id),
+                    ));
+                }
+                checker.reserve(clause);
+                let result = checker.check(clause, hints);
+                checker.undo(0);
+                result.map_err(|message| {
+                    ProofError::new(index + 1, format!("lemma {}: {}", id, message))
+                })?;
+                checker.clauses.insert(*id, clause.clone());
+                if clause.is_empty() {
+                    return Ok(());
+                }
+            }
+            LratStep::Delete { ids } => {
+                for id in ids {
+                    checker.clauses.remove(id);
+                }
+            }
+        }
+    }
+
+    Err(ProofError::new(
+        steps.len(),
+        "the proof does not derive the empty clause",
+    ))
+}
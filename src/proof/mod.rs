@@ -0,0 +1,309 @@
+//! # Proofs
+//!
+//! Certificates of unsatisfiability written by the complete solvers, so that an UNSAT answer can
+//! be checked independently of the solver which found it.
+//!
+//! A DRAT proof lists the clauses a solver derived from the formula, in order, each of which
+//! must follow from the formula and the clauses before it, until the empty clause is derived.
+//! Clauses the solver no longer uses are listed as deletions, which keeps checking fast. Proofs
+//! are written either as text, one clause per line as in DIMACS with deletions prefixed by `d`,
+//! or in the more compact binary format.
+//!
+//! An LRAT proof also numbers every clause, the clauses of the formula being numbered from `1`
+//! in order, and lists with each derived clause the antecedents (hints) which unit propagation
+//! goes through to derive it. Checking it then needs no search, see [`lrat`].
+//!
+//! ## Contents
+//!
+//! * [`ProofFormat`] - The textual or binary DRAT or LRAT format.
+//! * [`ProofWriter`] - Streams the added and deleted clauses of a proof to a writer.
+//! * [`ProofError`] - The step at which a proof check failed, and why.
+//! * [`drat`] - A backward checker for DRAT proofs, which trims them to LRAT proofs.
+//! * [`lrat`] - A checker for LRAT proofs.
+pub mod drat;
+pub mod lrat;
+
+use crate::notation::{Formula, Lit};
+use std::fmt;
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
+
+/// Format of a proof
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ProofFormat {
+    /// Clauses as DIMACS lines, e.g. `1 -2 0`, and deletions as `d 1 -2 0`
+    #[default]
+    Drat,
+    /// Clauses as the byte `a`, deletions as the byte `d`, followed by the variable-length
+    /// encoding of `2 * v` or `2 * v + 1` for each literal `v` or `-v`, and a zero byte
+    BinaryDrat,
+    /// Clauses as `id lits 0 hints 0` lines, e.g. `12 1 -2 0 3 7 0`, and deletions as
+    /// `id d ids 0` lines
+    Lrat,
+    /// Clauses as the byte `a` followed by the id, literals and hints, deletions as the byte `d`
+    /// followed by ids, every number encoded like a literal of [`ProofFormat::BinaryDrat`] and
+    /// every list ending with a zero byte
+    BinaryLrat,
+}
+
+impl ProofFormat {
+    /// Returns `true` for the LRAT formats, which need the id and antecedents of every clause
+    pub fn is_lrat(&self) -> bool {
+        matches!(self, ProofFormat::Lrat | ProofFormat::BinaryLrat)
+    }
+
+    /// Returns `true` for the binary formats
+    pub fn is_binary(&self) -> bool {
+        matches!(self, ProofFormat::BinaryDrat | ProofFormat::BinaryLrat)
+    }
+}
+
+/// The step at which a proof check failed
+///
+/// # Examples
+/// ```rust
+/// use sat_rs::proof::ProofError;
+///
+/// let error = ProofError::new(3, "the lemma is not implied");
+/// assert_eq!(error.to_string(), "step 3: the lemma is not implied");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProofError {
+    /// One-based index of the step in the proof, counting additions and deletions
+    pub step: usize,
+    /// Why the step is invalid
+    pub message: String,
+}
+
+impl ProofError {
+    /// Creates a new [`ProofError`]
+    pub fn new(step: usize, message: impl Into<String>) -> ProofError {
+        ProofError {
+            step,
+            message: message.into(),
+        }
+    }
+}
+
+impl fmt::Display for ProofError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "step {}: {}", self.step, self.message)
+    }
+}
+
+/// Streams a proof of unsatisfiability to a writer
+///
+/// Writing errors do not interrupt the solver, the first one is kept and returned by
+/// [`ProofWriter::finish`].
+///
+/// # Examples
+/// ```rust
+/// use sat_rs::proof::{ProofFormat, ProofWriter};
+///
+/// let path = std::env::temp_dir().join("sat-rs-proof-example.lrat");
+/// let mut proof = ProofWriter::create(&path, ProofFormat::Lrat).unwrap();
+/// proof.add(4, &[1, -2], &[1, 3]);
+/// proof.delete(4, &[1, -2]);
+/// proof.add(5, &[], &[2, 3]);
+/// proof.finish().unwrap();
+///
+/// let text = std::fs::read_to_string(&path).unwrap();
+/// assert_eq!(text, "4 1 -2 0 1 3 0\n4 d 4 0\n5 0 2 3 0\n");
+/// ```
+pub struct ProofWriter {
+    out: Box<dyn Write>,
+    format: ProofFormat,
+    /// Id of the last added clause, which prefixes textual LRAT deletions
+    last_id: u64,
+    error: Option<io::Error>,
+}
+
+impl ProofWriter {
+    /// Creates a new [`ProofWriter`] writing to `out`
+    pub fn new(out: impl Write + 'static, format: ProofFormat) -> ProofWriter {
+        ProofWriter {
+            out: Box::new(out),
+            format,
+            last_id: 0,
+            error: None,
+        }
+    }
+
+    /// Creates a new [`ProofWriter`] writing to a buffered file, created or truncated
+    pub fn create(path: &Path, format: ProofFormat) -> io::Result<ProofWriter> {
+        let file = File::create(path)?;
+        Ok(ProofWriter::new(BufWriter::new(file), format))
+    }
+
+    /// Returns the format of the proof
+    pub fn format(&self) -> ProofFormat {
+        self.format
+    }
+
+    /// Adds a clause of signed DIMACS literals, which must follow from the clauses so far
+    ///
+    /// # Arguments
+    /// * `id` - The id of the new clause, only written in the LRAT formats
+    /// * `clause` - The literals of the clause
+    /// * `hints` - The ids of the antecedents of the clause in the order unit propagation uses
+    ///   them, only written in the LRAT formats
+    pub fn add(&mut self, id: u64, clause: &[i32], hints: &[i64]) {
+        self.last_id = self.last_id.max(id);
+        if self.error.is_some() {
+            return;
+        }
+        let result = match self.format {
+            ProofFormat::Drat => write_text(&mut self.out, b'a', clause),
+            ProofFormat::BinaryDrat => write_binary(&mut self.out, b'a', clause),
+            ProofFormat::Lrat => write_lrat_text(&mut self.out, id, clause, hints),
+            ProofFormat::BinaryLrat => write_lrat_binary(&mut self.out, id, clause, hints),
+        };
+        self.keep_error(result);
+    }
+
+    /// Deletes a clause which is no longer needed
+    ///
+    /// # Arguments
+    /// * `id` - The id of the clause, only written in the LRAT formats
+    /// * `clause` - The literals of the clause, only written in the DRAT formats
+    pub fn delete(&mut self, id: u64, clause: &[i32]) {
+        if self.error.is_some() {
+            return;
+        }
+        let result = match self.format {
+            ProofFormat::Drat => write_text(&mut self.out, b'd', clause),
+            ProofFormat::BinaryDrat => write_binary(&mut self.out, b'd', clause),
+            ProofFormat::Lrat => writeln!(self.out, "{} d {} 0", self.last_id, id),
+            ProofFormat::BinaryLrat => {
+                let mut bytes = vec![b'd'];
+                encode(&mut bytes, id as i64);
+                bytes.push(0);
+                self.out.write_all(&bytes)
+            }
+        };
+        self.keep_error(result);
+    }
+
+    /// Flushes the proof
+    ///
+    /// # Returns
+    /// * [`io::Result`] - The first error met while writing the proof, if any
+    pub fn finish(&mut self) -> io::Result<()> {
+        if let Some(error) = self.error.take() {
+            return Err(error);
+        }
+        self.out.flush()
+    }
+
+    fn keep_error(&mut self, result: io::Result<()>) {
+        if let Err(error) = result {
+            self.error.get_or_insert(error);
+        }
+    }
+}
+
+impl fmt::Debug for ProofWriter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ProofWriter")
+            .field("format", &self.format)
+            .field("error", &self.error)
+            .finish_non_exhaustive()
+    }
+}
+
+fn write_text(out: &mut impl Write, kind: u8, clause: &[i32]) -> io::Result<()> {
+    if kind == b'd' {
+        out.write_all(b"d ")?;
+    }
+    for lit in clause {
+        write!(out, "{} ", lit)?;
+    }
+    out.write_all(b"0\n")
+}
+
+fn write_binary(out: &mut impl Write, kind: u8, clause: &[i32]) -> io::Result<()> {
+    let mut bytes = vec![kind];
+    for lit in clause {
+        encode(&mut bytes, *lit as i64);
+    }
+    bytes.push(0);
+    out.write_all(&bytes)
+}
+
+fn write_lrat_text(out: &mut impl Write, id: u64, clause: &[i32], hints: &[i64]) -> io::Result<()> {
+    write!(out, "{} ", id)?;
+    for lit in clause {
+        write!(out, "{} ", lit)?;
+    }
+    out.write_all(b"0 ")?;
+    for hint in hints {
+        write!(out, "{} ", hint)?;
+    }
+    out.write_all(b"0\n")
+}
+
+fn write_lrat_binary(
+    out: &mut impl Write,
+    id: u64,
+    clause: &[i32],
+    hints: &[i64],
+) -> io::Result<()> {
+    let mut bytes = vec![b'a'];
+    encode(&mut bytes, id as i64);
+    for lit in clause {
+        encode(&mut bytes, *lit as i64);
+    }
+    bytes.push(0);
+    for hint in hints {
+        encode(&mut bytes, *hint);
+    }
+    bytes.push(0);
+    out.write_all(&bytes)
+}
+
+/// Returns the clauses of a formula as signed DIMACS literals, the clause with id `i` at index
+/// `i - 1`
+pub(crate) fn dimacs_clauses(formula: &Formula) -> Vec<Vec<i32>> {
+    formula
+        .clauses
+        .iter()
+        .map(|clause| {
+            clause
+                .literals
+                .iter()
+                .map(|literal| Lit::from(literal).to_dimacs())
+                .collect()
+        })
+        .collect()
+}
+
+/// Appends the binary encoding of a non-zero number, `2 * n` or `2 * n + 1` for `n` or `-n` in
+/// groups of 7 bits, lowest first, with the high bit set on all but the last group
+fn encode(bytes: &mut Vec<u8>, number: i64) {
+    let mut code = 2 * number.unsigned_abs() + (number < 0) as u64;
+    while code >= 0x80 {
+        bytes.push((code & 0x7f) as u8 | 0x80);
+        code >>= 7;
+    }
+    bytes.push(code as u8);
+}
+
+/// Reads a number encoded by [`encode`] at a position, which is moved past it
+///
+/// Returns `None` at the end of the input, and `Some(0)` for the zero byte ending a list.
+pub(crate) fn decode(bytes: &[u8], position: &mut usize) -> Option<i64> {
+    let mut code: u64 = 0;
+    let mut shift = 0;
+    loop {
+        let byte = *bytes.get(*position)?;
+        *position += 1;
+        code |= ((byte & 0x7f) as u64).checked_shl(shift)?;
+        if byte & 0x80 == 0 {
+            break;
+        }
+        shift += 7;
+    }
+    let number = (code >> 1) as i64;
+    Some(if code & 1 == 1 { -number } else { number })
+}
@@ -0,0 +1,318 @@
+use crate::notation::Formula;
+use crate::solvers::localsearch::{LocalSearch, LocalSearchAlgorithm, LocalSearchSolver};
+use crate::solvers::result::SolveResult;
+use crate::solvers::solver::SolverStats;
+use rand::{Rng, RngExt};
+use std::str::FromStr;
+
+/// Cooling schedule of the [`annealing_algorithm`], lowering the temperature from the maximum
+/// to the minimum temperature over the flips of a try
+///
+/// # Examples
+/// ```rust
+/// use sat_rs::solvers::annealing::Cooling;
+///
+/// let cooling: Cooling = "adaptive:500".parse().unwrap();
+/// assert_eq!(cooling, Cooling::Adaptive { patience: 500 });
+///
+/// let cooling: Cooling = "linear".parse().unwrap();
+/// assert_eq!(cooling.temperature(2.0, 0.5, 0.5), 1.25);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum Cooling {
+    /// Multiplies the temperature by the same factor at every step
+    #[default]
+    Geometric,
+    /// Lowers the temperature by the same amount at every step
+    Linear,
+    /// Geometric cooling, reheated when the search is frozen: after `patience` steps, `1000` by
+    /// default, without a worsening flip nor an improvement, the cooling moves halfway back
+    /// towards the maximum temperature
+    Adaptive { patience: u64 },
+}
+
+impl Cooling {
+    /// Returns the temperature after a fraction `progress` of the cooling, between `0` and `1`
+    ///
+    /// # Arguments
+    /// * `max_temperature` - The temperature at the start of the cooling
+    /// * `min_temperature` - The temperature at the end of the cooling
+    /// * `progress` - The fraction of the steps of the cooling already made
+    pub fn temperature(&self, max_temperature: f64, min_temperature: f64, progress: f64) -> f64 {
+        match self {
+            Cooling::Linear => max_temperature + (min_temperature - max_temperature) * progress,
+            Cooling::Geometric | Cooling::Adaptive { .. } => {
+                max_temperature * (min_temperature / max_temperature).powf(progress)
+            }
+        }
+    }
+}
+
+impl FromStr for Cooling {
+    type Err = String;
+
+    fn from_str(spec: &str) -> Result<Cooling, String> {
+        match spec.split_once(':') {
+            None if spec == "geometric" => Ok(Cooling::Geometric),
+            None if spec == "linear" => Ok(Cooling::Linear),
+            None if spec == "adaptive" => Ok(Cooling::Adaptive { patience: 1000 }),
+            Some(("adaptive", patience)) => match patience.parse::<u64>() {
+                Ok(patience) if patience > 0 => Ok(Cooling::Adaptive { patience }),
+                _ => Err(format!("Invalid cooling patience: {}", patience)),
+            },
+            _ => Err(format!("Unknown cooling schedule: {}", spec)),
+        }
+    }
+}
+
+/// Parameters of the [`annealing_algorithm`]
+///
+/// # Examples
+/// ```rust
+/// use sat_rs::solvers::annealing::{AnnealingConfig, Cooling};
+///
+/// let config = AnnealingConfig {
+///     cooling: Cooling::Linear,
+///     ..Default::default()
+/// };
+/// assert_eq!(config.max_temperature, 2.0);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AnnealingConfig {
+    /// The [`Cooling`] schedule
+    pub cooling: Cooling,
+    /// Temperature at the start of a try
+    pub max_temperature: f64,
+    /// Temperature at the end of a try
+    pub min_temperature: f64,
+}
+
+impl AnnealingConfig {
+    /// Checks that the temperatures are finite with `0 < min_temperature <= max_temperature`, so
+    /// that the cooling never divides by zero nor accepts every flip
+    ///
+    /// # Returns
+    /// * `Result<(), String>` - `Ok` if the configuration is valid, otherwise why it is not
+    ///
+    /// # Examples
+    /// ```rust
+    /// use sat_rs::solvers::annealing::AnnealingConfig;
+    ///
+    /// assert!(AnnealingConfig::default().validate().is_ok());
+    ///
+    /// let config = AnnealingConfig { max_temperature: 0.0, ..Default::default() };
+    /// assert!(config.validate().is_err());
+    /// ```
+    pub fn validate(&self) -> Result<(), String> {
+        if self.min_temperature > 0.0
+            && self.min_temperature <= self.max_temperature
+            && self.max_temperature.is_finite()
+        {
+            Ok(())
+        } else {
+            Err(format!(
+                "Invalid annealing temperatures: from {} to {}",
+                self.max_temperature, self.min_temperature
+            ))
+        }
+    }
+}
+
+impl Default for AnnealingConfig {
+    fn default() -> Self {
+        AnnealingConfig {
+            cooling: Cooling::default(),
+            max_temperature: 2.0,
+            min_temperature: 0.05,
+        }
+    }
+}
+
+/// [`Solver`](crate::solvers::solver::Solver) running the [`annealing_algorithm`]
+///
+/// # Examples
+/// ```rust
+/// use sat_rs::notation::{Literal, Clause};
+/// use sat_rs::solvers::annealing::{AnnealingConfig, AnnealingSolver};
+/// use sat_rs::solvers::solver::Solver;
+///
+/// let mut clause = Clause::new(); // Clause: p v q
+/// clause.literals.push(Literal{ value: 1, negated: false});
+/// clause.literals.push(Literal{ value: 2, negated: false});
+///
+/// let mut solver = AnnealingSolver::new(10, 100, AnnealingConfig::default());
+/// solver.add_clause(clause);
+/// assert!(solver.solve().is_sat());
+/// ```
+pub type AnnealingSolver = LocalSearchSolver<Annealing>;
+
+/// The search of the [`annealing_algorithm`], run by a [`AnnealingSolver`]
+#[derive(Debug, Clone)]
+pub struct Annealing {
+    max_tries: u32,
+    max_flips: u32,
+    config: AnnealingConfig,
+}
+
+impl AnnealingSolver {
+    /// Creates a new [`AnnealingSolver`]
+    ///
+    /// # Arguments
+    /// * `max_tries` - The number of times to try to find a satisfying interpretation
+    /// * `max_flips` - The number of steps in an interpretation, over which it is cooled down
+    /// * `config` - An [`AnnealingConfig`] struct
+    pub fn new(max_tries: u32, max_flips: u32, config: AnnealingConfig) -> AnnealingSolver {
+        LocalSearchSolver::from_algorithm(Annealing {
+            max_tries,
+            max_flips,
+            config,
+        })
+    }
+}
+
+impl LocalSearchAlgorithm for Annealing {
+    fn search(
+        &self,
+        formula: &Formula,
+        rng: &mut impl Rng,
+        stats: &mut SolverStats,
+    ) -> SolveResult {
+        let mut state = LocalSearch::new(formula);
+        if state.has_empty_clause() {
+            return SolveResult::Unsat;
+        }
+        let AnnealingConfig {
+            cooling,
+            max_temperature,
+            min_temperature,
+        } = self.config;
+
+        for _ in 0..self.max_tries {
+            stats.tries += 1;
+
+            // Create a random interpretation
+            state.randomize(rng);
+            let mut best_unsat = state.num_unsat();
+            let mut progress = 0.0;
+            let mut last_change = 0;
+
+            for step in 0..self.max_flips {
+                // Check if the interpretation satisfies the formula
+                if state.is_satisfied() {
+                    break;
+                }
+
+                // Reheat when the search is frozen in a local minimum
+                if let Cooling::Adaptive { patience } = cooling {
+                    if (step - last_change) as u64 >= patience {
+                        progress /= 2.0;
+                        last_change = step;
+                        best_unsat = state.num_unsat();
+                    }
+                }
+                let temperature = cooling.temperature(max_temperature, min_temperature, progress);
+                progress += 1.0 / self.max_flips as f64;
+
+                // Flip a random variable, always if it does not increase the number of
+                // unsatisfied clauses, and with the Metropolis probability otherwise
+                let var = state.vars()[rng.random_range(0..state.vars().len())];
+                let delta = -state.score(var) as f64;
+                if delta > 0.0 && rng.random::<f64>() >= (-delta / temperature).exp() {
+                    continue;
+                }
+
+                state.flip(var);
+                stats.flips += 1;
+                if delta > 0.0 || state.num_unsat() < best_unsat {
+                    best_unsat = best_unsat.min(state.num_unsat());
+                    last_change = step;
+                }
+            }
+
+            if state.is_satisfied() {
+                return SolveResult::Sat(state.model(&formula.vars));
+            }
+        }
+
+        SolveResult::Unknown(format!(
+            "no model found in {} tries of {} flips",
+            self.max_tries, self.max_flips
+        ))
+    }
+}
+
+/// Simulated Annealing Algorithm for evaluation of propostional formulas
+///
+/// This algorithm generates random interpretations and proposes to flip a random variable at
+/// every step. A flip which does not increase the number of unsatisfied clauses is always made.
+/// A flip which increases it by `delta` is made with the Metropolis probability
+/// `exp(-delta / T)`, where the temperature `T` is lowered from `max_temperature` to
+/// `min_temperature` over the `max_flips` steps of a try by the [`Cooling`] schedule. Worsening
+/// flips are thus frequent at the start of a try and rare at its end. With [`Cooling::Adaptive`],
+/// the search is reheated whenever it is frozen in a local minimum. If all tries are exhausted,
+/// the result is unknown.
+///
+/// Only accepted flips are counted in the statistics, but every step counts towards
+/// `max_flips`.
+///
+/// # Pseudocode
+/// ```text
+/// procedure Annealing(Set of Clauses S)
+///
+/// input: A set of clauses S
+/// output: a model of S, or unknown
+/// parameters: max_tries, max_flips, cooling, max_temperature, min_temperature
+///
+/// begin
+///   repeat max_tries times
+///     create a random interpretation I
+///     repeat max_flips times
+///       if I satisfies S
+///         then return I
+///       T := temperature of the cooling schedule, reheated if adaptive and frozen
+///       p := a random variable
+///       delta := increase of the number of unsatisfied clauses if p is flipped
+///       if delta <= 0, or with probability exp(-delta / T)
+///         then flip the value of p in I
+///   return unknown
+/// end
+/// ```
+///
+/// # Arguments
+/// * `formula` - A [`Formula`] struct
+/// * `max_tries` - The number of times to try to find a satisfying interpretation
+/// * `max_flips` - The number of steps in an interpretation, over which it is cooled down
+/// * `config` - An [`AnnealingConfig`] struct
+/// * `seed` - The seed of the random number generator, `None` to seed from the operating
+///   system
+///
+/// # Returns
+/// * [`SolveResult`] - [`SolveResult::Sat`] with the model, or [`SolveResult::Unknown`]
+///
+/// # Examples
+/// ```
+/// use sat_rs::notation::{Literal, Clause, Formula};
+/// use sat_rs::solvers::annealing::{self, AnnealingConfig};
+///
+/// let mut clause = Clause::new(); // Clause: p v q
+/// clause.literals.push(Literal{ value: 1, negated: false});
+/// clause.literals.push(Literal{ value: 2, negated: false});
+///
+/// let mut formula = Formula::new();
+/// formula.add_clause(clause);
+///
+/// let config = AnnealingConfig::default();
+/// let result = annealing::annealing_algorithm(&mut formula, 10, 100, config, Some(1));
+/// assert_eq!(result.is_sat(), true);
+/// ```
+pub fn annealing_algorithm(
+    formula: &mut Formula,
+    max_tries: u32,
+    max_flips: u32,
+    config: AnnealingConfig,
+    seed: Option<u64>,
+) -> SolveResult {
+    AnnealingSolver::new(max_tries, max_flips, config)
+        .with_seed(seed)
+        .search(formula)
+}
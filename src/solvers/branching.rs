@@ -0,0 +1,424 @@
+//! # Branching Heuristics
+//!
+//! Variable orders used by the complete solvers to pick the next decision variable, and saved
+//! phases used to pick its value.
+//!
+//! Variables are identified by their zero-based index, i.e. DIMACS variable `v` has index `v - 1`.
+//!
+//! ## Contents
+//!
+//! * [`VariableOrder`] - The interface every variable order implements.
+//! * [`Vsids`] - Exponential VSIDS (EVSIDS) with a binary heap of variable activities.
+//! * [`Vmtf`] - Variable move-to-front, a queue ordered by the time of the last bump.
+//! * [`StaticOrder`] - Picks the unassigned variable with the smallest index.
+//! * [`Branching`] - Selects one of the above by name.
+//! * [`Phases`] - Saved polarities of variables.
+use std::str::FromStr;
+
+/// A variable order used to select decision variables
+///
+/// Solvers notify the order about variables involved in conflicts ([`VariableOrder::bump`]), the
+/// end of each conflict ([`VariableOrder::decay`]) and variables becoming unassigned during
+/// backtracking ([`VariableOrder::unassigned`]).
+pub trait VariableOrder {
+    /// Increases the priority of a variable which took part in a conflict
+    fn bump(&mut self, var: usize);
+
+    /// Called once after each conflict, after all variables of the conflict have been bumped
+    fn decay(&mut self);
+
+    /// Called when a variable becomes unassigned
+    fn unassigned(&mut self, var: usize);
+
+    /// Returns the unassigned variable with the highest priority
+    ///
+    /// # Arguments
+    /// * `is_assigned` - Returns `true` if the given variable currently has a value
+    ///
+    /// # Returns
+    /// * [`Option<usize>`] - The variable to branch on, or `None` if all variables are assigned
+    fn next_var(&mut self, is_assigned: &dyn Fn(usize) -> bool) -> Option<usize>;
+}
+
+/// Exponential VSIDS variable order
+///
+/// Each bump adds the current increment to the activity of a variable, and each decay divides
+/// the increment by the decay factor, so that recent conflicts weigh exponentially more than old
+/// ones. Activities are rescaled when they grow too large. Unassigned variables are kept in a
+/// binary max-heap ordered by activity.
+///
+/// # Examples
+/// ```rust
+/// use sat_rs::solvers::branching::{VariableOrder, Vsids};
+///
+/// let mut order = Vsids::new(3, 0.95);
+/// order.bump(2);
+/// order.decay();
+///
+/// assert_eq!(order.next_var(&|_| false), Some(2));
+/// assert_eq!(order.next_var(&|var| var == 2), Some(0));
+/// ```
+#[derive(Debug, Clone)]
+pub struct Vsids {
+    activity: Vec<f64>,
+    increment: f64,
+    decay: f64,
+    /// Binary max-heap of variables ordered by activity
+    heap: Vec<usize>,
+    /// Position of each variable in the heap
+    indices: Vec<Option<usize>>,
+}
+
+impl Vsids {
+    /// Creates a new [`Vsids`] order over `num_vars` variables with zero activity
+    ///
+    /// # Arguments
+    /// * `num_vars` - The number of variables
+    /// * `decay` - The decay factor in `(0, 1]`, typical values are between `0.8` and `0.999`
+    pub fn new(num_vars: usize, decay: f64) -> Vsids {
+        Vsids {
+            activity: vec![0.0; num_vars],
+            increment: 1.0,
+            decay,
+            heap: (0..num_vars).collect(),
+            indices: (0..num_vars).map(Some).collect(),
+        }
+    }
+
+    /// Returns the activity of a variable
+    pub fn activity(&self, var: usize) -> f64 {
+        self.activity[var]
+    }
+
+    fn insert(&mut self, var: usize) {
+        if self.indices[var].is_none() {
+            self.indices[var] = Some(self.heap.len());
+            self.heap.push(var);
+            self.sift_up(self.heap.len() - 1);
+        }
+    }
+
+    fn pop(&mut self) -> Option<usize> {
+        let top = *self.heap.first()?;
+        let last = self.heap.pop().unwrap();
+        self.indices[top] = None;
+        if !self.heap.is_empty() {
+            self.heap[0] = last;
+            self.indices[last] = Some(0);
+            self.sift_down(0);
+        }
+        Some(top)
+    }
+
+    fn sift_up(&mut self, mut pos: usize) {
+        let var = self.heap[pos];
+        while pos > 0 {
+            let parent = (pos - 1) / 2;
+            if self.activity[self.heap[parent]] >= self.activity[var] {
+                break;
+            }
+            self.heap[pos] = self.heap[parent];
+            self.indices[self.heap[pos]] = Some(pos);
+            pos = parent;
+        }
+        self.heap[pos] = var;
+        self.indices[var] = Some(pos);
+    }
+
+    fn sift_down(&mut self, mut pos: usize) {
+        let var = self.heap[pos];
+        loop {
+            let left = 2 * pos + 1;
+            if left >= self.heap.len() {
+                break;
+            }
+            let right = left + 1;
+            let child = if right < self.heap.len()
+                && self.activity[self.heap[right]] > self.activity[self.heap[left]]
+            {
+                right
+            } else {
+                left
+            };
+            if self.activity[self.heap[child]] <= self.activity[var] {
+                break;
+            }
+            self.heap[pos] = self.heap[child];
+            self.indices[self.heap[pos]] = Some(pos);
+            pos = child;
+        }
+        self.heap[pos] = var;
+        self.indices[var] = Some(pos);
+    }
+}
+
+impl VariableOrder for Vsids {
+    fn bump(&mut self, var: usize) {
+        self.activity[var] += self.increment;
+
+        // Rescale all activities to avoid overflowing
+        if self.activity[var] > 1e100 {
+            for activity in &mut self.activity {
+                *activity *= 1e-100;
+            }
+            self.increment *= 1e-100;
+        }
+
+        if let Some(pos) = self.indices[var] {
+            self.sift_up(pos);
+        }
+    }
+
+    fn decay(&mut self) {
+        self.increment /= self.decay;
+    }
+
+    fn unassigned(&mut self, var: usize) {
+        self.insert(var);
+    }
+
+    fn next_var(&mut self, is_assigned: &dyn Fn(usize) -> bool) -> Option<usize> {
+        // Assigned variables are removed lazily and reinserted when they become unassigned
+        while let Some(var) = self.pop() {
+            if !is_assigned(var) {
+                return Some(var);
+            }
+        }
+        None
+    }
+}
+
+/// Variable move-to-front order
+///
+/// Variables are kept in a doubly linked queue ordered by the time they were last bumped. A
+/// bumped variable is moved to the front of the queue, and decisions are taken on the most
+/// recently bumped unassigned variable. A search pointer caches the position in the queue after
+/// which all variables are known to be assigned, which relies on variables being assigned when
+/// they are bumped, as is the case for variables of a conflict.
+///
+/// # Examples
+/// ```rust
+/// use sat_rs::solvers::branching::{VariableOrder, Vmtf};
+///
+/// let mut order = Vmtf::new(3);
+/// assert_eq!(order.next_var(&|_| false), Some(2));
+///
+/// // Variables of a conflict are bumped while assigned and reported once unassigned
+/// order.bump(0);
+/// order.unassigned(0);
+///
+/// assert_eq!(order.next_var(&|_| false), Some(0));
+/// assert_eq!(order.next_var(&|var| var == 0), Some(2));
+/// ```
+#[derive(Debug, Clone)]
+pub struct Vmtf {
+    /// Next older variable in the queue
+    prev: Vec<Option<usize>>,
+    /// Next more recently bumped variable in the queue
+    next: Vec<Option<usize>>,
+    /// Time of the last bump of each variable
+    stamp: Vec<u64>,
+    /// The most recently bumped variable
+    last: Option<usize>,
+    /// All variables bumped more recently than this one are assigned
+    search: Option<usize>,
+    time: u64,
+}
+
+impl Vmtf {
+    /// Creates a new [`Vmtf`] order over `num_vars` variables, with higher indices in front
+    pub fn new(num_vars: usize) -> Vmtf {
+        let last = num_vars.checked_sub(1);
+        Vmtf {
+            prev: (0..num_vars).map(|var| var.checked_sub(1)).collect(),
+            next: (0..num_vars)
+                .map(|var| Some(var + 1).filter(|next| *next < num_vars))
+                .collect(),
+            stamp: (1..=num_vars as u64).collect(),
+            last,
+            search: last,
+            time: num_vars as u64,
+        }
+    }
+}
+
+impl VariableOrder for Vmtf {
+    fn bump(&mut self, var: usize) {
+        if self.last == Some(var) {
+            return;
+        }
+
+        // Unlink the variable, the search pointer follows it to the front
+        if let Some(prev) = self.prev[var] {
+            self.next[prev] = self.next[var];
+        }
+        if let Some(next) = self.next[var] {
+            self.prev[next] = self.prev[var];
+        }
+
+        // Append it to the front of the queue
+        self.prev[var] = self.last;
+        self.next[var] = None;
+        if let Some(last) = self.last {
+            self.next[last] = Some(var);
+        }
+        self.last = Some(var);
+        self.time += 1;
+        self.stamp[var] = self.time;
+    }
+
+    fn decay(&mut self) {}
+
+    fn unassigned(&mut self, var: usize) {
+        match self.search {
+            Some(search) if self.stamp[search] >= self.stamp[var] => (),
+            _ => self.search = Some(var),
+        }
+    }
+
+    fn next_var(&mut self, is_assigned: &dyn Fn(usize) -> bool) -> Option<usize> {
+        let mut current = self.search;
+        while let Some(var) = current {
+            if !is_assigned(var) {
+                self.search = Some(var);
+                return Some(var);
+            }
+            current = self.prev[var];
+        }
+        self.search = None;
+        None
+    }
+}
+
+/// Static variable order, picking the unassigned variable with the smallest index
+///
+/// # Examples
+/// ```rust
+/// use sat_rs::solvers::branching::{StaticOrder, VariableOrder};
+///
+/// let mut order = StaticOrder::new(3);
+/// order.bump(2);
+///
+/// assert_eq!(order.next_var(&|var| var == 0), Some(1));
+/// ```
+#[derive(Debug, Clone)]
+pub struct StaticOrder {
+    num_vars: usize,
+    /// All variables below this index are assigned
+    cursor: usize,
+}
+
+impl StaticOrder {
+    /// Creates a new [`StaticOrder`] over `num_vars` variables
+    pub fn new(num_vars: usize) -> StaticOrder {
+        StaticOrder {
+            num_vars,
+            cursor: 0,
+        }
+    }
+}
+
+impl VariableOrder for StaticOrder {
+    fn bump(&mut self, _var: usize) {}
+
+    fn decay(&mut self) {}
+
+    fn unassigned(&mut self, var: usize) {
+        self.cursor = self.cursor.min(var);
+    }
+
+    fn next_var(&mut self, is_assigned: &dyn Fn(usize) -> bool) -> Option<usize> {
+        while self.cursor < self.num_vars {
+            if !is_assigned(self.cursor) {
+                return Some(self.cursor);
+            }
+            self.cursor += 1;
+        }
+        None
+    }
+}
+
+/// Name of a [`VariableOrder`], used to select the branching heuristic of a solver
+///
+/// # Examples
+/// ```rust
+/// use sat_rs::solvers::branching::Branching;
+///
+/// let branching: Branching = "vmtf".parse().unwrap();
+/// assert_eq!(branching, Branching::Vmtf);
+///
+/// let order = branching.build(10);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Branching {
+    /// [`Vsids`] with a decay factor of `0.95`
+    #[default]
+    Vsids,
+    /// [`Vmtf`]
+    Vmtf,
+    /// [`StaticOrder`]
+    Static,
+}
+
+impl Branching {
+    /// Creates the variable order over `num_vars` variables
+    pub fn build(self, num_vars: usize) -> Box<dyn VariableOrder> {
+        match self {
+            Branching::Vsids => Box::new(Vsids::new(num_vars, 0.95)),
+            Branching::Vmtf => Box::new(Vmtf::new(num_vars)),
+            Branching::Static => Box::new(StaticOrder::new(num_vars)),
+        }
+    }
+}
+
+impl FromStr for Branching {
+    type Err = String;
+
+    fn from_str(name: &str) -> Result<Branching, String> {
+        match name {
+            "vsids" => Ok(Branching::Vsids),
+            "vmtf" => Ok(Branching::Vmtf),
+            "static" => Ok(Branching::Static),
+            _ => Err(format!("Unknown branching heuristic: {}", name)),
+        }
+    }
+}
+
+/// Saved polarities of variables (phase saving)
+///
+/// When a variable is unassigned during backtracking its last value is saved, and the next
+/// decision on the variable reuses it. Variables which were never assigned default to `false`.
+///
+/// # Examples
+/// ```rust
+/// use sat_rs::solvers::branching::Phases;
+///
+/// let mut phases = Phases::new(2);
+/// phases.save(1, true);
+///
+/// assert_eq!(phases.get(0), false);
+/// assert_eq!(phases.get(1), true);
+/// ```
+#[derive(Debug, Clone)]
+pub struct Phases {
+    saved: Vec<bool>,
+}
+
+impl Phases {
+    /// Creates new [`Phases`] over `num_vars` variables, all set to `false`
+    pub fn new(num_vars: usize) -> Phases {
+        Phases {
+            saved: vec![false; num_vars],
+        }
+    }
+
+    /// Saves the value of a variable
+    pub fn save(&mut self, var: usize, value: bool) {
+        self.saved[var] = value;
+    }
+
+    /// Returns the saved value of a variable
+    pub fn get(&self, var: usize) -> bool {
+        self.saved[var]
+    }
+}
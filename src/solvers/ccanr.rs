@@ -0,0 +1,350 @@
+use crate::notation::{Formula, Lit};
+use crate::solvers::localsearch::{LocalSearch, LocalSearchAlgorithm, LocalSearchSolver};
+use crate::solvers::result::SolveResult;
+use crate::solvers::solver::SolverStats;
+use crate::solvers::weighting::ClauseWeights;
+use rand::{Rng, RngExt};
+
+/// Parameters of the [`ccanr_algorithm`]
+///
+/// # Examples
+/// ```rust
+/// use sat_rs::solvers::ccanr::CcanrConfig;
+///
+/// let config = CcanrConfig {
+///     smooth_threshold: 100.0,
+///     ..Default::default()
+/// };
+/// assert_eq!(config.rho, 0.3);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CcanrConfig {
+    /// Mean clause weight above which the weights are smoothed, each time the mean grows by `1`
+    pub smooth_threshold: f64,
+    /// Share of its own weight a clause keeps when weights are smoothed, the rest is the
+    /// threshold
+    pub rho: f64,
+}
+
+impl CcanrConfig {
+    /// Checks that the smoothing threshold is finite and at least `1`, so that smoothing keeps
+    /// every weight at least `1`, and that `rho` is between `0` and `1`
+    ///
+    /// # Returns
+    /// * `Result<(), String>` - `Ok` if the configuration is valid, otherwise why it is not
+    ///
+    /// # Examples
+    /// ```rust
+    /// use sat_rs::solvers::ccanr::CcanrConfig;
+    ///
+    /// assert!(CcanrConfig::default().validate().is_ok());
+    ///
+    /// let config = CcanrConfig { rho: 1.5, ..Default::default() };
+    /// assert!(config.validate().is_err());
+    /// ```
+    pub fn validate(&self) -> Result<(), String> {
+        if !(self.smooth_threshold >= 1.0 && self.smooth_threshold.is_finite()) {
+            Err(format!(
+                "Invalid CCAnr smoothing threshold: {}",
+                self.smooth_threshold
+            ))
+        } else if !(0.0..=1.0).contains(&self.rho) {
+            Err(format!("Invalid CCAnr rho: {}", self.rho))
+        } else {
+            Ok(())
+        }
+    }
+}
+
+impl Default for CcanrConfig {
+    fn default() -> Self {
+        CcanrConfig {
+            smooth_threshold: 50.0,
+            rho: 0.3,
+        }
+    }
+}
+
+/// Variables sharing a clause with each variable
+fn neighbours(state: &LocalSearch) -> Vec<Vec<usize>> {
+    let mut neighbours = vec![Vec::new(); state.num_vars()];
+    let mut seen = vec![usize::MAX; state.num_vars()];
+    for var in 0..state.num_vars() {
+        seen[var] = var;
+        for negated in [false, true] {
+            let lit = Lit::new(var, negated);
+            for &clause in state.occurrences(lit) {
+                for other in state.clause(clause) {
+                    if seen[other.var()] != var {
+                        seen[other.var()] = var;
+                        neighbours[var].push(other.var());
+                    }
+                }
+            }
+        }
+    }
+    neighbours
+}
+
+/// Returns `true` if variable `a` has a higher score than `b`, or the same score and was flipped
+/// longer ago, or if there is no `b`
+fn better(state: &LocalSearch, weights: &ClauseWeights, a: usize, b: Option<usize>) -> bool {
+    match b {
+        None => true,
+        Some(b) => {
+            (weights.score(a), std::cmp::Reverse(state.last_flip(a)))
+                > (weights.score(b), std::cmp::Reverse(state.last_flip(b)))
+        }
+    }
+}
+
+/// Selects the best configuration changed decreasing (CCD) variable, or if there is none the best
+/// significant decreasing (SD) variable, by score and then age, among the variables of the
+/// unsatisfied clauses
+///
+/// A variable is decreasing if flipping it lowers the weight of the unsatisfied clauses, and
+/// significant decreasing if it lowers it by more than the mean clause weight.
+///
+/// # Arguments
+/// * `state` - The interpretation
+/// * `weights` - The [`ClauseWeights`] of the clauses of `state`
+/// * `changed` - Whether a neighbour of each variable was flipped since its own last flip
+///
+/// # Returns
+/// * [`Option<usize>`] - The variable to flip, `None` if no variable is CCD nor SD
+pub fn select_decreasing(
+    state: &LocalSearch,
+    weights: &ClauseWeights,
+    changed: &[bool],
+) -> Option<usize> {
+    let mut ccd = None;
+    let mut sd = None;
+    for &clause in state.unsat_clauses() {
+        for lit in state.clause(clause) {
+            let var = lit.var();
+            let score = weights.score(var);
+            if changed[var] && score > 0.0 && better(state, weights, var, ccd) {
+                ccd = Some(var);
+            }
+            if score > weights.mean() && better(state, weights, var, sd) {
+                sd = Some(var);
+            }
+        }
+    }
+    ccd.or(sd)
+}
+
+/// [`Solver`](crate::solvers::solver::Solver) running the [`ccanr_algorithm`]
+///
+/// # Examples
+/// ```rust
+/// use sat_rs::notation::{Literal, Clause};
+/// use sat_rs::solvers::ccanr::{CcanrConfig, CcanrSolver};
+/// use sat_rs::solvers::solver::Solver;
+///
+/// let mut clause = Clause::new(); // Clause: p v q
+/// clause.literals.push(Literal{ value: 1, negated: false});
+/// clause.literals.push(Literal{ value: 2, negated: false});
+///
+/// let mut solver = CcanrSolver::new(10, 10, CcanrConfig::default());
+/// solver.add_clause(clause);
+/// assert!(solver.solve().is_sat());
+/// ```
+pub type CcanrSolver = LocalSearchSolver<Ccanr>;
+
+/// The search of the [`ccanr_algorithm`], run by a [`CcanrSolver`]
+#[derive(Debug, Clone)]
+pub struct Ccanr {
+    max_tries: u32,
+    max_flips: u32,
+    config: CcanrConfig,
+}
+
+impl CcanrSolver {
+    /// Creates a new [`CcanrSolver`]
+    ///
+    /// # Arguments
+    /// * `max_tries` - The number of times to try to find a satisfying interpretation
+    /// * `max_flips` - The number of times to flip the value of a variable in an interpretation
+    /// * `config` - A [`CcanrConfig`] struct
+    pub fn new(max_tries: u32, max_flips: u32, config: CcanrConfig) -> CcanrSolver {
+        LocalSearchSolver::from_algorithm(Ccanr {
+            max_tries,
+            max_flips,
+            config,
+        })
+    }
+}
+
+impl LocalSearchAlgorithm for Ccanr {
+    fn search(
+        &self,
+        formula: &Formula,
+        rng: &mut impl Rng,
+        stats: &mut SolverStats,
+    ) -> SolveResult {
+        let mut state = LocalSearch::new(formula);
+        if state.has_empty_clause() {
+            return SolveResult::Unsat;
+        }
+        let neighbours = neighbours(&state);
+        let mut weights = ClauseWeights::new(&state, 1.0);
+        let mut changed = vec![true; state.num_vars()];
+
+        for _ in 0..self.max_tries {
+            stats.tries += 1;
+
+            // Create a random interpretation with uniform weights, every configuration is new
+            state.randomize(rng);
+            weights.reset(&state, 1.0);
+            changed.fill(true);
+            let mut smoothed_mean = 0.0;
+
+            for _ in 0..self.max_flips {
+                // Check if the interpretation satisfies the formula
+                if state.is_satisfied() {
+                    break;
+                }
+
+                let var = match select_decreasing(&state, &weights, &changed) {
+                    Some(var) => var,
+                    None => {
+                        // Raise the weight of the unsatisfied clauses, smoothing them once heavy
+                        let unsat = state.unsat_clauses().to_vec();
+                        for &clause in &unsat {
+                            weights.add(&state, clause, 1.0);
+                        }
+                        if weights.mean() > self.config.smooth_threshold
+                            && weights.mean() >= smoothed_mean + 1.0
+                        {
+                            let target = self.config.smooth_threshold;
+                            weights.smooth_towards(&state, self.config.rho, target);
+                            smoothed_mean = weights.mean();
+                        }
+
+                        // Flip the best variable of a random unsatisfied clause
+                        let clause = state.clause(unsat[rng.random_range(0..unsat.len())]);
+                        let mut best = None;
+                        for lit in clause {
+                            if better(&state, &weights, lit.var(), best) {
+                                best = Some(lit.var());
+                            }
+                        }
+                        best.unwrap()
+                    }
+                };
+
+                weights.flip(&mut state, var);
+                stats.flips += 1;
+
+                // The neighbours of the variable are in a new configuration, the variable itself
+                // must wait until one of them is flipped
+                changed[var] = false;
+                for &neighbour in &neighbours[var] {
+                    changed[neighbour] = true;
+                }
+            }
+
+            if state.is_satisfied() {
+                return SolveResult::Sat(state.model(&formula.vars));
+            }
+        }
+
+        SolveResult::Unknown(format!(
+            "no model found in {} tries of {} flips",
+            self.max_tries, self.max_flips
+        ))
+    }
+}
+
+/// CCAnr Algorithm for evaluation of propostional formulas
+///
+/// Configuration checking with aspiration for non-random instances. Every variable has a
+/// configuration changed flag, which is cleared when the variable is flipped and set when one of
+/// its neighbours, i.e. the variables sharing a clause with it, is flipped. A variable whose
+/// configuration did not change since its last flip is not flipped again greedily, which avoids
+/// cycling.
+///
+/// Scores are weighted by clause weights (see [`crate::solvers::weighting`]). Each step flips,
+/// by decreasing priority:
+///
+/// 1. the configuration changed decreasing (CCD) variable with the highest score, i.e. with a
+///    changed configuration and a positive score,
+/// 2. by aspiration, the significant decreasing (SD) variable with the highest score, i.e. with
+///    a score above the mean clause weight, even if its configuration did not change,
+/// 3. otherwise, after raising the weight of every unsatisfied clause by `1`, the variable with
+///    the highest score in a random unsatisfied clause.
+///
+/// Ties are broken in favour of the variable flipped longest ago. When the mean weight exceeds
+/// the `smooth_threshold`, and has grown by at least `1` since the last smoothing, every weight
+/// `w` is smoothed to `rho * w + (1 - rho) * smooth_threshold`. This keeps the mean weight
+/// around the threshold and makes the search forget old weights. If all tries are exhausted, the
+/// result is unknown.
+///
+/// # Pseudocode
+/// ```text
+/// procedure CCAnr(Set of Clauses S)
+///
+/// input: A set of clauses S
+/// output: a model of S, or unknown
+/// parameters: max_tries, max_flips, smooth_threshold, rho
+///
+/// begin
+///   repeat max_tries times
+///     create a random interpretation I
+///     set the weight of every clause to 1 and the configuration of every variable as changed
+///     repeat max_flips times
+///       if I satisfies S
+///         then return I
+///       if there is a CCD variable
+///         then p := the CCD variable with the highest score
+///       else if there is an SD variable
+///         then p := the SD variable with the highest score
+///       else
+///         raise the weight of every unsatisfied clause by 1
+///         if the mean weight exceeds smooth_threshold, and grew by 1 since the last smoothing
+///           then w := rho * w + (1 - rho) * smooth_threshold, for every weight w
+///         p := the variable with the highest score in a random unsatisfied clause
+///       flip the value of p in I
+///       mark the configuration of p as unchanged and of its neighbours as changed
+///   return unknown
+/// end
+/// ```
+///
+/// # Arguments
+/// * `formula` - A [`Formula`] struct
+/// * `max_tries` - The number of times to try to find a satisfying interpretation
+/// * `max_flips` - The number of times to flip the value of a variable in an interpretation
+/// * `config` - A [`CcanrConfig`] struct
+/// * `seed` - The seed of the random number generator, `None` to seed from the operating
+///   system
+///
+/// # Returns
+/// * [`SolveResult`] - [`SolveResult::Sat`] with the model, or [`SolveResult::Unknown`]
+///
+/// # Examples
+/// ```
+/// use sat_rs::notation::{Literal, Clause, Formula};
+/// use sat_rs::solvers::ccanr::{self, CcanrConfig};
+///
+/// let mut clause = Clause::new(); // Clause: p v q
+/// clause.literals.push(Literal{ value: 1, negated: false});
+/// clause.literals.push(Literal{ value: 2, negated: false});
+///
+/// let mut formula = Formula::new();
+/// formula.add_clause(clause);
+///
+/// let result = ccanr::ccanr_algorithm(&mut formula, 10, 10, CcanrConfig::default(), Some(1));
+/// assert_eq!(result.is_sat(), true);
+/// ```
+pub fn ccanr_algorithm(
+    formula: &mut Formula,
+    max_tries: u32,
+    max_flips: u32,
+    config: CcanrConfig,
+    seed: Option<u64>,
+) -> SolveResult {
+    CcanrSolver::new(max_tries, max_flips, config)
+        .with_seed(seed)
+        .search(formula)
+}
@@ -0,0 +1,915 @@
+use crate::assignment::Assignment;
+use crate::notation::{Clause, ClauseArena, ClauseRef, Formula, Lit, Literal};
+use crate::proof::ProofWriter;
+use crate::solvers::branching::{Branching, Phases, VariableOrder};
+use crate::solvers::clausedb::{ClauseDb, ClauseDbConfig};
+use crate::solvers::restart::{Restart, RestartPolicy};
+use crate::solvers::result::{Model, SolveResult};
+use crate::solvers::solver::{Solver, SolverStats};
+use std::collections::HashMap;
+use std::io;
+
+/// Configuration of the [`cdcl_algorithm`]
+///
+/// # Examples
+/// ```rust
+/// use sat_rs::solvers::branching::Branching;
+/// use sat_rs::solvers::cdcl::CdclConfig;
+/// use sat_rs::solvers::clausedb::ClauseDbConfig;
+/// use sat_rs::solvers::restart::Restart;
+///
+/// let config = CdclConfig {
+///     branching: Branching::Vmtf,
+///     restart: Restart::Glucose { margin: 1.25, min_conflicts: 50 },
+///     clause_db: ClauseDbConfig::default(),
+/// };
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct CdclConfig {
+    /// The variable order used to pick decision variables
+    pub branching: Branching,
+    /// The policy deciding when to restart the search
+    pub restart: Restart,
+    /// Scoring and reduction schedule of the learnt clauses
+    pub clause_db: ClauseDbConfig,
+}
+
+/// State of the conflict-driven clause learning search
+///
+/// Clauses are stored in a [`ClauseArena`], which is compacted once enough learnt clauses have
+/// been deleted. Every clause with two or more literals watches its first two literals. Clauses
+/// which are the reason for an assignment keep the implied literal in the first position.
+struct Cdcl {
+    arena: ClauseArena,
+    /// Scores of the learnt clauses
+    db: ClauseDb,
+    /// Clauses watching a literal, indexed by [`Lit::index`] and visited when that literal
+    /// becomes false
+    watches: Vec<Vec<ClauseRef>>,
+    assigns: Vec<Option<bool>>,
+    level: Vec<usize>,
+    reason: Vec<Option<ClauseRef>>,
+    trail: Vec<Lit>,
+    /// Position in the trail where each decision level starts
+    trail_lim: Vec<usize>,
+    /// Position in the trail of the next literal to propagate
+    qhead: usize,
+    seen: Vec<bool>,
+    order: Box<dyn VariableOrder>,
+    phases: Phases,
+    restart: Box<dyn RestartPolicy>,
+    /// Scratch space to count the distinct decision levels of a clause
+    level_stamps: Vec<usize>,
+    stamp: usize,
+    /// `false` once a conflict at decision level zero has been found
+    ok: bool,
+    /// Receives the learnt and deleted clauses, and the empty clause once `ok` is `false`
+    proof: Option<ProofWriter>,
+    /// Proof ids of the stored clauses by arena index, kept for LRAT proofs only
+    clause_ids: HashMap<usize, u64>,
+    /// Proof id of the unit clause of each variable assigned at decision level zero, `0` if it
+    /// has not been derived yet
+    unit_ids: Vec<u64>,
+    /// Number of literals at the start of the trail whose unit clause has been derived
+    derived_units: usize,
+    /// Id of the last clause of the proof
+    last_id: u64,
+    stats: SolverStats,
+}
+
+impl Cdcl {
+    fn new(num_vars: usize, config: &CdclConfig) -> Cdcl {
+        Cdcl {
+            arena: ClauseArena::new(),
+            db: ClauseDb::new(config.clause_db.clone()),
+            watches: vec![Vec::new(); 2 * num_vars],
+            assigns: vec![None; num_vars],
+            level: vec![0; num_vars],
+            reason: vec![None; num_vars],
+            trail: Vec::new(),
+            trail_lim: Vec::new(),
+            qhead: 0,
+            seen: vec![false; num_vars],
+            order: config.branching.build(num_vars),
+            phases: Phases::new(num_vars),
+            restart: config.restart.build(),
+            level_stamps: vec![0; num_vars + 1],
+            stamp: 0,
+            ok: true,
+            proof: None,
+            clause_ids: HashMap::new(),
+            unit_ids: vec![0; num_vars],
+            derived_units: 0,
+            last_id: 0,
+            stats: SolverStats::default(),
+        }
+    }
+
+    /// Returns `true` if an LRAT proof is written, which needs the antecedents of every clause
+    fn lrat(&self) -> bool {
+        self.proof
+            .as_ref()
+            .is_some_and(|proof| proof.format().is_lrat())
+    }
+
+    /// Adds a derived clause to the proof, if one is written
+    ///
+    /// # Arguments
+    /// * `lits` - The literals of the clause
+    /// * `hints` - The ids of its antecedents, only needed for LRAT proofs
+    ///
+    /// # Returns
+    /// * `u64` - The id of the clause in the proof
+    fn prove(&mut self, lits: &[Lit], hints: &[i64]) -> u64 {
+        self.last_id += 1;
+        if let Some(proof) = &mut self.proof {
+            let clause: Vec<i32> = lits.iter().map(|lit| lit.to_dimacs()).collect();
+            proof.add(self.last_id, &clause, hints);
+        }
+        self.last_id
+    }
+
+    /// Records the deletion of a clause in the proof, if one is written
+    fn prove_deletion(&mut self, cref: ClauseRef) {
+        let id = self.clause_ids.remove(&cref.index()).unwrap_or(0);
+        if let Some(proof) = &mut self.proof {
+            let clause: Vec<i32> = self
+                .arena
+                .lits(cref)
+                .iter()
+                .map(|lit| lit.to_dimacs())
+                .collect();
+            proof.delete(id, &clause);
+        }
+    }
+
+    /// Derives the unit clauses of the literals propagated at decision level zero, from their
+    /// reason and the unit clauses of the literals before them
+    fn derive_units(&mut self) {
+        let end = self.trail_lim.first().copied().unwrap_or(self.trail.len());
+        while self.derived_units < end {
+            let lit = self.trail[self.derived_units];
+            self.derived_units += 1;
+            if self.unit_ids[lit.var()] != 0 {
+                continue;
+            }
+
+            let cref = self.reason[lit.var()].unwrap();
+            let mut hints: Vec<i64> = self.arena.lits(cref)[1..]
+                .iter()
+                .map(|other| self.unit_ids[other.var()] as i64)
+                .collect();
+            hints.push(self.clause_ids[&cref.index()] as i64);
+            self.unit_ids[lit.var()] = self.prove(&[lit], &hints);
+        }
+    }
+
+    /// Returns the antecedents of a clause implied by a conflict, in the order unit propagation
+    /// uses them once the literals of the clause are false
+    ///
+    /// These are the unit clauses of the literals of decision level zero involved, then the
+    /// reasons of the implied literals involved in trail order, then the conflicting clause.
+    fn chain(&mut self, conflict: ClauseRef, lits: &[Lit]) -> Vec<i64> {
+        self.derive_units();
+
+        // 1 for the literals of the clause, 2 for the implied literals, 3 for units
+        let mut marks = vec![0u8; self.assigns.len()];
+        for lit in lits {
+            marks[lit.var()] = 1;
+        }
+        let mut hints = Vec::new();
+        let mut stack = vec![conflict];
+        while let Some(cref) = stack.pop() {
+            for lit in self.arena.lits(cref) {
+                let v = lit.var();
+                if marks[v] != 0 {
+                    continue;
+                }
+                if self.level[v] == 0 {
+                    marks[v] = 3;
+                    hints.push(self.unit_ids[v] as i64);
+                } else {
+                    marks[v] = 2;
+                    stack.push(self.reason[v].unwrap());
+                }
+            }
+        }
+
+        for lit in &self.trail {
+            if marks[lit.var()] == 2 {
+                let cref = self.reason[lit.var()].unwrap();
+                hints.push(self.clause_ids[&cref.index()] as i64);
+            }
+        }
+        hints.push(self.clause_ids[&conflict.index()] as i64);
+        hints
+    }
+
+    /// Derives the empty clause from a conflict at decision level zero
+    fn prove_refutation(&mut self, conflict: ClauseRef) {
+        let hints = if self.lrat() {
+            self.chain(conflict, &[])
+        } else {
+            Vec::new()
+        };
+        self.prove(&[], &hints);
+    }
+
+    fn value(&self, lit: Lit) -> Option<bool> {
+        self.assigns[lit.var()].map(|value| value != lit.is_negated())
+    }
+
+    fn decision_level(&self) -> usize {
+        self.trail_lim.len()
+    }
+
+    /// Adds an input clause at decision level zero, with its id in the proof
+    ///
+    /// Clauses marked as learnt are registered in the clause database and may be deleted.
+    fn add_clause(&mut self, mut lits: Vec<Lit>, learnt: bool, mut id: u64) {
+        if !self.ok {
+            return;
+        }
+
+        lits.sort_unstable();
+        lits.dedup();
+
+        // Drop tautologies and satisfied clauses, remove false literals
+        if lits.windows(2).any(|pair| pair[0] == !pair[1]) {
+            return;
+        }
+        if lits.iter().any(|lit| self.value(*lit) == Some(true)) {
+            return;
+        }
+        // A clause without its false literals follows from it and their unit clauses
+        let false_lits: Vec<Lit> = lits
+            .iter()
+            .copied()
+            .filter(|lit| self.value(*lit) == Some(false))
+            .collect();
+        lits.retain(|lit| self.value(*lit).is_none());
+        if !false_lits.is_empty() || lits.is_empty() {
+            let mut hints = Vec::new();
+            if self.lrat() {
+                self.derive_units();
+                hints.extend(false_lits.iter().map(|lit| self.unit_ids[lit.var()] as i64));
+                hints.push(id as i64);
+            }
+            id = self.prove(&lits, &hints);
+        }
+
+        match lits.len() {
+            0 => self.ok = false,
+            1 => {
+                self.enqueue(lits[0], None);
+                self.unit_ids[lits[0].var()] = id;
+                if let Some(conflict) = self.propagate() {
+                    self.ok = false;
+                    self.prove_refutation(conflict);
+                }
+            }
+            _ => {
+                let size = lits.len();
+                let cref = self.attach(&lits, learnt, id);
+                if learnt {
+                    self.db.add(cref.index(), size);
+                }
+            }
+        }
+    }
+
+    /// Stores a clause with its proof id and watches its first two literals
+    fn attach(&mut self, lits: &[Lit], learnt: bool, id: u64) -> ClauseRef {
+        let cref = self.arena.alloc(lits, learnt);
+        self.watches[lits[0].index()].push(cref);
+        self.watches[lits[1].index()].push(cref);
+        if self.lrat() {
+            self.clause_ids.insert(cref.index(), id);
+        }
+        cref
+    }
+
+    fn enqueue(&mut self, lit: Lit, reason: Option<ClauseRef>) {
+        let v = lit.var();
+        self.assigns[v] = Some(!lit.is_negated());
+        self.level[v] = self.decision_level();
+        self.reason[v] = reason;
+        self.trail.push(lit);
+    }
+
+    /// Propagates all enqueued assignments using the two watched literals
+    ///
+    /// Returns the conflicting clause, if any.
+    fn propagate(&mut self) -> Option<ClauseRef> {
+        while self.qhead < self.trail.len() {
+            let false_lit = !self.trail[self.qhead];
+            self.qhead += 1;
+            self.stats.propagations += 1;
+
+            let mut watchers = std::mem::take(&mut self.watches[false_lit.index()]);
+            let mut i = 0;
+            let mut j = 0;
+
+            while i < watchers.len() {
+                let cref = watchers[i];
+                i += 1;
+
+                // Make sure the false literal is in the second position
+                let clause = self.arena.lits_mut(cref);
+                if clause[0] == false_lit {
+                    clause.swap(0, 1);
+                }
+                let first = clause[0];
+
+                if self.assigns[first.var()].map(|value| value != first.is_negated()) == Some(true)
+                {
+                    watchers[j] = cref;
+                    j += 1;
+                    continue;
+                }
+
+                // Look for a new literal to watch
+                let clause = self.arena.lits(cref);
+                let replacement = (2..clause.len()).find(|k| {
+                    let lit = clause[*k];
+                    self.assigns[lit.var()].map(|value| value != lit.is_negated()) != Some(false)
+                });
+                if let Some(k) = replacement {
+                    let clause = self.arena.lits_mut(cref);
+                    clause.swap(1, k);
+                    self.watches[clause[1].index()].push(cref);
+                    continue;
+                }
+
+                // The clause is unit or conflicting
+                watchers[j] = cref;
+                j += 1;
+                if self.value(first) == Some(false) {
+                    while i < watchers.len() {
+                        watchers[j] = watchers[i];
+                        i += 1;
+                        j += 1;
+                    }
+                    watchers.truncate(j);
+                    self.watches[false_lit.index()] = watchers;
+                    self.qhead = self.trail.len();
+                    return Some(cref);
+                }
+                self.enqueue(first, Some(cref));
+            }
+
+            watchers.truncate(j);
+            self.watches[false_lit.index()] = watchers;
+        }
+
+        None
+    }
+
+    /// Derives the first-UIP clause of a conflict
+    ///
+    /// Returns the minimized learnt clause, with the asserting literal first and a literal of the
+    /// backjump level second, together with the backjump level.
+    fn analyze(&mut self, conflict: ClauseRef) -> (Vec<Lit>, usize) {
+        let mut learnt: Vec<Lit> = vec![Lit::new(0, false)];
+        let mut path_count = 0;
+        let mut index = self.trail.len();
+        let mut cref = conflict;
+        let mut asserting: Option<Lit> = None;
+
+        loop {
+            if self.arena.is_learnt(cref) {
+                let lbd = self.clause_lbd(cref);
+                self.db.bump(cref.index(), lbd);
+            }
+
+            // Reason clauses hold the implied literal first, which is already accounted for
+            let start = if asserting.is_some() { 1 } else { 0 };
+            for k in start..self.arena.clause_len(cref) {
+                let lit = self.arena.lits(cref)[k];
+                let v = lit.var();
+                if !self.seen[v] && self.level[v] > 0 {
+                    self.seen[v] = true;
+                    self.order.bump(v);
+                    if self.level[v] >= self.decision_level() {
+                        path_count += 1;
+                    } else {
+                        learnt.push(lit);
+                    }
+                }
+            }
+
+            // Select the next literal of the current level to resolve on
+            loop {
+                index -= 1;
+                if self.seen[self.trail[index].var()] {
+                    break;
+                }
+            }
+            let lit = self.trail[index];
+            self.seen[lit.var()] = false;
+            asserting = Some(lit);
+            path_count -= 1;
+
+            if path_count == 0 {
+                break;
+            }
+            cref = self.reason[lit.var()].unwrap();
+        }
+        learnt[0] = !asserting.unwrap();
+
+        // Remove literals implied by the rest of the clause
+        let mut to_clear: Vec<Lit> = learnt.clone();
+        let abstract_levels = learnt[1..]
+            .iter()
+            .fold(0u64, |acc, lit| acc | self.abstract_level(lit.var()));
+        let mut k = 1;
+        while k < learnt.len() {
+            let lit = learnt[k];
+            if self.reason[lit.var()].is_some()
+                && self.is_redundant(lit, abstract_levels, &mut to_clear)
+            {
+                learnt.swap_remove(k);
+            } else {
+                k += 1;
+            }
+        }
+        for lit in to_clear {
+            self.seen[lit.var()] = false;
+        }
+
+        // Find the backjump level and move one of its literals to the second position
+        let mut backjump_level = 0;
+        if learnt.len() > 1 {
+            let mut max_k = 1;
+            for k in 2..learnt.len() {
+                if self.level[learnt[k].var()] > self.level[learnt[max_k].var()] {
+                    max_k = k;
+                }
+            }
+            learnt.swap(1, max_k);
+            backjump_level = self.level[learnt[1].var()];
+        }
+
+        (learnt, backjump_level)
+    }
+
+    /// Computes the literal block distance, i.e. the number of distinct decision levels
+    fn clause_lbd(&mut self, cref: ClauseRef) -> usize {
+        self.stamp += 1;
+        let mut distinct = 0;
+        for lit in self.arena.lits(cref) {
+            let level = self.level[lit.var()];
+            if self.level_stamps[level] != self.stamp {
+                self.level_stamps[level] = self.stamp;
+                distinct += 1;
+            }
+        }
+        distinct
+    }
+
+    fn abstract_level(&self, v: usize) -> u64 {
+        1 << (self.level[v] & 63)
+    }
+
+    /// Checks whether a literal of the learnt clause is implied by the other literals
+    ///
+    /// Literals visited along the way are marked as seen and recorded in `to_clear`. If the
+    /// literal is not redundant, the marks made by this call are undone.
+    fn is_redundant(&mut self, lit: Lit, abstract_levels: u64, to_clear: &mut Vec<Lit>) -> bool {
+        let top = to_clear.len();
+        let mut stack: Vec<Lit> = vec![lit];
+
+        while let Some(current) = stack.pop() {
+            let cref = self.reason[current.var()].unwrap();
+            for k in 1..self.arena.clause_len(cref) {
+                let other = self.arena.lits(cref)[k];
+                let v = other.var();
+                if self.seen[v] || self.level[v] == 0 {
+                    continue;
+                }
+                if self.reason[v].is_some() && self.abstract_level(v) & abstract_levels != 0 {
+                    self.seen[v] = true;
+                    stack.push(other);
+                    to_clear.push(other);
+                } else {
+                    for cleared in to_clear.drain(top..) {
+                        self.seen[cleared.var()] = false;
+                    }
+                    return false;
+                }
+            }
+        }
+
+        true
+    }
+
+    /// Undoes all assignments above the given decision level
+    fn cancel_until(&mut self, level: usize) {
+        if self.decision_level() <= level {
+            return;
+        }
+        let start = self.trail_lim[level];
+        for lit in self.trail.drain(start..) {
+            let v = lit.var();
+            self.assigns[v] = None;
+            self.reason[v] = None;
+            self.phases.save(v, !lit.is_negated());
+            self.order.unassigned(v);
+        }
+        self.trail_lim.truncate(level);
+        self.qhead = start;
+    }
+
+    /// Picks the next decision variable from the variable order, with its saved phase
+    fn pick_branch_lit(&mut self) -> Option<Lit> {
+        let assigns = &self.assigns;
+        let v = self.order.next_var(&|v| assigns[v].is_some())?;
+        Some(Lit::new(v, !self.phases.get(v)))
+    }
+
+    /// Deletes the learnt clauses selected by the clause database
+    ///
+    /// The arena is compacted once deleted clauses take up a fifth of it.
+    fn reduce_db(&mut self) {
+        let deleted = {
+            let arena = &self.arena;
+            let assigns = &self.assigns;
+            let reason = &self.reason;
+            self.db.reduce(&|index| {
+                let cref = ClauseRef::new(index);
+                let lit = arena.lits(cref)[0];
+                assigns[lit.var()].is_some() && reason[lit.var()] == Some(cref)
+            })
+        };
+
+        self.stats.deleted_clauses += deleted.len() as u64;
+        for index in deleted {
+            self.prove_deletion(ClauseRef::new(index));
+            self.arena.delete(ClauseRef::new(index));
+        }
+
+        if self.arena.wasted() > self.arena.size() / 5 {
+            self.collect_garbage();
+        } else {
+            let arena = &self.arena;
+            for watchers in &mut self.watches {
+                watchers.retain(|cref| !arena.is_deleted(*cref));
+            }
+        }
+    }
+
+    /// Compacts the clause arena and updates every clause reference
+    fn collect_garbage(&mut self) {
+        let relocation = self.arena.collect_garbage();
+
+        for watchers in &mut self.watches {
+            *watchers = watchers
+                .iter()
+                .filter_map(|cref| relocation.get(*cref))
+                .collect();
+        }
+        for reason in &mut self.reason {
+            *reason = reason.and_then(|cref| relocation.get(cref));
+        }
+        self.clause_ids = self
+            .clause_ids
+            .drain()
+            .filter_map(|(index, id)| Some((relocation.get(ClauseRef::new(index))?.index(), id)))
+            .collect();
+        self.db
+            .relocate(&|index| relocation.get(ClauseRef::new(index)).map(ClauseRef::index));
+    }
+
+    /// Runs the search until a model or a refutation is found
+    ///
+    /// The assumptions are decided first, one per decision level. Returns `false` if the clauses
+    /// are unsatisfiable together with the assumptions, which only makes the solver unusable if
+    /// the clauses alone are unsatisfiable.
+    fn solve(&mut self, assumptions: &[Lit]) -> bool {
+        if !self.ok {
+            return false;
+        }
+
+        loop {
+            if let Some(conflict) = self.propagate() {
+                self.stats.conflicts += 1;
+                if self.decision_level() == 0 {
+                    self.ok = false;
+                    self.prove_refutation(conflict);
+                    return false;
+                }
+
+                let (learnt, backjump_level) = self.analyze(conflict);
+                let hints = if self.lrat() {
+                    self.chain(conflict, &learnt)
+                } else {
+                    Vec::new()
+                };
+                let id = self.prove(&learnt, &hints);
+                self.stats.learnt_clauses += 1;
+                self.order.decay();
+                self.db.conflict();
+                self.cancel_until(backjump_level);
+                if learnt.len() == 1 {
+                    self.restart.conflict(1);
+                    self.enqueue(learnt[0], None);
+                    self.unit_ids[learnt[0].var()] = id;
+                } else {
+                    let asserting = learnt[0];
+                    let cref = self.attach(&learnt, true, id);
+                    let lbd = self.clause_lbd(cref);
+                    self.restart.conflict(lbd);
+                    self.db.add(cref.index(), lbd);
+                    self.enqueue(asserting, Some(cref));
+                }
+            } else {
+                if self.restart.should_restart() {
+                    self.stats.restarts += 1;
+                    self.cancel_until(0);
+                }
+                if self.db.should_reduce() {
+                    self.reduce_db();
+                }
+
+                // Decide the next assumption, or pick a branching literal once all hold
+                let mut next: Option<Lit> = None;
+                while self.decision_level() < assumptions.len() {
+                    let lit = assumptions[self.decision_level()];
+                    match self.value(lit) {
+                        Some(true) => self.trail_lim.push(self.trail.len()),
+                        Some(false) => return false,
+                        None => {
+                            next = Some(lit);
+                            break;
+                        }
+                    }
+                }
+                if next.is_none() {
+                    next = self.pick_branch_lit();
+                }
+
+                match next {
+                    Some(lit) => {
+                        self.stats.decisions += 1;
+                        self.trail_lim.push(self.trail.len());
+                        self.enqueue(lit, None);
+                    }
+                    None => return true,
+                }
+            }
+        }
+    }
+}
+
+/// [`Solver`] running the [`cdcl_algorithm`] incrementally
+///
+/// Learnt clauses and heuristic scores are kept between calls to solve, and assumptions are
+/// decided before any other variable instead of being added to the clauses. Adding a clause with
+/// a variable larger than all variables seen so far starts the search state afresh.
+///
+/// # Examples
+/// ```rust
+/// use sat_rs::notation::{Literal, Clause};
+/// use sat_rs::solvers::cdcl::{CdclConfig, CdclSolver};
+/// use sat_rs::solvers::solver::Solver;
+///
+/// let mut clause = Clause::new(); // Clause: -p v q
+/// clause.literals.push(Literal{ value: 1, negated: true});
+/// clause.literals.push(Literal{ value: 2, negated: false});
+///
+/// let mut solver = CdclSolver::new(CdclConfig::default());
+/// solver.add_clause(clause);
+///
+/// let result = solver.solve_with_assumptions(&[Literal{ value: 1, negated: false}]);
+/// assert_eq!(result.model().unwrap().value(2), Some(true));
+/// ```
+pub struct CdclSolver {
+    config: CdclConfig,
+    /// Every clause added so far, used to rebuild the search state when variables are added
+    clauses: ClauseArena,
+    /// DIMACS variables seen so far, in order of appearance
+    vars: Vec<i32>,
+    known: Vec<bool>,
+    search: Option<Cdcl>,
+    /// Proof handed to the search state when it is built
+    proof: Option<ProofWriter>,
+    /// Id of the last clause of the proof, kept so that a rebuilt search state numbers its
+    /// clauses after it
+    last_id: u64,
+    model: Option<Model>,
+    stats: SolverStats,
+}
+
+impl CdclSolver {
+    /// Creates a new [`CdclSolver`] with the given configuration
+    pub fn new(config: CdclConfig) -> CdclSolver {
+        CdclSolver {
+            config,
+            clauses: ClauseArena::new(),
+            vars: Vec::new(),
+            known: Vec::new(),
+            search: None,
+            proof: None,
+            last_id: 0,
+            model: None,
+            stats: SolverStats::default(),
+        }
+    }
+
+    /// Converts a literal to a packed [`Lit`], registering its variable
+    ///
+    /// Drops the search state if the variable does not fit in it.
+    fn encode(&mut self, literal: &Literal) -> Lit {
+        let index = literal.value as usize - 1;
+        if self.known.len() <= index {
+            self.known.resize(index + 1, false);
+            self.drop_search();
+        }
+        if !self.known[index] {
+            self.known[index] = true;
+            self.vars.push(literal.value);
+        }
+        Lit::from(literal)
+    }
+
+    /// Drops the search state, keeping its statistics and proof
+    fn drop_search(&mut self) {
+        if let Some(search) = self.search.take() {
+            self.stats = search.stats;
+            self.proof = search.proof;
+            self.last_id = search.last_id;
+        }
+    }
+
+    /// Returns the search state, building it from the clauses added so far if needed
+    fn search(&mut self) -> &mut Cdcl {
+        if self.search.is_none() {
+            let mut search = Cdcl::new(self.known.len(), &self.config);
+            search.stats = std::mem::take(&mut self.stats);
+            search.proof = self.proof.take();
+            search.last_id = self.last_id.max(self.clauses.len() as u64);
+            for (index, cref) in self.clauses.iter().enumerate() {
+                let lits = self.clauses.lits(cref).to_vec();
+                search.add_clause(lits, self.clauses.is_learnt(cref), index as u64 + 1);
+            }
+            self.search = Some(search);
+        }
+        self.search.as_mut().unwrap()
+    }
+}
+
+impl Solver for CdclSolver {
+    fn add_clause(&mut self, clause: Clause) {
+        let lits: Vec<Lit> = clause
+            .literals
+            .iter()
+            .map(|literal| self.encode(literal))
+            .collect();
+        if let Some(search) = &mut self.search {
+            let id = self.clauses.len() as u64 + 1;
+            search.add_clause(lits.clone(), clause.learnt, id);
+        }
+        self.clauses.alloc(&lits, clause.learnt);
+    }
+
+    fn solve_with_assumptions(&mut self, assumptions: &[Literal]) -> SolveResult {
+        let assumptions: Vec<Lit> = assumptions
+            .iter()
+            .map(|literal| self.encode(literal))
+            .collect();
+        let vars = self.vars.clone();
+        let search = self.search();
+
+        let sat = search.solve(&assumptions);
+        let model = sat.then(|| {
+            let mut interpretation = Assignment::new(search.assigns.len());
+            for var in &vars {
+                interpretation.set(*var, search.assigns[*var as usize - 1].unwrap_or(false));
+            }
+            Model::from(interpretation)
+        });
+        search.cancel_until(0);
+        self.model = model;
+
+        match &self.model {
+            Some(model) => SolveResult::Sat(model.clone()),
+            None => SolveResult::Unsat,
+        }
+    }
+
+    fn model(&self) -> Option<&Model> {
+        self.model.as_ref()
+    }
+
+    fn stats(&self) -> &SolverStats {
+        match &self.search {
+            Some(search) => &search.stats,
+            None => &self.stats,
+        }
+    }
+
+    fn set_proof(&mut self, proof: ProofWriter) -> bool {
+        // LRAT proofs need the ids of the clauses, which are only kept from the start
+        if proof.format().is_lrat() {
+            self.drop_search();
+        }
+        match &mut self.search {
+            Some(search) => search.proof = Some(proof),
+            None => self.proof = Some(proof),
+        }
+        true
+    }
+
+    fn finish_proof(&mut self) -> io::Result<()> {
+        let proof = match &mut self.search {
+            Some(search) => &mut search.proof,
+            None => &mut self.proof,
+        };
+        match proof {
+            Some(proof) => proof.finish(),
+            None => Ok(()),
+        }
+    }
+}
+
+/// CDCL Algorithm for evaluation of propositional formulas
+///
+/// This algorithm extends DPLL with conflict-driven clause learning. Unit propagation is done
+/// with two watched literals per clause, so that only clauses watching a literal that became false
+/// are visited. Every assignment is recorded on a trail together with its decision level and the
+/// clause that implied it. When a clause becomes false, the conflict is analysed by resolving
+/// the conflicting clause with the reasons of the trail literals until only one literal of the
+/// current decision level is left (the first unique implication point). The resulting clause is
+/// minimized by removing literals implied by the others, learnt, and the search jumps back to the
+/// second highest decision level in the clause, where the learnt clause becomes unit.
+///
+/// Decision variables are picked by the variable order selected in the [`CdclConfig`] (see
+/// [`crate::solvers::branching`]), which is bumped for every variable seen during conflict
+/// analysis. Decisions reuse the last value a variable had before it was unassigned. The search
+/// is restarted from decision level zero whenever the [`crate::solvers::restart`] policy of the
+/// configuration asks for it. Learnt clauses are scored by their literal block distance and
+/// periodically reduced by the [`crate::solvers::clausedb`] of the configuration. Clauses of the
+/// formula marked as learnt are treated like learnt clauses.
+///
+/// Given a [`crate::proof::ProofWriter`], the solver writes every learnt clause as an addition,
+/// every deleted learnt clause as a deletion, and the empty clause once the formula is refuted.
+/// Input clauses shortened by the assignments of decision level zero are added as well. In the
+/// LRAT formats every learnt clause lists the unit clauses and reasons which conflict analysis
+/// resolved, in trail order, so the literals implied at decision level zero are first added as
+/// unit clauses. The clauses of the formula are numbered in the order they were added, which
+/// matches the checker when the whole formula is added before the first solve.
+///
+/// # Pseudocode
+/// ```text
+/// procedure CDCL(Set of Clauses S)
+///
+/// input: A set of clauses S
+/// output: a model of S, or unsatisfiable
+///
+/// begin
+///   I := empty interpretation
+///   loop
+///     propagate units of S under I
+///     if some clause C in S is false under I
+///       if I contains no decision
+///         then return unsatisfiable
+///       L := first-UIP clause derived from C, minimized
+///       S := S ∪ {L}
+///       undo I up to the second highest decision level of L
+///     else if every variable is assigned
+///       then return I
+///     else
+///       p := some unassigned variable
+///       I := I ∪ {p} (as a new decision level)
+/// end
+/// ```
+///
+/// # Arguments
+/// * `formula` - A [`Formula`] struct
+/// * `config` - A [`CdclConfig`] struct
+///
+/// # Returns
+/// * [`SolveResult`] - [`SolveResult::Sat`] with the model, or [`SolveResult::Unsat`]
+///
+/// # Examples
+/// ```
+/// use sat_rs::notation::{Literal, Clause, Formula};
+/// use sat_rs::solvers::cdcl;
+///
+/// let mut clause = Clause::new(); // Clause: p v q
+/// let p = Literal{ value: 1, negated: false};
+/// let q = Literal{ value: 2, negated: false};
+/// clause.literals.push(p);
+/// clause.literals.push(q);
+///
+/// let mut formula = Formula::new();
+/// formula.add_clause(clause);
+///
+/// let result = cdcl::cdcl_algorithm(&mut formula, &cdcl::CdclConfig::default());
+/// assert_eq!(result.is_sat(), true);
+/// ```
+pub fn cdcl_algorithm(formula: &mut Formula, config: &CdclConfig) -> SolveResult {
+    let mut solver = CdclSolver::new(config.clone());
+    solver.add_formula(formula);
+    solver.solve()
+}
@@ -1,34 +1,101 @@
+use crate::assignment::Assignment;
 use crate::notation::Formula;
-use std::collections::HashMap;
+use crate::solvers::localsearch::{LocalSearchAlgorithm, LocalSearchSolver};
+use crate::solvers::result::{Model, SolveResult};
+use crate::solvers::solver::SolverStats;
+use rand::{Rng, RngExt};
+
+/// [`Solver`](crate::solvers::solver::Solver) running the [`chaos_algorithm`]
+///
+/// # Examples
+/// ```rust
+/// use sat_rs::notation::{Literal, Clause};
+/// use sat_rs::solvers::chaos::ChaosSolver;
+/// use sat_rs::solvers::solver::Solver;
+///
+/// let mut clause = Clause::new(); // Clause: p
+/// clause.literals.push(Literal{ value: 1, negated: false});
+///
+/// let mut solver = ChaosSolver::new(100);
+/// solver.add_clause(clause);
+/// assert!(solver.solve().is_sat());
+/// assert_eq!(solver.stats().flips, 0);
+/// ```
+pub type ChaosSolver = LocalSearchSolver<Chaos>;
+
+/// The search of the [`chaos_algorithm`], run by a [`ChaosSolver`]
+#[derive(Debug, Clone)]
+pub struct Chaos {
+    max_tries: u32,
+}
+
+impl ChaosSolver {
+    /// Creates a new [`ChaosSolver`] giving up after `max_tries` random interpretations
+    pub fn new(max_tries: u32) -> ChaosSolver {
+        LocalSearchSolver::from_algorithm(Chaos { max_tries })
+    }
+}
+
+impl LocalSearchAlgorithm for Chaos {
+    fn search(
+        &self,
+        formula: &Formula,
+        rng: &mut impl Rng,
+        stats: &mut SolverStats,
+    ) -> SolveResult {
+        for _ in 0..self.max_tries {
+            stats.tries += 1;
+
+            // Create a random interpretation
+            let mut interpretation =
+                Assignment::new(formula.vars.iter().copied().max().unwrap_or(0) as usize);
+
+            for var in &formula.vars {
+                interpretation.set(*var, rng.random_bool(0.5));
+            }
+
+            // Check if the interpretation satisfies the formula
+            if formula.evaluate(&interpretation) == Some(true) {
+                return SolveResult::Sat(Model::from(interpretation));
+            }
+        }
+
+        SolveResult::Unknown(format!("no model found in {} tries", self.max_tries))
+    }
+}
 
 /// Chaos Algorithm for evaluation of propostional formulas
 ///
 /// This algorithm generates random interpretations and checks if the formula is satisfied by the
-/// interpretation. If the formula is satisfied, the algorithm returns true. If the formula is not
-/// satisfied, the algorithm returns false.
+/// interpretation. If the formula is satisfied, the algorithm returns the interpretation as a
+/// model. If no interpretation satisfies the formula, the algorithm gives up and the result is
+/// unknown.
 ///
 /// # Pseudocode
 /// ```text
 /// procedure CHAOS(Set of Clauses S)
 ///
 /// input: A set of clauses S
-/// output: true if S is satisfiable, false otherwise
+/// output: a model of S, or unknown
 /// parameters: max_tries
 ///
 /// begin
 ///   repeat max_tries times
 ///     create a random interpretation I
 ///     if I satisfies S
-///      then return true else return false
+///      then return I
+///   return unknown
 /// end
 /// ```
 ///
 /// # Arguments
 /// * `formula` - A [`Formula`] struct
 /// * `max_tries` - The number of times to try to find a satisfying interpretation
+/// * `seed` - The seed of the random number generator, `None` to seed from the operating
+///   system
 ///
 /// # Returns
-/// * `bool` - The value of the formula
+/// * [`SolveResult`] - [`SolveResult::Sat`] with the model, or [`SolveResult::Unknown`]
 ///
 /// # Examples
 /// ```
@@ -43,40 +110,13 @@ use std::collections::HashMap;
 /// let q = Literal{ value: 1, negated: true};
 /// invalid_clause.literals.push(q);
 ///
-/// let mut formula = Formula {
-///     clauses: vec![valid_clause, invalid_clause],
-///     literals: vec![Literal{ value: 1, negated: false}, Literal{ value: 1, negated: true}],
-///     vars: vec![1],
-///     num_clauses: 2,
-///     num_vars: 1,
-/// };
+/// let mut formula = Formula::new();
+/// formula.add_clause(valid_clause);
+/// formula.add_clause(invalid_clause);
 ///
-/// let result = chaos::chaos_algorithm(&mut formula, 10);
-/// assert_eq!(result, false);
+/// let result = chaos::chaos_algorithm(&mut formula, 10, Some(1));
+/// assert_eq!(result.is_sat(), false);
 /// ```
-pub fn chaos_algorithm(formula: &mut Formula, max_tries: u32) -> bool {
-    let mut value: bool = true;
-
-    for _ in 0..max_tries {
-        // Create a random interpretation
-        let mut interpretation: HashMap<i32, bool> = HashMap::new();
-
-        for var in &formula.vars {
-            interpretation.insert(*var, rand::random::<bool>());
-        }
-
-        // Check if the interpretation satisfies the formula
-        if formula.evaluate(&interpretation) {
-            value = true;
-            println!(
-                "Formula is satisfied by the interpretation: {:?}",
-                interpretation
-            );
-            break;
-        } else {
-            value = false;
-        }
-    }
-
-    return value;
+pub fn chaos_algorithm(formula: &mut Formula, max_tries: u32, seed: Option<u64>) -> SolveResult {
+    ChaosSolver::new(max_tries).with_seed(seed).search(formula)
 }
@@ -0,0 +1,251 @@
+//! # Learnt Clause Database
+//!
+//! Bookkeeping for the clauses learnt by a conflict-driven solver, deciding which of them are
+//! deleted periodically so that memory and propagation cost stay bounded.
+//!
+//! Learnt clauses are scored by their literal block distance (LBD), the number of distinct
+//! decision levels among their literals, and by an activity which is bumped whenever the clause
+//! takes part in a conflict. They are split into three tiers:
+//!
+//! * [`Tier::Core`] - Clauses with a very small LBD, which are never deleted.
+//! * [`Tier::Tier2`] - Clauses with a small LBD, kept as long as they are used regularly.
+//! * [`Tier::Local`] - All other clauses, of which the worse half by LBD and then activity is
+//!   deleted at every reduction.
+//!
+//! The solver owns the clauses themselves and refers to them by index, this module only stores
+//! their scores and selects the ones to delete. Indices need not be dense, so the positions of
+//! clauses in a [`crate::notation::ClauseArena`] can be used, see [`ClauseDb::relocate`].
+use std::collections::HashMap;
+
+/// Configuration of a [`ClauseDb`]
+///
+/// # Examples
+/// ```rust
+/// use sat_rs::solvers::clausedb::ClauseDbConfig;
+///
+/// let config = ClauseDbConfig {
+///     reduce_interval: 5000,
+///     ..Default::default()
+/// };
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct ClauseDbConfig {
+    /// Number of conflicts before the first reduction
+    pub reduce_interval: u64,
+    /// Growth of the number of conflicts between two reductions after each reduction
+    pub reduce_increment: u64,
+    /// Clauses with an LBD up to this value belong to the core tier
+    pub core_lbd: usize,
+    /// Clauses with an LBD up to this value belong to tier 2
+    pub tier2_lbd: usize,
+    /// Tier 2 clauses unused for this many conflicts are handled as local clauses
+    pub tier2_window: u64,
+    /// Decay factor of clause activities, applied after each conflict
+    pub activity_decay: f64,
+}
+
+impl Default for ClauseDbConfig {
+    fn default() -> Self {
+        ClauseDbConfig {
+            reduce_interval: 2000,
+            reduce_increment: 300,
+            core_lbd: 2,
+            tier2_lbd: 6,
+            tier2_window: 10000,
+            activity_decay: 0.999,
+        }
+    }
+}
+
+/// Tier of a learnt clause, see the [module documentation](self)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Tier {
+    Core,
+    Tier2,
+    Local,
+}
+
+/// Scores of a learnt clause
+#[derive(Debug, Clone)]
+struct LearntInfo {
+    lbd: usize,
+    activity: f64,
+    /// Conflict count when the clause was last used in conflict analysis
+    last_used: u64,
+}
+
+/// Scores of the learnt clauses of a solver and the schedule of their reductions
+///
+/// # Examples
+/// ```rust
+/// use sat_rs::solvers::clausedb::{ClauseDb, ClauseDbConfig, Tier};
+///
+/// let mut db = ClauseDb::new(ClauseDbConfig::default());
+/// db.add(0, 2);
+/// db.add(1, 9);
+///
+/// assert_eq!(db.tier(0), Some(Tier::Core));
+/// assert_eq!(db.tier(1), Some(Tier::Local));
+/// assert_eq!(db.tier(2), None);
+/// ```
+#[derive(Debug, Clone)]
+pub struct ClauseDb {
+    config: ClauseDbConfig,
+    /// Scores of the registered learnt clauses, by clause index
+    info: HashMap<usize, LearntInfo>,
+    increment: f64,
+    conflicts: u64,
+    next_reduce: u64,
+    reductions: u64,
+}
+
+impl ClauseDb {
+    /// Creates a new, empty [`ClauseDb`]
+    pub fn new(config: ClauseDbConfig) -> ClauseDb {
+        ClauseDb {
+            next_reduce: config.reduce_interval,
+            config,
+            info: HashMap::new(),
+            increment: 1.0,
+            conflicts: 0,
+            reductions: 0,
+        }
+    }
+
+    /// Registers a learnt clause
+    ///
+    /// # Arguments
+    /// * `cref` - The index of the clause in the solver
+    /// * `lbd` - The literal block distance of the clause
+    pub fn add(&mut self, cref: usize, lbd: usize) {
+        self.info.insert(
+            cref,
+            LearntInfo {
+                lbd,
+                activity: 0.0,
+                last_used: self.conflicts,
+            },
+        );
+    }
+
+    /// Returns `true` if the clause is a registered learnt clause
+    pub fn is_learnt(&self, cref: usize) -> bool {
+        self.info.contains_key(&cref)
+    }
+
+    /// Returns the literal block distance of a learnt clause
+    pub fn lbd(&self, cref: usize) -> Option<usize> {
+        self.info.get(&cref).map(|info| info.lbd)
+    }
+
+    /// Returns the tier of a learnt clause
+    pub fn tier(&self, cref: usize) -> Option<Tier> {
+        let info = self.info.get(&cref)?;
+        Some(self.tier_of(info))
+    }
+
+    fn tier_of(&self, info: &LearntInfo) -> Tier {
+        if info.lbd <= self.config.core_lbd {
+            Tier::Core
+        } else if info.lbd <= self.config.tier2_lbd
+            && self.conflicts - info.last_used <= self.config.tier2_window
+        {
+            Tier::Tier2
+        } else {
+            Tier::Local
+        }
+    }
+
+    /// Records that a learnt clause was used in conflict analysis
+    ///
+    /// Bumps its activity and, if the clause now spans fewer decision levels, lowers its LBD.
+    ///
+    /// # Arguments
+    /// * `cref` - The index of the clause in the solver
+    /// * `lbd` - The current literal block distance of the clause
+    pub fn bump(&mut self, cref: usize, lbd: usize) {
+        let Some(info) = self.info.get_mut(&cref) else {
+            return;
+        };
+        info.activity += self.increment;
+        info.last_used = self.conflicts;
+        info.lbd = info.lbd.min(lbd);
+
+        // Rescale all activities to avoid overflowing
+        if info.activity > 1e20 {
+            for info in self.info.values_mut() {
+                info.activity *= 1e-20;
+            }
+            self.increment *= 1e-20;
+        }
+    }
+
+    /// Records the end of a conflict, decaying the clause activities
+    pub fn conflict(&mut self) {
+        self.conflicts += 1;
+        self.increment /= self.config.activity_decay;
+    }
+
+    /// Returns `true` if enough conflicts have passed since the last reduction
+    pub fn should_reduce(&self) -> bool {
+        self.conflicts >= self.next_reduce
+    }
+
+    /// Selects the learnt clauses to delete and schedules the next reduction
+    ///
+    /// Core and tier 2 clauses are kept, as well as clauses for which `is_locked` returns `true`,
+    /// typically because they are the reason of a current assignment. Of the remaining local
+    /// clauses, the half with the highest LBD is deleted, ties being broken by lowest activity.
+    /// The selected clauses are unregistered.
+    ///
+    /// # Arguments
+    /// * `is_locked` - Returns `true` if the clause with the given index must not be deleted
+    ///
+    /// # Returns
+    /// * [`Vec<usize>`] - The indices of the clauses to delete
+    pub fn reduce(&mut self, is_locked: &dyn Fn(usize) -> bool) -> Vec<usize> {
+        self.reductions += 1;
+        self.next_reduce = self.conflicts
+            + self.config.reduce_interval
+            + self.reductions * self.config.reduce_increment;
+
+        let mut candidates: Vec<(usize, usize, f64)> = self
+            .info
+            .iter()
+            .filter(|(cref, info)| self.tier_of(info) == Tier::Local && !is_locked(**cref))
+            .map(|(cref, info)| (*cref, info.lbd, info.activity))
+            .collect();
+
+        // Delete clauses with a high LBD and low activity first, older clauses on ties
+        candidates.sort_by(|a, b| b.1.cmp(&a.1).then(a.2.total_cmp(&b.2)).then(a.0.cmp(&b.0)));
+        candidates.truncate(candidates.len() / 2);
+
+        candidates
+            .into_iter()
+            .map(|(cref, _, _)| {
+                self.info.remove(&cref);
+                cref
+            })
+            .collect()
+    }
+
+    /// Moves the scores of the learnt clauses to new indices
+    ///
+    /// Used after the solver has compacted its clauses, e.g. with
+    /// [`crate::notation::ClauseArena::collect_garbage`].
+    ///
+    /// # Arguments
+    /// * `relocate` - Returns the new index of a clause, or `None` to unregister it
+    pub fn relocate(&mut self, relocate: &dyn Fn(usize) -> Option<usize>) {
+        self.info = self
+            .info
+            .drain()
+            .filter_map(|(cref, info)| Some((relocate(cref)?, info)))
+            .collect();
+    }
+
+    /// Returns the number of reductions done so far
+    pub fn reductions(&self) -> u64 {
+        self.reductions
+    }
+}
@@ -0,0 +1,412 @@
+use crate::assignment::Assignment;
+use crate::notation::{Clause, Formula, Literal};
+use crate::proof::ProofWriter;
+use crate::solvers::branching::{Branching, Phases, VariableOrder};
+use crate::solvers::result::{Model, SolveResult};
+use crate::solvers::solver::{with_assumptions, Solver, SolverStats};
+use std::io;
+
+/// An entry on the assignment trail
+///
+/// `decision` is `true` for branching decisions whose opposite value has not been tried yet.
+/// Implied (unit or pure) assignments and flipped decisions are recorded with `decision` set to
+/// `false`, so they are simply undone when backtracking past them.
+struct TrailEntry {
+    var: i32,
+    decision: bool,
+}
+
+/// State of a clause under a partial interpretation
+enum ClauseState {
+    Satisfied,
+    Falsified,
+    Unit(i32),
+    Unresolved,
+}
+
+/// [`Solver`] running the [`dpll_algorithm`]
+///
+/// # Examples
+/// ```rust
+/// use sat_rs::notation::{Literal, Clause};
+/// use sat_rs::solvers::branching::Branching;
+/// use sat_rs::solvers::dpll::DpllSolver;
+/// use sat_rs::solvers::solver::Solver;
+///
+/// let mut clause = Clause::new(); // Clause: -p
+/// clause.literals.push(Literal{ value: 1, negated: true});
+///
+/// let mut solver = DpllSolver::new(Branching::Vsids);
+/// solver.add_clause(clause);
+/// assert!(solver.solve().is_sat());
+/// assert_eq!(solver.model().unwrap().value(1), Some(false));
+/// assert!(solver.solve_with_assumptions(&[Literal{ value: 1, negated: false}]).is_unsat());
+/// ```
+#[derive(Debug)]
+pub struct DpllSolver {
+    formula: Formula,
+    branching: Branching,
+    proof: Option<ProofWriter>,
+    model: Option<Model>,
+    stats: SolverStats,
+}
+
+impl DpllSolver {
+    /// Creates a new [`DpllSolver`] picking decision variables with the given [`Branching`]
+    pub fn new(branching: Branching) -> DpllSolver {
+        DpllSolver {
+            formula: Formula::new(),
+            branching,
+            proof: None,
+            model: None,
+            stats: SolverStats::default(),
+        }
+    }
+
+    fn search(&mut self, formula: &Formula) -> SolveResult {
+        // Signed DIMACS literals of every clause
+        let clauses: Vec<Vec<i32>> = formula
+            .clauses
+            .iter()
+            .map(|clause| {
+                clause
+                    .literals
+                    .iter()
+                    .map(|literal| {
+                        if literal.negated {
+                            -literal.value
+                        } else {
+                            literal.value
+                        }
+                    })
+                    .collect()
+            })
+            .collect();
+
+        // The variable order and phases use zero-based indices, DIMACS variable `v` has index `v - 1`
+        let num_vars = formula.vars.iter().copied().max().unwrap_or(0) as usize;
+        let mut order = self.branching.build(num_vars);
+        let mut phases = Phases::new(num_vars);
+
+        let mut interpretation = Assignment::new(num_vars);
+        let mut trail: Vec<TrailEntry> = Vec::new();
+
+        loop {
+            let assigned = trail.len();
+            let conflict = propagate(&clauses, &mut interpretation, &mut trail);
+            self.stats.propagations += (trail.len() - assigned) as u64;
+
+            if let Some(conflict) = conflict {
+                self.stats.conflicts += 1;
+                for lit in &clauses[conflict] {
+                    order.bump(lit.unsigned_abs() as usize - 1);
+                }
+                order.decay();
+
+                // Every decision cannot hold together, which is the empty clause if there is none
+                let lemma = negated_decisions(&interpretation, &trail);
+                if let Some(proof) = &mut self.proof {
+                    proof.add(0, &lemma, &[]);
+                }
+
+                if !backtrack(&mut interpretation, &mut trail, &mut *order, &mut phases) {
+                    return SolveResult::Unsat;
+                }
+            } else {
+                let assigned = trail.len();
+                eliminate_pure_literals(&clauses, &mut interpretation, &mut trail);
+
+                // A pure literal is implied by the decisions, up to satisfiability
+                if let Some(proof) = &mut self.proof {
+                    let decisions = negated_decisions(&interpretation, &trail[..assigned]);
+                    for entry in &trail[assigned..] {
+                        let lit = if interpretation.get(entry.var) == Some(true) {
+                            entry.var
+                        } else {
+                            -entry.var
+                        };
+                        let lemma: Vec<i32> =
+                            std::iter::once(lit).chain(decisions.clone()).collect();
+                        proof.add(0, &lemma, &[]);
+                    }
+                }
+
+                let unresolved = clauses.iter().any(|clause| {
+                    !matches!(
+                        clause_state(clause, &interpretation),
+                        ClauseState::Satisfied
+                    )
+                });
+
+                if unresolved {
+                    let index = order
+                        .next_var(&|index| interpretation.is_assigned(index as i32 + 1))
+                        .unwrap();
+                    let var = index as i32 + 1;
+                    self.stats.decisions += 1;
+                    interpretation.set(var, phases.get(index));
+                    trail.push(TrailEntry {
+                        var,
+                        decision: true,
+                    });
+                } else {
+                    // Every clause is satisfied, variables which do not matter default to false
+                    for var in &formula.vars {
+                        if !interpretation.is_assigned(*var) {
+                            interpretation.set(*var, false);
+                        }
+                    }
+                    return SolveResult::Sat(Model::from(interpretation));
+                }
+            }
+        }
+    }
+}
+
+impl Solver for DpllSolver {
+    fn add_clause(&mut self, clause: Clause) {
+        self.formula.add_clause(clause);
+    }
+
+    fn solve_with_assumptions(&mut self, assumptions: &[Literal]) -> SolveResult {
+        let formula = with_assumptions(&self.formula, assumptions);
+        let result = self.search(&formula);
+        self.model = result.model().cloned();
+        result
+    }
+
+    fn model(&self) -> Option<&Model> {
+        self.model.as_ref()
+    }
+
+    fn stats(&self) -> &SolverStats {
+        &self.stats
+    }
+
+    fn set_proof(&mut self, proof: ProofWriter) -> bool {
+        // The lemmas are not tracked back to their antecedents
+        if proof.format().is_lrat() {
+            return false;
+        }
+        self.proof = Some(proof);
+        true
+    }
+
+    fn finish_proof(&mut self) -> io::Result<()> {
+        match &mut self.proof {
+            Some(proof) => proof.finish(),
+            None => Ok(()),
+        }
+    }
+}
+
+/// DPLL Algorithm for evaluation of propositional formulas
+///
+/// This algorithm performs a systematic backtracking search over partial interpretations. Before
+/// every decision the interpretation is extended with all unit clauses (unit propagation) and all
+/// literals which occur with only one polarity in the remaining clauses (pure literal
+/// elimination). If a clause becomes false, the algorithm backtracks to the most recent decision
+/// and tries the opposite value. Unlike the local search solvers, the algorithm always decides
+/// whether the formula is satisfiable.
+///
+/// Decision variables are picked by the given variable order (see [`crate::solvers::branching`]),
+/// which is bumped for the variables of every falsified clause. Decisions reuse the last value a
+/// variable had before it was unassigned.
+///
+/// Given a [`crate::proof::ProofWriter`], the [`DpllSolver`] writes the negation of the untried
+/// decisions at every conflict, which is the empty clause once there are none left. Pure
+/// literals are written as the clause of the pure literal and the negated decisions, which
+/// holds up to satisfiability (RAT) rather than following from the clauses (RUP). Only DRAT
+/// proofs are written, as the lemmas do not keep track of their antecedents.
+///
+/// # Pseudocode
+/// ```text
+/// procedure DPLL(Set of Clauses S)
+///
+/// input: A set of clauses S
+/// output: a model of S, or unsatisfiable
+///
+/// begin
+///   I := empty interpretation
+///   loop
+///     while S contains a unit clause {l} under I
+///       I := I ∪ {l}
+///     if some clause in S is false under I
+///       if I contains no untried decision
+///         then return unsatisfiable
+///       undo I up to the most recent untried decision p
+///       I := I ∪ {¬p}
+///     else
+///       forall pure literals l in S under I
+///         I := I ∪ {l}
+///       if every clause in S is true under I
+///         then return I
+///       p := some unassigned variable
+///       I := I ∪ {p} (as a decision)
+/// end
+/// ```
+///
+/// # Arguments
+/// * `formula` - A [`Formula`] struct
+/// * `branching` - The [`Branching`] heuristic used to pick decision variables
+///
+/// # Returns
+/// * [`SolveResult`] - [`SolveResult::Sat`] with the model, or [`SolveResult::Unsat`]
+///
+/// # Examples
+/// ```
+/// use sat_rs::notation::{Literal, Clause, Formula};
+/// use sat_rs::solvers::branching::Branching;
+/// use sat_rs::solvers::dpll;
+///
+/// let mut valid_clause = Clause::new();
+/// let p = Literal{ value: 1, negated: false};
+/// valid_clause.literals.push(p);
+///
+/// let mut invalid_clause = Clause::new();
+/// let q = Literal{ value: 1, negated: true};
+/// invalid_clause.literals.push(q);
+///
+/// let mut formula = Formula::new();
+/// formula.add_clause(valid_clause);
+/// formula.add_clause(invalid_clause);
+///
+/// let result = dpll::dpll_algorithm(&mut formula, Branching::Vsids);
+/// assert_eq!(result.is_unsat(), true);
+/// ```
+pub fn dpll_algorithm(formula: &mut Formula, branching: Branching) -> SolveResult {
+    DpllSolver::new(branching).search(formula)
+}
+
+/// Computes the [`ClauseState`] of a clause under a partial interpretation
+fn clause_state(clause: &[i32], interpretation: &Assignment) -> ClauseState {
+    let mut unassigned: Option<i32> = None;
+    let mut num_unassigned: usize = 0;
+
+    for lit in clause {
+        match interpretation.get(lit.abs()) {
+            Some(value) if value == (*lit > 0) => return ClauseState::Satisfied,
+            Some(_) => (),
+            None => {
+                unassigned = Some(*lit);
+                num_unassigned += 1;
+            }
+        }
+    }
+
+    match num_unassigned {
+        0 => ClauseState::Falsified,
+        1 => ClauseState::Unit(unassigned.unwrap()),
+        _ => ClauseState::Unresolved,
+    }
+}
+
+/// Assigns unit literals until a fixpoint is reached
+///
+/// Returns the index of a clause falsified by the interpretation, if any.
+fn propagate(
+    clauses: &[Vec<i32>],
+    interpretation: &mut Assignment,
+    trail: &mut Vec<TrailEntry>,
+) -> Option<usize> {
+    let mut changed = true;
+
+    while changed {
+        changed = false;
+        for (index, clause) in clauses.iter().enumerate() {
+            match clause_state(clause, interpretation) {
+                ClauseState::Falsified => return Some(index),
+                ClauseState::Unit(lit) => {
+                    interpretation.set(lit.abs(), lit > 0);
+                    trail.push(TrailEntry {
+                        var: lit.abs(),
+                        decision: false,
+                    });
+                    changed = true;
+                }
+                ClauseState::Satisfied | ClauseState::Unresolved => (),
+            }
+        }
+    }
+
+    None
+}
+
+/// Assigns every literal that occurs with a single polarity in the unsatisfied clauses
+fn eliminate_pure_literals(
+    clauses: &[Vec<i32>],
+    interpretation: &mut Assignment,
+    trail: &mut Vec<TrailEntry>,
+) {
+    // Polarities seen per unassigned variable: (positive, negative)
+    let mut polarities: Vec<(bool, bool)> = vec![(false, false); interpretation.len()];
+
+    for clause in clauses {
+        if let ClauseState::Satisfied = clause_state(clause, interpretation) {
+            continue;
+        }
+        for lit in clause {
+            if !interpretation.is_assigned(lit.abs()) {
+                let entry = &mut polarities[lit.unsigned_abs() as usize - 1];
+                if *lit > 0 {
+                    entry.0 = true;
+                } else {
+                    entry.1 = true;
+                }
+            }
+        }
+    }
+
+    for (index, (positive, negative)) in polarities.into_iter().enumerate() {
+        if positive != negative {
+            let var = index as i32 + 1;
+            interpretation.set(var, positive);
+            trail.push(TrailEntry {
+                var,
+                decision: false,
+            });
+        }
+    }
+}
+
+/// Returns the clause of the negations of the untried decisions on the trail
+fn negated_decisions(interpretation: &Assignment, trail: &[TrailEntry]) -> Vec<i32> {
+    trail
+        .iter()
+        .filter(|entry| entry.decision)
+        .map(|entry| {
+            if interpretation.get(entry.var) == Some(true) {
+                -entry.var
+            } else {
+                entry.var
+            }
+        })
+        .collect()
+}
+
+/// Undoes the trail up to the most recent untried decision and flips it
+///
+/// Returns `false` if there is no decision left to flip.
+fn backtrack(
+    interpretation: &mut Assignment,
+    trail: &mut Vec<TrailEntry>,
+    order: &mut dyn VariableOrder,
+    phases: &mut Phases,
+) -> bool {
+    while let Some(entry) = trail.pop() {
+        let value = interpretation.unassign(entry.var).unwrap();
+        let index = entry.var as usize - 1;
+        phases.save(index, value);
+        order.unassigned(index);
+        if entry.decision {
+            interpretation.set(entry.var, !value);
+            trail.push(TrailEntry {
+                var: entry.var,
+                decision: false,
+            });
+            return true;
+        }
+    }
+
+    false
+}
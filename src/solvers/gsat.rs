@@ -1,40 +1,264 @@
-use crate::notation::{Clause, Formula};
-use crate::solvers::utils::flip;
-use rand::prelude::IndexedRandom;
-use rand::RngExt;
-use std::collections::HashMap;
+use crate::notation::Formula;
+use crate::solvers::localsearch::{LocalSearch, LocalSearchAlgorithm, LocalSearchSolver};
+use crate::solvers::result::SolveResult;
+use crate::solvers::solver::SolverStats;
+use rand::{Rng, RngExt};
+
+/// [`Solver`](crate::solvers::solver::Solver) running the [`gsat_algorithm`]
+///
+/// # Examples
+/// ```rust
+/// use sat_rs::notation::{Literal, Clause};
+/// use sat_rs::solvers::gsat::GsatSolver;
+/// use sat_rs::solvers::solver::Solver;
+///
+/// let mut clause = Clause::new(); // Clause: p v q
+/// clause.literals.push(Literal{ value: 1, negated: false});
+/// clause.literals.push(Literal{ value: 2, negated: false});
+///
+/// let mut solver = GsatSolver::new(10, 10, None, 0);
+/// solver.add_clause(clause);
+/// assert!(solver.solve().is_sat());
+/// ```
+pub type GsatSolver = LocalSearchSolver<Gsat>;
+
+/// The search of the [`gsat_algorithm`], run by a [`GsatSolver`]
+#[derive(Debug, Clone)]
+pub struct Gsat {
+    max_tries: u32,
+    max_flips: u32,
+    walk_probability: Option<f32>,
+    tabu_tenure: u64,
+}
+
+impl GsatSolver {
+    /// Creates a new [`GsatSolver`]
+    ///
+    /// # Arguments
+    /// * `max_tries` - The number of times to try to find a satisfying interpretation
+    /// * `max_flips` - The number of times to flip the value of a variable in an interpretation
+    /// * `walk_probability` - The probability of flipping the greedily selected variable instead
+    ///   of a random variable of an unsatisfied clause, `None` to always flip greedily
+    /// * `tabu_tenure` - The number of steps during which a flipped variable may not be flipped
+    ///   again, unless flipping it leads to the fewest unsatisfied clauses of the try, `0` to
+    ///   disable the tabu list
+    pub fn new(
+        max_tries: u32,
+        max_flips: u32,
+        walk_probability: Option<f32>,
+        tabu_tenure: u64,
+    ) -> GsatSolver {
+        LocalSearchSolver::from_algorithm(Gsat {
+            max_tries,
+            max_flips,
+            walk_probability,
+            tabu_tenure,
+        })
+    }
+}
+
+impl LocalSearchAlgorithm for Gsat {
+    fn search(
+        &self,
+        formula: &Formula,
+        rng: &mut impl Rng,
+        stats: &mut SolverStats,
+    ) -> SolveResult {
+        let mut state = LocalSearch::new(formula);
+        if state.has_empty_clause() {
+            return SolveResult::Unsat;
+        }
+        let mut candidates = Vec::new();
+
+        for _ in 0..self.max_tries {
+            stats.tries += 1;
+
+            // Create a random interpretation
+            state.randomize(rng);
+            let mut best_unsat = state.num_unsat();
+
+            for _ in 0..self.max_flips {
+                // Check if the interpretation satisfies the formula
+                if state.is_satisfied() {
+                    break;
+                }
+
+                // Flip the variable with the best score with probability walk_probability, and a
+                // random variable of a random unsatisfied clause with probability
+                // 1 - walk_probability
+                let greedy = match self.walk_probability {
+                    None => true,
+                    Some(walk_probability) => rng.random::<f32>() < walk_probability,
+                };
+                let var = if greedy {
+                    best_variable(&state, best_unsat, self.tabu_tenure, rng, stats)
+                } else {
+                    // The walk leaves out the tabu variables as well, unless flipping them leads
+                    // to a new best. The step counts as blocked if no variable is left
+                    let unsat = state.unsat_clauses();
+                    let clause = state.clause(unsat[rng.random_range(0..unsat.len())]);
+                    candidates.clear();
+                    for lit in clause {
+                        let var = lit.var();
+                        let unsat_after = state.num_unsat() as i64 - state.score(var);
+                        if !state.is_tabu(var, self.tabu_tenure) || unsat_after < best_unsat as i64
+                        {
+                            candidates.push(var);
+                        }
+                    }
+                    if candidates.is_empty() {
+                        stats.tabu_blocked += 1;
+                        None
+                    } else {
+                        Some(candidates[rng.random_range(0..candidates.len())])
+                    }
+                };
+
+                // Every variable may be tabu, then nothing is flipped
+                match var {
+                    Some(var) => {
+                        state.flip(var);
+                        stats.flips += 1;
+                        best_unsat = best_unsat.min(state.num_unsat());
+                    }
+                    None => state.skip(),
+                }
+            }
+
+            if state.is_satisfied() {
+                return SolveResult::Sat(state.model(&formula.vars));
+            }
+        }
+
+        SolveResult::Unknown(format!(
+            "no model found in {} tries of {} flips",
+            self.max_tries, self.max_flips
+        ))
+    }
+}
+
+/// Returns a variable with the highest score (make minus break), ties are broken uniformly at
+/// random
+///
+/// Tabu variables are skipped, unless flipping them leaves fewer than `best_unsat` unsatisfied
+/// clauses. The step counts as blocked in `stats` if a skipped variable has a higher score than
+/// the variable returned, or if none is returned.
+///
+/// The variables are visited by decreasing score (see [`LocalSearch::vars_with_score`]), so
+/// only the best ones and the tabu ones with a higher score are looked at.
+///
+/// # Arguments
+/// * `state` - The [`LocalSearch`] state
+/// * `best_unsat` - The fewest unsatisfied clauses of the try
+/// * `tabu_tenure` - The number of steps during which a flipped variable is tabu
+/// * `rng` - The random number generator breaking the ties
+/// * `stats` - The counters of the solver, to count the blocked steps in
+///
+/// # Returns
+/// * `Option<usize>` - The variable to flip, `None` if every variable is tabu
+pub fn best_variable(
+    state: &LocalSearch,
+    best_unsat: usize,
+    tabu_tenure: u64,
+    rng: &mut impl Rng,
+    stats: &mut SolverStats,
+) -> Option<usize> {
+    // A single variable is flipped per step, so at most this many variables are tabu
+    let max_tabu = tabu_tenure.min(state.step()) as usize;
+    let mut left = state.vars().len();
+    let mut score = state.max_score()?;
+    let mut blocked = false;
+    while left > 0 {
+        let vars = state.vars_with_score(score);
+        left -= vars.len();
+        let unsat_after = state.num_unsat() as i64 - score;
+
+        let var = if vars.is_empty() {
+            None
+        } else if unsat_after < best_unsat as i64 || vars.len() > 2 * max_tabu {
+            // At least half of the variables are allowed, draw until one of them is
+            loop {
+                let var = vars[rng.random_range(0..vars.len())];
+                if unsat_after < best_unsat as i64 || !state.is_tabu(var, tabu_tenure) {
+                    break Some(var);
+                }
+            }
+        } else {
+            // Reservoir sampling keeps each of the allowed variables with the same probability
+            let mut chosen = None;
+            let mut allowed = 0;
+            for &var in vars {
+                if !state.is_tabu(var, tabu_tenure) {
+                    allowed += 1;
+                    if rng.random_range(0..allowed) == 0 {
+                        chosen = Some(var);
+                    }
+                }
+            }
+            blocked |= chosen.is_none();
+            chosen
+        };
+
+        if var.is_some() {
+            if blocked {
+                stats.tabu_blocked += 1;
+            }
+            return var;
+        }
+        score -= 1;
+    }
+    stats.tabu_blocked += 1;
+    None
+}
 
 /// GSAT Algorithm for evaluation of propostional formulas
 ///
 /// This algorithm generates random interpretations and checks if the formula is satisfied by the
-/// interpretation. If the formula is satisfied, the algorithm returns true. If the formula is not
-/// statisfied, the algorithm selects a variable which upon flipping satisfies the maximum number
-/// of clauses. The algorithm then flips the value of the variable in the interpretation. The
-/// algorithm then checks if the formula is satisfied by the new interpretation. If the formula is
-/// satisfied, the algorithm returns true. If the formula is not satisfied, the algorithm repeats
-/// the process until the formula is satisfied or the maximum number of flips is reached.
+/// interpretation. If the formula is satisfied, the algorithm returns the interpretation as a
+/// model. If the formula is not statisfied, the algorithm selects a variable which upon flipping
+/// satisfies the maximum number of clauses, i.e. with the highest score (make minus break), and
+/// breaks ties at random. The algorithm then flips the value of the variable in the
+/// interpretation. The algorithm then checks if the formula is satisfied by the new
+/// interpretation. If the formula is satisfied, the algorithm returns the model. If the formula is
+/// not satisfied, the algorithm repeats the process until the formula is satisfied or the maximum
+/// number of flips is reached. If all tries are exhausted, the result is unknown.
+///
+/// With a `walk_probability`, the greedy flip is only made with that probability, and a random
+/// variable of a random unsatisfied clause is flipped otherwise.
+///
+/// With a `tabu_tenure`, a flipped variable is tabu for that many steps: greedy flips and walks
+/// skip it, unless flipping it would leave fewer unsatisfied clauses than ever before in the try.
+/// This keeps GSAT from flipping the same variable back and forth. When every variable is tabu,
+/// nothing is flipped during the step.
+///
+/// The score of every variable is maintained incrementally, and the variables are grouped by
+/// score (see [`crate::solvers::localsearch`]), so a flip only visits the clauses containing the
+/// flipped variable and a greedy step only looks at the variables with the highest score. A
+/// formula containing the empty clause is reported as unsatisfiable.
 ///
 /// # Pseudocode
 /// ```text
 /// procedure GSAT(Set of Clauses S)
 ///
 /// input: A set of clauses S
-/// output: true if S is satisfiable, false otherwise
+/// output: a model of S, or unknown
 /// parameters: max_tries, max_flips
 ///
 /// begin
 ///   repeat max_tries times
 ///     create a random interpretation I
 ///     if I satisfies S
-///       then return true
+///       then return I
 ///     else
 ///       repeat max_flips times
-///         p := variable which upon flipping satisfies the maximum number of clauses
+///         p := variable which is not tabu and upon flipping satisfies the maximum number of
+///              clauses, chosen at random among ties
 ///         I = flip(I, p)
 ///         if I satisfies S
-///           then return true
+///           then return I
 ///         else
 ///           continue
+///   return unknown
 /// end
 /// ```
 ///
@@ -42,9 +266,14 @@ use std::collections::HashMap;
 /// * `formula` - A [`Formula`] struct
 /// * `max_tries` - The number of times to try to find a satisfying interpretation
 /// * `max_flips` - The number of times to flip the value of a variable in an interpretation
+/// * `walk_probability` - The probability of a greedy flip, `None` to always flip greedily
+/// * `tabu_tenure` - The number of steps during which a flipped variable is tabu, `0` to disable
+///   the tabu list
+/// * `seed` - The seed of the random number generator, `None` to seed from the operating
+///   system
 ///
 /// # Returns
-/// * `bool` - The value of the formula
+/// * [`SolveResult`] - [`SolveResult::Sat`] with the model, or [`SolveResult::Unknown`]
 ///
 /// # Examples
 /// ```rust
@@ -57,107 +286,21 @@ use std::collections::HashMap;
 /// clause.literals.push(p);
 /// clause.literals.push(q);
 ///
-/// let mut formula = Formula {
-///    clauses: vec![clause],
-///    literals: vec![Literal{ value: 1, negated: false}, Literal{ value: 2, negated: false}],
-///    vars: vec![1, 2],
-///    num_clauses: 1,
-///    num_vars: 2,
-/// };
+/// let mut formula = Formula::new();
+/// formula.add_clause(clause);
 ///
-/// let result = gsat::gsat_algorithm(&mut formula, 10, 10, None);
-/// assert_eq!(result, true);
+/// let result = gsat::gsat_algorithm(&mut formula, 10, 10, None, 0, Some(1));
+/// assert_eq!(result.is_sat(), true);
 /// ```
 pub fn gsat_algorithm(
     formula: &mut Formula,
     max_tries: u32,
     max_flips: u32,
     walk_probability: Option<f32>,
-) -> bool {
-    let mut value: bool = false;
-
-    for _ in 0..max_tries {
-        // Create a random interpretation
-        let mut interpretation: HashMap<i32, bool> = HashMap::new();
-
-        for var in &formula.vars {
-            interpretation.insert(*var, rand::random::<bool>());
-        }
-
-        // Check if the interpretation satisfies the formula
-        if formula.evaluate(&interpretation) {
-            value = true;
-            println!(
-                "Formula is satisfied by the interpretation: {:?}",
-                interpretation
-            );
-            return value;
-        } else {
-            for _ in 0..max_flips {
-                // Collect unsatisfied clauses
-                let unsatisfied_clauses: Vec<Clause> = formula.get_unsatisfied_clauses();
-
-                // Loop over all the unsatisfied clauses, and find the variable
-                // which upon flipping satisfies the maximum number of clauses
-                let mut var_to_flip: i32 = 0;
-                for clause in &unsatisfied_clauses {
-                    let mut max_satisfied_clauses: u32 = 0;
-                    for literal in &clause.literals {
-                        let mut interpretation_copy = interpretation.clone();
-                        interpretation_copy = flip(&mut interpretation_copy, literal.value).clone();
-
-                        let mut num_satisfied_clauses: u32 = 0;
-                        // Clone the formual per iteration
-                        let mut temp_formula = formula.clone();
-
-                        for clause in &mut temp_formula.clauses {
-                            clause.is_satisfied = clause.evaluate(&interpretation_copy);
-                            if clause.is_satisfied {
-                                num_satisfied_clauses += 1;
-                            }
-                        }
-
-                        if num_satisfied_clauses > max_satisfied_clauses {
-                            max_satisfied_clauses = num_satisfied_clauses;
-                            var_to_flip = literal.value;
-                        }
-                    }
-                }
-
-                // Flip the value of the variable
-                if walk_probability == None {
-                    interpretation = flip(&mut interpretation, var_to_flip).clone();
-                } else {
-                    // flip the value of the variable with a probability walk_probability
-                    // and flip a random variable with probability 1 - walk_probabilityN
-                    let random_number: f32 = rand::random::<f32>();
-                    if random_number < walk_probability.unwrap() {
-                        interpretation = flip(&mut interpretation, var_to_flip).clone();
-                    } else {
-                        // Randomly select a clause that is not satisfied by the interpretation
-                        let clause = unsatisfied_clauses.choose(&mut rand::rng());
-                        let clausal_variables: Vec<i32> =
-                            formula.get_clausal_variables(&clause.unwrap());
-                        let random_var =
-                            clausal_variables[rand::rng().random_range(0..clausal_variables.len())];
-                        interpretation = flip(&mut interpretation, random_var).clone();
-                    }
-                }
-
-                // Check if the interpretation satisfies the formula
-                if formula.evaluate(&interpretation) {
-                    value = true;
-                    println!(
-                        "Formula is satisfied by the interpretation: {:?}",
-                        interpretation
-                    );
-                    return value;
-                } else {
-                    continue;
-                }
-            }
-        }
-    }
-
-    return value;
+    tabu_tenure: u64,
+    seed: Option<u64>,
+) -> SolveResult {
+    GsatSolver::new(max_tries, max_flips, walk_probability, tabu_tenure)
+        .with_seed(seed)
+        .search(formula)
 }
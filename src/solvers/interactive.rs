@@ -1,5 +1,47 @@
-use crate::notation::Formula;
-use std::collections::HashMap;
+use crate::assignment::Assignment;
+use crate::notation::{Clause, Formula, Literal};
+use crate::solvers::result::{Model, SolveResult};
+use crate::solvers::solver::{with_assumptions, Solver, SolverStats};
+
+/// [`Solver`] running the [`interactive_algorithm`]
+///
+/// Assumptions are added to the formula as unit clauses, so the user is still asked for the
+/// value of the assumed variables.
+#[derive(Debug, Clone, Default)]
+pub struct InteractiveSolver {
+    formula: Formula,
+    model: Option<Model>,
+    stats: SolverStats,
+}
+
+impl InteractiveSolver {
+    /// Creates a new [`InteractiveSolver`]
+    pub fn new() -> InteractiveSolver {
+        InteractiveSolver::default()
+    }
+}
+
+impl Solver for InteractiveSolver {
+    fn add_clause(&mut self, clause: Clause) {
+        self.formula.add_clause(clause);
+    }
+
+    fn solve_with_assumptions(&mut self, assumptions: &[Literal]) -> SolveResult {
+        let mut formula = with_assumptions(&self.formula, assumptions);
+        self.stats.tries += 1;
+        let result = interactive_algorithm(&mut formula);
+        self.model = result.model().cloned();
+        result
+    }
+
+    fn model(&self) -> Option<&Model> {
+        self.model.as_ref()
+    }
+
+    fn stats(&self) -> &SolverStats {
+        &self.stats
+    }
+}
 
 /// Purely Syntactic Algorithm for evaluation of propostional formulas
 ///
@@ -27,21 +69,25 @@ use std::collections::HashMap;
 /// * `formula` - A [`Formula`] struct
 ///
 /// # Returns
-/// * `bool` - The value of the formula
-pub fn interactive_algorithm(formula: &mut Formula) -> bool {
-    let mut interpretation: HashMap<i32, bool> = HashMap::new();
+/// * [`SolveResult`] - [`SolveResult::Sat`] with the interpretation if it satisfies the formula,
+///   otherwise [`SolveResult::Unknown`]
+pub fn interactive_algorithm(formula: &mut Formula) -> SolveResult {
+    let mut interpretation =
+        Assignment::new(formula.vars.iter().copied().max().unwrap_or(0) as usize);
 
     for var in &formula.vars {
-        // Ask for an interpretation
-        println!("Enter value for variable {:?} in the interpretation: ", var);
+        // Ask for an interpretation on stderr, so that stdout only holds the result
+        eprintln!("Enter value for variable {:?} in the interpretation: ", var);
         let mut input = String::new();
         std::io::stdin().read_line(&mut input).unwrap();
         let input: bool = input.trim().parse().unwrap();
-        interpretation.insert(*var, input);
+        interpretation.set(*var, input);
     }
 
     // Evaluate formula based on the interpretation
-    let value = formula.evaluate(&interpretation);
-
-    return value;
+    if formula.evaluate(&interpretation) == Some(true) {
+        SolveResult::Sat(Model::from(interpretation))
+    } else {
+        SolveResult::Unknown("the interpretation does not satisfy the formula".to_string())
+    }
 }
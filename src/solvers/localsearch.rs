@@ -0,0 +1,543 @@
+//! # Local Search State
+//!
+//! The data structures shared by the local search solvers, which flip one variable at a time
+//! starting from a random interpretation. Everything a local search heuristic looks at is kept
+//! up to date incrementally, so that a flip only visits the clauses containing the flipped
+//! variable instead of the whole formula:
+//!
+//! * the occurrence list of every literal,
+//! * the number of true literals of every clause,
+//! * the set of unsatisfied clauses,
+//! * the make count of every variable, i.e. the number of unsatisfied clauses that flipping it
+//!   would satisfy,
+//! * the break count of every variable, i.e. the number of satisfied clauses that flipping it
+//!   would falsify,
+//! * the step at which every variable was last flipped, i.e. its age,
+//! * the variables grouped by score, so that the best ones are found without visiting every
+//!   variable.
+//!
+//! Every randomized solver is a [`LocalSearchSolver`] running its [`LocalSearchAlgorithm`], which
+//! holds the clauses, the seed, the model and the statistics, so that an algorithm only
+//! implements the search itself.
+use crate::assignment::Assignment;
+use crate::notation::{Clause, ClauseArena, ClauseRef, Formula, Lit, Literal};
+use crate::solvers::result::{Model, SolveResult};
+use crate::solvers::solver::{seeded_rng, with_assumptions, Solver, SolverStats};
+use rand::{Rng, RngExt};
+
+/// Marks a clause which is not in the unsatisfied set
+const SATISFIED: usize = usize::MAX;
+
+/// Variables grouped by score, which lies between minus and plus the number of clauses containing
+/// the variable
+#[derive(Debug, Clone)]
+struct ScoreBuckets {
+    /// Variables of every score, bucket `i` holding the score `i - offset`
+    buckets: Vec<Vec<usize>>,
+    /// Bucket of every variable
+    bucket: Vec<usize>,
+    /// Position of every variable in its bucket
+    pos: Vec<usize>,
+    offset: usize,
+    /// Highest non-empty bucket, `0` if every bucket is empty
+    top: usize,
+}
+
+impl ScoreBuckets {
+    fn new(num_vars: usize, max_occurrences: usize) -> ScoreBuckets {
+        ScoreBuckets {
+            buckets: vec![Vec::new(); 2 * max_occurrences + 1],
+            bucket: vec![0; num_vars],
+            pos: vec![0; num_vars],
+            offset: max_occurrences,
+            top: 0,
+        }
+    }
+
+    fn clear(&mut self) {
+        for bucket in &mut self.buckets {
+            bucket.clear();
+        }
+        self.top = 0;
+    }
+
+    fn insert(&mut self, var: usize, score: i64) {
+        let bucket = (score + self.offset as i64) as usize;
+        self.bucket[var] = bucket;
+        self.pos[var] = self.buckets[bucket].len();
+        self.buckets[bucket].push(var);
+        self.top = self.top.max(bucket);
+    }
+
+    /// Moves a variable whose score changed by `delta`
+    fn shift(&mut self, var: usize, delta: i64) {
+        let bucket = self.bucket[var];
+        let pos = self.pos[var];
+        self.buckets[bucket].swap_remove(pos);
+        if let Some(&moved) = self.buckets[bucket].get(pos) {
+            self.pos[moved] = pos;
+        }
+        self.insert(var, self.score(bucket) + delta);
+        while self.top > 0 && self.buckets[self.top].is_empty() {
+            self.top -= 1;
+        }
+    }
+
+    fn score(&self, bucket: usize) -> i64 {
+        bucket as i64 - self.offset as i64
+    }
+
+    fn get(&self, score: i64) -> &[usize] {
+        usize::try_from(score + self.offset as i64)
+            .ok()
+            .and_then(|bucket| self.buckets.get(bucket))
+            .map_or(&[], Vec::as_slice)
+    }
+
+    fn max_score(&self) -> Option<i64> {
+        (!self.buckets[self.top].is_empty()).then(|| self.score(self.top))
+    }
+}
+
+/// Interpretation of a formula with incrementally maintained clause and variable scores
+///
+/// Variables are zero-based indices, the DIMACS variable `v` has index `v - 1`, and clauses are
+/// numbered in the order of the formula. Duplicate literals are removed and tautologies are left
+/// out, as they are satisfied by every interpretation.
+///
+/// # Examples
+/// ```rust
+/// use sat_rs::notation::{Literal, Clause, Formula};
+/// use sat_rs::solvers::localsearch::LocalSearch;
+///
+/// let mut formula = Formula::new();
+/// let mut clause = Clause::new(); // Clause: p v q
+/// clause.literals.push(Literal{ value: 1, negated: false});
+/// clause.literals.push(Literal{ value: 2, negated: false});
+/// formula.add_clause(clause);
+///
+/// let mut clause = Clause::new(); // Clause: -p
+/// clause.literals.push(Literal{ value: 1, negated: true});
+/// formula.add_clause(clause);
+///
+/// // Starts with every variable false
+/// let mut state = LocalSearch::new(&formula);
+/// assert_eq!(state.unsat_clauses(), &[0]);
+/// assert_eq!(state.make(1), 1);
+/// assert_eq!(state.breaks(0), 1);
+///
+/// state.flip(1);
+/// assert!(state.is_satisfied());
+/// assert_eq!(state.breaks(1), 1);
+/// ```
+#[derive(Debug, Clone)]
+pub struct LocalSearch {
+    arena: ClauseArena,
+    /// Reference of every clause in the arena, by clause index
+    clauses: Vec<ClauseRef>,
+    /// Clauses containing a literal, indexed by [`Lit::index`]
+    occurrences: Vec<Vec<usize>>,
+    /// Variables occurring in a clause, in increasing order
+    vars: Vec<usize>,
+    values: Vec<bool>,
+    true_count: Vec<u32>,
+    /// XOR of the variables of the true literals of every clause, which is the only true
+    /// variable when the clause has a single true literal
+    true_vars: Vec<usize>,
+    make: Vec<u32>,
+    breaks: Vec<u32>,
+    /// The variables of `vars`, grouped by score
+    buckets: ScoreBuckets,
+    unsat: Vec<usize>,
+    /// Position of every clause in `unsat`, or [`SATISFIED`]
+    unsat_pos: Vec<usize>,
+    /// Number of steps since the last random interpretation
+    step: u64,
+    /// Step of the last flip of every variable, `0` if it was not flipped since
+    last_flip: Vec<u64>,
+    has_empty_clause: bool,
+}
+
+impl LocalSearch {
+    /// Creates the state of a formula with every variable false
+    pub fn new(formula: &Formula) -> LocalSearch {
+        let num_vars = formula
+            .clauses
+            .iter()
+            .flat_map(|clause| clause.literals.iter().map(|literal| literal.value))
+            .chain(formula.vars.iter().copied())
+            .max()
+            .unwrap_or(0) as usize;
+
+        let mut arena = ClauseArena::new();
+        let mut clauses = Vec::new();
+        let mut occurrences = vec![Vec::new(); 2 * num_vars];
+        let mut has_empty_clause = false;
+
+        for clause in &formula.clauses {
+            let mut lits: Vec<Lit> = clause.literals.iter().map(Lit::from).collect();
+            lits.sort_unstable();
+            lits.dedup();
+            if lits.windows(2).any(|pair| pair[0] == !pair[1]) {
+                continue;
+            }
+            has_empty_clause |= lits.is_empty();
+
+            for lit in &lits {
+                occurrences[lit.index()].push(clauses.len());
+            }
+            clauses.push(arena.alloc(&lits, false));
+        }
+
+        let occurrences_of = |var| {
+            occurrences[Lit::new(var, false).index()].len()
+                + occurrences[Lit::new(var, true).index()].len()
+        };
+        let vars = (0..num_vars)
+            .filter(|&var| occurrences_of(var) > 0)
+            .collect();
+        let max_occurrences = (0..num_vars).map(occurrences_of).max().unwrap_or(0);
+
+        let mut state = LocalSearch {
+            arena,
+            true_count: vec![0; clauses.len()],
+            true_vars: vec![0; clauses.len()],
+            unsat_pos: vec![SATISFIED; clauses.len()],
+            clauses,
+            occurrences,
+            vars,
+            values: vec![false; num_vars],
+            make: vec![0; num_vars],
+            breaks: vec![0; num_vars],
+            buckets: ScoreBuckets::new(num_vars, max_occurrences),
+            unsat: Vec::new(),
+            step: 0,
+            last_flip: vec![0; num_vars],
+            has_empty_clause,
+        };
+        state.recompute();
+        state
+    }
+
+    /// Returns the number of variables
+    pub fn num_vars(&self) -> usize {
+        self.values.len()
+    }
+
+    /// Returns the variables occurring in a clause, tautologies excluded, which are the only ones
+    /// worth flipping
+    pub fn vars(&self) -> &[usize] {
+        &self.vars
+    }
+
+    /// Returns the number of clauses, tautologies excluded
+    pub fn num_clauses(&self) -> usize {
+        self.clauses.len()
+    }
+
+    /// Returns `true` if the formula contains an empty clause, and is thus unsatisfiable
+    pub fn has_empty_clause(&self) -> bool {
+        self.has_empty_clause
+    }
+
+    /// Returns the literals of a clause
+    pub fn clause(&self, clause: usize) -> &[Lit] {
+        self.arena.lits(self.clauses[clause])
+    }
+
+    /// Returns the clauses containing a literal
+    pub fn occurrences(&self, lit: Lit) -> &[usize] {
+        &self.occurrences[lit.index()]
+    }
+
+    /// Returns the value of a variable
+    pub fn value(&self, var: usize) -> bool {
+        self.values[var]
+    }
+
+    /// Returns `true` if the literal is true
+    pub fn is_true(&self, lit: Lit) -> bool {
+        self.values[lit.var()] != lit.is_negated()
+    }
+
+    /// Returns the number of true literals of a clause
+    pub fn true_count(&self, clause: usize) -> u32 {
+        self.true_count[clause]
+    }
+
+    /// Returns the number of unsatisfied clauses that flipping a variable would satisfy
+    pub fn make(&self, var: usize) -> u32 {
+        self.make[var]
+    }
+
+    /// Returns the variable of the only true literal of a clause, `None` if the clause does not
+    /// have exactly one true literal
+    pub fn critical_var(&self, clause: usize) -> Option<usize> {
+        (self.true_count[clause] == 1).then_some(self.true_vars[clause])
+    }
+
+    /// Returns the number of satisfied clauses that flipping a variable would falsify
+    pub fn breaks(&self, var: usize) -> u32 {
+        self.breaks[var]
+    }
+
+    /// Returns the decrease in the number of unsatisfied clauses if a variable is flipped
+    pub fn score(&self, var: usize) -> i64 {
+        self.make[var] as i64 - self.breaks[var] as i64
+    }
+
+    /// Returns the highest score of a variable of [`LocalSearch::vars`], `None` if there is none
+    pub fn max_score(&self) -> Option<i64> {
+        self.buckets.max_score()
+    }
+
+    /// Returns the variables of [`LocalSearch::vars`] with a given score, in no particular order
+    pub fn vars_with_score(&self, score: i64) -> &[usize] {
+        self.buckets.get(score)
+    }
+
+    /// Returns the unsatisfied clauses, in no particular order
+    pub fn unsat_clauses(&self) -> &[usize] {
+        &self.unsat
+    }
+
+    /// Returns the number of unsatisfied clauses
+    pub fn num_unsat(&self) -> usize {
+        self.unsat.len()
+    }
+
+    /// Returns `true` if every clause is satisfied
+    pub fn is_satisfied(&self) -> bool {
+        self.unsat.is_empty()
+    }
+
+    /// Returns the number of steps since the last random interpretation, i.e. the number of
+    /// flips and of [`LocalSearch::skip`]ped steps
+    pub fn step(&self) -> u64 {
+        self.step
+    }
+
+    /// Counts a step in which no variable is flipped, so that variables still age
+    pub fn skip(&mut self) {
+        self.step += 1;
+    }
+
+    /// Returns the step at which a variable was last flipped, `0` if it was not flipped since the
+    /// last random interpretation
+    pub fn last_flip(&self, var: usize) -> u64 {
+        self.last_flip[var]
+    }
+
+    /// Returns `true` if a variable was flipped during the last `tenure` steps
+    pub fn is_tabu(&self, var: usize, tenure: u64) -> bool {
+        self.last_flip[var] != 0 && self.step - self.last_flip[var] < tenure
+    }
+
+    /// Assigns a random value to every variable
+    pub fn randomize(&mut self, rng: &mut impl Rng) {
+        for value in &mut self.values {
+            *value = rng.random_bool(0.5);
+        }
+        self.step = 0;
+        self.last_flip.fill(0);
+        self.recompute();
+    }
+
+    /// Returns the current interpretation as a model of the given DIMACS variables
+    pub fn model(&self, vars: &[i32]) -> Model {
+        let mut assignment = Assignment::new(self.num_vars());
+        for var in vars {
+            assignment.set(*var, self.values[*var as usize - 1]);
+        }
+        Model::from(assignment)
+    }
+
+    /// Computes the clause and variable scores of the current interpretation from scratch
+    fn recompute(&mut self) {
+        self.make.fill(0);
+        self.breaks.fill(0);
+        self.unsat.clear();
+
+        for clause in 0..self.clauses.len() {
+            let mut count = 0;
+            let mut vars = 0;
+            for lit in self.arena.lits(self.clauses[clause]) {
+                if self.values[lit.var()] != lit.is_negated() {
+                    count += 1;
+                    vars ^= lit.var();
+                }
+            }
+            self.true_count[clause] = count;
+            self.true_vars[clause] = vars;
+            self.unsat_pos[clause] = SATISFIED;
+
+            match count {
+                0 => {
+                    self.add_unsat(clause);
+                    for lit in self.arena.lits(self.clauses[clause]) {
+                        self.make[lit.var()] += 1;
+                    }
+                }
+                1 => self.breaks[vars] += 1,
+                _ => (),
+            }
+        }
+
+        self.buckets.clear();
+        for &var in &self.vars {
+            let score = self.make[var] as i64 - self.breaks[var] as i64;
+            self.buckets.insert(var, score);
+        }
+    }
+
+    /// Flips the value of a variable, updating the scores of the affected clauses and variables
+    pub fn flip(&mut self, var: usize) {
+        // The literal of the variable which becomes true
+        let made_true = Lit::new(var, self.values[var]);
+        self.values[var] = !self.values[var];
+        self.step += 1;
+        self.last_flip[var] = self.step;
+
+        for k in 0..self.occurrences[made_true.index()].len() {
+            let clause = self.occurrences[made_true.index()][k];
+            let count = self.true_count[clause];
+            let sole = self.true_vars[clause];
+            self.true_count[clause] += 1;
+            self.true_vars[clause] ^= var;
+
+            match count {
+                0 => {
+                    self.remove_unsat(clause);
+                    for lit in self.arena.lits(self.clauses[clause]) {
+                        self.make[lit.var()] -= 1;
+                        self.buckets.shift(lit.var(), -1);
+                    }
+                    self.breaks[var] += 1;
+                    self.buckets.shift(var, -1);
+                }
+                1 => {
+                    self.breaks[sole] -= 1;
+                    self.buckets.shift(sole, 1);
+                }
+                _ => (),
+            }
+        }
+
+        let made_false = !made_true;
+        for k in 0..self.occurrences[made_false.index()].len() {
+            let clause = self.occurrences[made_false.index()][k];
+            self.true_count[clause] -= 1;
+            self.true_vars[clause] ^= var;
+
+            match self.true_count[clause] {
+                0 => {
+                    self.add_unsat(clause);
+                    for lit in self.arena.lits(self.clauses[clause]) {
+                        self.make[lit.var()] += 1;
+                        self.buckets.shift(lit.var(), 1);
+                    }
+                    self.breaks[var] -= 1;
+                    self.buckets.shift(var, 1);
+                }
+                1 => {
+                    self.breaks[self.true_vars[clause]] += 1;
+                    self.buckets.shift(self.true_vars[clause], -1);
+                }
+                _ => (),
+            }
+        }
+    }
+
+    fn add_unsat(&mut self, clause: usize) {
+        self.unsat_pos[clause] = self.unsat.len();
+        self.unsat.push(clause);
+    }
+
+    fn remove_unsat(&mut self, clause: usize) {
+        let pos = self.unsat_pos[clause];
+        let last = *self.unsat.last().unwrap();
+        self.unsat.swap_remove(pos);
+        if last != clause {
+            self.unsat_pos[last] = pos;
+        }
+        self.unsat_pos[clause] = SATISFIED;
+    }
+}
+
+/// The search of a randomized solver, run by a [`LocalSearchSolver`] on the clauses added to it
+pub trait LocalSearchAlgorithm {
+    /// Searches for a model of a formula
+    ///
+    /// # Arguments
+    /// * `formula` - The clauses added to the solver, with the assumptions as unit clauses
+    /// * `rng` - The random number generator, seeded by [`LocalSearchSolver::with_seed`]
+    /// * `stats` - The counters of the solver, to count the tries and flips in
+    ///
+    /// # Returns
+    /// * [`SolveResult`] - [`SolveResult::Sat`] with the model, [`SolveResult::Unsat`] if the
+    ///   formula has an empty clause, or [`SolveResult::Unknown`]
+    fn search(&self, formula: &Formula, rng: &mut impl Rng, stats: &mut SolverStats)
+        -> SolveResult;
+}
+
+/// [`Solver`] running a [`LocalSearchAlgorithm`], e.g. a
+/// [`GsatSolver`](crate::solvers::gsat::GsatSolver)
+///
+/// Assumptions are added to the clauses as unit clauses for the call.
+#[derive(Debug, Clone)]
+pub struct LocalSearchSolver<A> {
+    formula: Formula,
+    algorithm: A,
+    seed: Option<u64>,
+    model: Option<Model>,
+    stats: SolverStats,
+}
+
+impl<A: LocalSearchAlgorithm> LocalSearchSolver<A> {
+    /// Creates a new [`LocalSearchSolver`] running `algorithm`, without clauses
+    pub fn from_algorithm(algorithm: A) -> LocalSearchSolver<A> {
+        LocalSearchSolver {
+            formula: Formula::new(),
+            algorithm,
+            seed: None,
+            model: None,
+            stats: SolverStats::default(),
+        }
+    }
+
+    /// Seeds the random number generator, so that every solve makes the same random choices
+    ///
+    /// # Arguments
+    /// * `seed` - The seed, `None` to seed from the operating system
+    pub fn with_seed(mut self, seed: Option<u64>) -> LocalSearchSolver<A> {
+        self.seed = seed;
+        self
+    }
+
+    /// Runs the algorithm on a formula, with a new random number generator from the seed
+    pub(crate) fn search(&mut self, formula: &Formula) -> SolveResult {
+        let mut rng = seeded_rng(self.seed);
+        self.algorithm.search(formula, &mut rng, &mut self.stats)
+    }
+}
+
+impl<A: LocalSearchAlgorithm> Solver for LocalSearchSolver<A> {
+    fn add_clause(&mut self, clause: Clause) {
+        self.formula.add_clause(clause);
+    }
+
+    fn solve_with_assumptions(&mut self, assumptions: &[Literal]) -> SolveResult {
+        let formula = with_assumptions(&self.formula, assumptions);
+        let result = self.search(&formula);
+        self.model = result.model().cloned();
+        result
+    }
+
+    fn model(&self) -> Option<&Model> {
+        self.model.as_ref()
+    }
+
+    fn stats(&self) -> &SolverStats {
+        &self.stats
+    }
+}
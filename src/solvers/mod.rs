@@ -8,8 +8,43 @@
 //! * [`chaos`] - A solver based on the CHAOS algorithm.
 //! * [`wsat`] - A solver based on the WSAT algorithm.
 //! * [`gsat`] - A solver based on the GSAT algorithm.
+//! * [`probsat`] - A solver based on the probSAT algorithm.
+//! * [`novelty`] - Solvers based on the Novelty, Novelty+ and AdaptNovelty+ algorithms.
+//! * [`weighting`] - Clause weighting solvers based on the DDFW and SAPS algorithms.
+//! * [`ccanr`] - A configuration checking solver based on the CCAnr algorithm.
+//! * [`annealing`] - A solver based on simulated annealing.
+//! * [`dpll`] - A complete solver based on the DPLL algorithm.
+//! * [`cdcl`] - A complete solver based on conflict-driven clause learning.
+//!
+//! Every solver also implements the [`solver::Solver`] trait, and [`registry`] builds any of
+//! them by name.
+//!
+//! ## Shared components
+//!
+//! * [`solver`] - The [`solver::Solver`] trait and solver statistics.
+//! * [`registry`] - Builds a solver from its name and configuration.
+//! * [`result`] - The [`result::SolveResult`] returned by every solver.
+//! * [`branching`] - Variable orders and phase saving for the complete solvers.
+//! * [`restart`] - Restart policies for the conflict-driven solvers.
+//! * [`clausedb`] - Learnt clause scoring and reduction for the conflict-driven solvers.
+//! * [`localsearch`] - Incrementally maintained scores and the solver wrapper of the local search
+//!   solvers.
+pub mod annealing;
+pub mod branching;
+pub mod ccanr;
+pub mod cdcl;
 pub mod chaos;
+pub mod clausedb;
+pub mod dpll;
 pub mod gsat;
 pub mod interactive;
+pub mod localsearch;
+pub mod novelty;
+pub mod probsat;
+pub mod registry;
+pub mod restart;
+pub mod result;
+pub mod solver;
 pub mod utils;
+pub mod weighting;
 pub mod wsat;
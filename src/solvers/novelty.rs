@@ -0,0 +1,415 @@
+use crate::notation::{Formula, Lit};
+use crate::solvers::localsearch::{LocalSearch, LocalSearchAlgorithm, LocalSearchSolver};
+use crate::solvers::result::SolveResult;
+use crate::solvers::solver::SolverStats;
+use rand::{Rng, RngExt};
+
+/// Fraction of the number of clauses without improvement after which adaptive noise is raised
+const ADAPT_THETA: f64 = 1.0 / 6.0;
+
+/// Relative amount by which adaptive noise is raised, it is lowered by half of it
+const ADAPT_PHI: f64 = 0.2;
+
+/// Parameters of the [`novelty_algorithm`]
+///
+/// # Examples
+/// ```rust
+/// use sat_rs::solvers::novelty::NoveltyConfig;
+///
+/// let config = NoveltyConfig::novelty_plus(0.5);
+/// assert_eq!(config.walk_probability, 0.01);
+///
+/// let config = NoveltyConfig::adaptive();
+/// assert_eq!(config.noise, None);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct NoveltyConfig {
+    /// Probability of flipping the second best variable when the best one is the most recently
+    /// flipped variable of the clause, `None` to adapt it during the search
+    pub noise: Option<f64>,
+    /// Probability of flipping a random variable of the clause instead of applying the Novelty
+    /// rule, `0` for plain Novelty
+    pub walk_probability: f64,
+}
+
+impl NoveltyConfig {
+    /// Novelty with a fixed noise
+    pub fn novelty(noise: f64) -> NoveltyConfig {
+        NoveltyConfig {
+            noise: Some(noise),
+            walk_probability: 0.0,
+        }
+    }
+
+    /// Novelty+ with a fixed noise and a random walk probability of `0.01`
+    pub fn novelty_plus(noise: f64) -> NoveltyConfig {
+        NoveltyConfig {
+            noise: Some(noise),
+            walk_probability: 0.01,
+        }
+    }
+
+    /// Novelty+ with adaptive noise (AdaptNovelty+)
+    pub fn adaptive() -> NoveltyConfig {
+        NoveltyConfig {
+            noise: None,
+            walk_probability: 0.01,
+        }
+    }
+}
+
+impl NoveltyConfig {
+    /// Checks that the noise and the random walk probability are probabilities
+    ///
+    /// # Returns
+    /// * `Result<(), String>` - `Ok` if the configuration is valid, otherwise why it is not
+    ///
+    /// # Examples
+    /// ```rust
+    /// use sat_rs::solvers::novelty::NoveltyConfig;
+    ///
+    /// assert!(NoveltyConfig::novelty(0.2).validate().is_ok());
+    /// assert!(NoveltyConfig::novelty(1.5).validate().is_err());
+    /// ```
+    pub fn validate(&self) -> Result<(), String> {
+        match self.noise {
+            Some(noise) if !(0.0..=1.0).contains(&noise) => {
+                Err(format!("Invalid Novelty noise: {}", noise))
+            }
+            _ if !(0.0..=1.0).contains(&self.walk_probability) => Err(format!(
+                "Invalid Novelty walk probability: {}",
+                self.walk_probability
+            )),
+            _ => Ok(()),
+        }
+    }
+}
+
+impl Default for NoveltyConfig {
+    fn default() -> Self {
+        NoveltyConfig::adaptive()
+    }
+}
+
+/// Parses the noise of Novelty and Novelty+, which must be between `0` and `1`
+///
+/// # Arguments
+/// * `noise` - The noise, e.g. `0.5`
+///
+/// # Returns
+/// * `Result<f64, String>` - The noise, or an error message if it is not a probability
+///
+/// # Examples
+/// ```rust
+/// use sat_rs::solvers::novelty::parse_noise;
+///
+/// assert_eq!(parse_noise("0.2"), Ok(0.2));
+/// assert!(parse_noise("1.5").is_err());
+/// assert!(parse_noise("x").is_err());
+/// ```
+pub fn parse_noise(noise: &str) -> Result<f64, String> {
+    match noise.parse::<f64>() {
+        Ok(value) if (0.0..=1.0).contains(&value) => Ok(value),
+        _ => Err(format!("Invalid Novelty noise: {}", noise)),
+    }
+}
+
+/// Selects the variable to flip in an unsatisfied clause with the Novelty rule
+///
+/// Variables are ranked by score, ties are broken in favour of the variable flipped longest ago.
+///
+/// # Arguments
+/// * `state` - The [`LocalSearch`] state
+/// * `clause` - The literals of the unsatisfied clause
+/// * `noise` - The probability of the second best variable when the best one is the most
+///   recently flipped variable of the clause
+/// * `rng` - The random number generator
+///
+/// # Returns
+/// * `usize` - The variable to flip
+pub fn select_novelty(
+    state: &LocalSearch,
+    clause: &[Lit],
+    noise: f64,
+    rng: &mut impl Rng,
+) -> usize {
+    let better = |a: usize, b: usize| {
+        (state.score(a), std::cmp::Reverse(state.last_flip(a)))
+            > (state.score(b), std::cmp::Reverse(state.last_flip(b)))
+    };
+
+    let mut best = clause[0].var();
+    let mut second: Option<usize> = None;
+    for lit in &clause[1..] {
+        let var = lit.var();
+        if better(var, best) {
+            second = Some(best);
+            best = var;
+        } else if second.is_none_or(|second| better(var, second)) {
+            second = Some(var);
+        }
+    }
+
+    // No variable of the clause is the youngest until one of them was flipped
+    let youngest = clause
+        .iter()
+        .map(|lit| lit.var())
+        .filter(|var| state.last_flip(*var) > 0)
+        .max_by_key(|var| state.last_flip(*var));
+
+    match second {
+        Some(second) if youngest == Some(best) && rng.random_bool(noise) => second,
+        _ => best,
+    }
+}
+
+/// Hoos' adaptive noise: raised when the search stagnates, lowered when it improves
+///
+/// # Examples
+/// ```rust
+/// use sat_rs::solvers::novelty::AdaptiveNoise;
+///
+/// // 6 clauses, so the noise is raised after a step without improvement
+/// let mut adaptive = AdaptiveNoise::new(6, 3);
+/// adaptive.update(1, 3);
+/// assert_eq!(adaptive.noise(), 0.0);
+/// adaptive.update(2, 3);
+/// assert!(adaptive.noise() > 0.0);
+/// ```
+#[derive(Debug, Clone)]
+pub struct AdaptiveNoise {
+    noise: f64,
+    /// Number of flips without improvement after which the noise is raised
+    stagnation: u64,
+    /// Step of the last improvement or noise increase
+    last_change: u64,
+    /// Number of unsatisfied clauses at the last improvement or noise increase
+    last_unsat: usize,
+}
+
+impl AdaptiveNoise {
+    /// Creates the noise of a try, starting at `0`
+    ///
+    /// # Arguments
+    /// * `num_clauses` - The number of clauses, which sets how long the search may stagnate
+    /// * `num_unsat` - The number of unsatisfied clauses of the random interpretation
+    pub fn new(num_clauses: usize, num_unsat: usize) -> AdaptiveNoise {
+        AdaptiveNoise {
+            noise: 0.0,
+            stagnation: ((ADAPT_THETA * num_clauses as f64) as u64).max(1),
+            last_change: 0,
+            last_unsat: num_unsat,
+        }
+    }
+
+    /// Returns the current noise
+    pub fn noise(&self) -> f64 {
+        self.noise
+    }
+
+    /// Updates the noise after a flip
+    ///
+    /// # Arguments
+    /// * `step` - The step of the flip
+    /// * `num_unsat` - The number of unsatisfied clauses after the flip
+    pub fn update(&mut self, step: u64, num_unsat: usize) {
+        if num_unsat < self.last_unsat {
+            self.noise -= self.noise * ADAPT_PHI / 2.0;
+            self.last_change = step;
+            self.last_unsat = num_unsat;
+        } else if step - self.last_change > self.stagnation {
+            self.noise += (1.0 - self.noise) * ADAPT_PHI;
+            self.last_change = step;
+            self.last_unsat = num_unsat;
+        }
+    }
+}
+
+/// [`Solver`](crate::solvers::solver::Solver) running the [`novelty_algorithm`]
+///
+/// # Examples
+/// ```rust
+/// use sat_rs::notation::{Literal, Clause};
+/// use sat_rs::solvers::novelty::{NoveltyConfig, NoveltySolver};
+/// use sat_rs::solvers::solver::Solver;
+///
+/// let mut clause = Clause::new(); // Clause: p v q
+/// clause.literals.push(Literal{ value: 1, negated: false});
+/// clause.literals.push(Literal{ value: 2, negated: false});
+///
+/// let mut solver = NoveltySolver::new(10, 10, NoveltyConfig::adaptive());
+/// solver.add_clause(clause);
+/// assert!(solver.solve().is_sat());
+/// ```
+pub type NoveltySolver = LocalSearchSolver<Novelty>;
+
+/// The search of the [`novelty_algorithm`], run by a [`NoveltySolver`]
+#[derive(Debug, Clone)]
+pub struct Novelty {
+    max_tries: u32,
+    max_flips: u32,
+    config: NoveltyConfig,
+}
+
+impl NoveltySolver {
+    /// Creates a new [`NoveltySolver`]
+    ///
+    /// # Arguments
+    /// * `max_tries` - The number of times to try to find a satisfying interpretation
+    /// * `max_flips` - The number of times to flip the value of a variable in an interpretation
+    /// * `config` - A [`NoveltyConfig`] struct
+    ///
+    /// # Panics
+    /// If the noise or the random walk probability is not between `0` and `1`, see
+    /// [`NoveltyConfig::validate`].
+    pub fn new(max_tries: u32, max_flips: u32, config: NoveltyConfig) -> NoveltySolver {
+        if let Err(err) = config.validate() {
+            panic!("{}", err);
+        }
+        LocalSearchSolver::from_algorithm(Novelty {
+            max_tries,
+            max_flips,
+            config,
+        })
+    }
+}
+
+impl LocalSearchAlgorithm for Novelty {
+    fn search(
+        &self,
+        formula: &Formula,
+        rng: &mut impl Rng,
+        stats: &mut SolverStats,
+    ) -> SolveResult {
+        let mut state = LocalSearch::new(formula);
+        if state.has_empty_clause() {
+            return SolveResult::Unsat;
+        }
+
+        for _ in 0..self.max_tries {
+            stats.tries += 1;
+
+            // Create a random interpretation
+            state.randomize(rng);
+            let mut adaptive = AdaptiveNoise::new(state.num_clauses(), state.num_unsat());
+
+            for _ in 0..self.max_flips {
+                // Check if the interpretation satisfies the formula
+                if state.is_satisfied() {
+                    break;
+                }
+
+                // Randomly select a clause that is not satisfied by the interpretation
+                let unsat = state.unsat_clauses();
+                let clause = state.clause(unsat[rng.random_range(0..unsat.len())]);
+
+                // Random walk step of Novelty+, otherwise the Novelty rule
+                let var = if rng.random_bool(self.config.walk_probability) {
+                    clause[rng.random_range(0..clause.len())].var()
+                } else {
+                    let noise = self.config.noise.unwrap_or(adaptive.noise);
+                    select_novelty(&state, clause, noise, rng)
+                };
+
+                state.flip(var);
+                stats.flips += 1;
+                adaptive.update(state.step(), state.num_unsat());
+            }
+
+            if state.is_satisfied() {
+                return SolveResult::Sat(state.model(&formula.vars));
+            }
+        }
+
+        SolveResult::Unknown(format!(
+            "no model found in {} tries of {} flips",
+            self.max_tries, self.max_flips
+        ))
+    }
+}
+
+/// Novelty Algorithm for evaluation of propostional formulas
+///
+/// Like the [`crate::solvers::wsat::wsat_algorithm`], this algorithm generates random
+/// interpretations and flips variables of random unsatisfied clauses. The variables of the clause
+/// are ranked by score (make minus break), ties being broken in favour of the variable flipped
+/// longest ago. The best variable is flipped, unless it is the most recently flipped variable of
+/// the clause: then the second best variable is flipped with probability `noise`.
+///
+/// Novelty can get stuck in loops, which Novelty+ escapes by flipping a random variable of the
+/// clause with a small `walk_probability`. With adaptive noise (AdaptNovelty+), the noise starts
+/// at `0`. It is raised whenever the number of unsatisfied clauses has not improved for a sixth
+/// of the number of clauses flips, and lowered whenever it improves. If all tries are exhausted,
+/// the result is unknown.
+///
+/// # Pseudocode
+/// ```text
+/// procedure Novelty+(Set of Clauses S)
+///
+/// input: A set of clauses S
+/// output: a model of S, or unknown
+/// parameters: max_tries, max_flips, noise, walk_probability
+///
+/// begin
+///   repeat max_tries times
+///     create a random interpretation I
+///     repeat max_flips times
+///       if I satisfies S
+///         then return I
+///       randomly select an unsatisfied clause C
+///       with probability walk_probability
+///         p := a random variable of C
+///       else
+///         best, second := the two best variables of C by score, then age
+///         if best is the most recently flipped variable of C
+///           then with probability noise p := second, else p := best
+///         else
+///           p := best
+///       flip the value of p in I
+///       adapt the noise if it is adaptive
+///   return unknown
+/// end
+/// ```
+///
+/// # Arguments
+/// * `formula` - A [`Formula`] struct
+/// * `max_tries` - The number of times to try to find a satisfying interpretation
+/// * `max_flips` - The number of times to flip the value of a variable in an interpretation
+/// * `config` - A [`NoveltyConfig`] struct selecting the variant
+/// * `seed` - The seed of the random number generator, `None` to seed from the operating
+///   system
+///
+/// # Returns
+/// * [`SolveResult`] - [`SolveResult::Sat`] with the model, or [`SolveResult::Unknown`]
+///
+/// # Panics
+/// If the noise or the random walk probability is not between `0` and `1`, see
+/// [`NoveltyConfig::validate`].
+///
+/// # Examples
+/// ```
+/// use sat_rs::notation::{Literal, Clause, Formula};
+/// use sat_rs::solvers::novelty::{self, NoveltyConfig};
+///
+/// let mut clause = Clause::new(); // Clause: p v q
+/// clause.literals.push(Literal{ value: 1, negated: false});
+/// clause.literals.push(Literal{ value: 2, negated: false});
+///
+/// let mut formula = Formula::new();
+/// formula.add_clause(clause);
+///
+/// let config = NoveltyConfig::novelty(0.5);
+/// let result = novelty::novelty_algorithm(&mut formula, 10, 10, config, Some(1));
+/// assert_eq!(result.is_sat(), true);
+/// ```
+pub fn novelty_algorithm(
+    formula: &mut Formula,
+    max_tries: u32,
+    max_flips: u32,
+    config: NoveltyConfig,
+    seed: Option<u64>,
+) -> SolveResult {
+    NoveltySolver::new(max_tries, max_flips, config)
+        .with_seed(seed)
+        .search(formula)
+}
@@ -0,0 +1,309 @@
+use crate::notation::{Formula, Lit};
+use crate::solvers::localsearch::{LocalSearch, LocalSearchAlgorithm, LocalSearchSolver};
+use crate::solvers::result::SolveResult;
+use crate::solvers::solver::SolverStats;
+use rand::{Rng, RngExt};
+use std::str::FromStr;
+
+/// Break count function of the [`probsat_algorithm`], mapping the break count `b` of a variable
+/// to its (unnormalized) probability of being flipped
+///
+/// # Examples
+/// ```rust
+/// use sat_rs::solvers::probsat::BreakFunction;
+///
+/// let function: BreakFunction = "poly:2.38".parse().unwrap();
+/// assert_eq!(function, BreakFunction::Poly { cb: 2.38, eps: 1.0 });
+/// assert_eq!(function, BreakFunction::for_width(3));
+///
+/// let function: BreakFunction = "exp:3.7".parse().unwrap();
+/// assert_eq!(function, BreakFunction::for_width(5));
+/// assert_eq!(function.probability(0), 1.0);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum BreakFunction {
+    /// `(eps + b)^-cb`, `eps` is `1` by default
+    Poly { cb: f64, eps: f64 },
+    /// `cb^-b`
+    Exp { cb: f64 },
+}
+
+impl BreakFunction {
+    /// Returns the function with the published parameters of probSAT for clauses of width `k`
+    ///
+    /// The polynomial function is used for 3-SAT, and the exponential function for larger clauses.
+    pub fn for_width(k: usize) -> BreakFunction {
+        match k {
+            0..=3 => BreakFunction::Poly { cb: 2.38, eps: 1.0 },
+            4 => BreakFunction::Exp { cb: 3.0 },
+            5 => BreakFunction::Exp { cb: 3.7 },
+            6 => BreakFunction::Exp { cb: 5.1 },
+            _ => BreakFunction::Exp { cb: 5.4 },
+        }
+    }
+
+    /// Returns the unnormalized probability of flipping a variable with a break count of `breaks`
+    pub fn probability(&self, breaks: u32) -> f64 {
+        match *self {
+            BreakFunction::Poly { cb, eps } => (eps + breaks as f64).powf(-cb),
+            BreakFunction::Exp { cb } => cb.powi(-(breaks as i32)),
+        }
+    }
+}
+
+/// Parses the parameter at `index`, or returns `default` if it is not given
+fn parameter(params: &[&str], index: usize, default: Option<f64>) -> Result<f64, String> {
+    match (params.get(index), default) {
+        (None, Some(default)) => Ok(default),
+        (None, None) => Err("Missing probSAT parameter: cb".to_string()),
+        (Some(param), _) => match param.parse::<f64>() {
+            Ok(value) if value > 0.0 => Ok(value),
+            _ => Err(format!("Invalid probSAT parameter: {}", param)),
+        },
+    }
+}
+
+impl FromStr for BreakFunction {
+    type Err = String;
+
+    fn from_str(spec: &str) -> Result<BreakFunction, String> {
+        let mut tokens = spec.split(':');
+        let name = tokens.next().unwrap_or_default();
+        let params: Vec<&str> = tokens.collect();
+
+        let (function, max_params) = match name {
+            "poly" => (
+                BreakFunction::Poly {
+                    cb: parameter(&params, 0, None)?,
+                    eps: parameter(&params, 1, Some(1.0))?,
+                },
+                2,
+            ),
+            "exp" => (
+                BreakFunction::Exp {
+                    cb: parameter(&params, 0, None)?,
+                },
+                1,
+            ),
+            _ => return Err(format!("Unknown probSAT break function: {}", name)),
+        };
+
+        if params.len() > max_params {
+            return Err(format!("Too many parameters for probSAT: {}", spec));
+        }
+
+        Ok(function)
+    }
+}
+
+/// Probabilities of the break counts below this bound are computed once per search
+const CACHED_BREAKS: usize = 64;
+
+/// [`Solver`](crate::solvers::solver::Solver) running the [`probsat_algorithm`]
+///
+/// # Examples
+/// ```rust
+/// use sat_rs::notation::{Literal, Clause};
+/// use sat_rs::solvers::probsat::ProbsatSolver;
+/// use sat_rs::solvers::solver::Solver;
+///
+/// let mut clause = Clause::new(); // Clause: p v q
+/// clause.literals.push(Literal{ value: 1, negated: false});
+/// clause.literals.push(Literal{ value: 2, negated: false});
+///
+/// let mut solver = ProbsatSolver::new(10, 10, None);
+/// solver.add_clause(clause);
+/// assert!(solver.solve().is_sat());
+/// ```
+pub type ProbsatSolver = LocalSearchSolver<Probsat>;
+
+/// The search of the [`probsat_algorithm`], run by a [`ProbsatSolver`]
+#[derive(Debug, Clone)]
+pub struct Probsat {
+    max_tries: u32,
+    max_flips: u32,
+    function: Option<BreakFunction>,
+}
+
+impl ProbsatSolver {
+    /// Creates a new [`ProbsatSolver`]
+    ///
+    /// # Arguments
+    /// * `max_tries` - The number of times to try to find a satisfying interpretation
+    /// * `max_flips` - The number of times to flip the value of a variable in an interpretation
+    /// * `function` - The [`BreakFunction`], `None` to use [`BreakFunction::for_width`] of the
+    ///   longest clause
+    pub fn new(max_tries: u32, max_flips: u32, function: Option<BreakFunction>) -> ProbsatSolver {
+        LocalSearchSolver::from_algorithm(Probsat {
+            max_tries,
+            max_flips,
+            function,
+        })
+    }
+}
+
+impl LocalSearchAlgorithm for Probsat {
+    fn search(
+        &self,
+        formula: &Formula,
+        rng: &mut impl Rng,
+        stats: &mut SolverStats,
+    ) -> SolveResult {
+        let mut state = LocalSearch::new(formula);
+        if state.has_empty_clause() {
+            return SolveResult::Unsat;
+        }
+
+        let function = self.function.unwrap_or_else(|| {
+            let width = (0..state.num_clauses())
+                .map(|clause| state.clause(clause).len())
+                .max()
+                .unwrap_or(0);
+            BreakFunction::for_width(width)
+        });
+        let table: Vec<f64> = (0..CACHED_BREAKS as u32)
+            .map(|breaks| function.probability(breaks))
+            .collect();
+        let mut weights = Vec::new();
+
+        for _ in 0..self.max_tries {
+            stats.tries += 1;
+
+            // Create a random interpretation
+            state.randomize(rng);
+
+            for _ in 0..self.max_flips {
+                // Check if the interpretation satisfies the formula
+                if state.is_satisfied() {
+                    break;
+                }
+
+                // Randomly select a clause that is not satisfied by the interpretation
+                let unsat = state.unsat_clauses();
+                let clause = state.clause(unsat[rng.random_range(0..unsat.len())]);
+
+                // Select a variable from the clause with probability proportional to its weight
+                let var = select(&state, clause, &function, &table, &mut weights, rng);
+
+                state.flip(var);
+                stats.flips += 1;
+            }
+
+            if state.is_satisfied() {
+                return SolveResult::Sat(state.model(&formula.vars));
+            }
+        }
+
+        SolveResult::Unknown(format!(
+            "no model found in {} tries of {} flips",
+            self.max_tries, self.max_flips
+        ))
+    }
+}
+
+/// Selects a variable of a clause with probability proportional to the break function of its
+/// break count
+fn select(
+    state: &LocalSearch,
+    clause: &[Lit],
+    function: &BreakFunction,
+    table: &[f64],
+    weights: &mut Vec<f64>,
+    rng: &mut impl Rng,
+) -> usize {
+    weights.clear();
+    let mut sum = 0.0;
+    for lit in clause {
+        let breaks = state.breaks(lit.var());
+        let weight = match table.get(breaks as usize) {
+            Some(weight) => *weight,
+            None => function.probability(breaks),
+        };
+        sum += weight;
+        weights.push(weight);
+    }
+
+    let mut threshold = rng.random::<f64>() * sum;
+    for (lit, weight) in clause.iter().zip(weights.iter()) {
+        if threshold < *weight {
+            return lit.var();
+        }
+        threshold -= weight;
+    }
+
+    // Rounding can leave a tiny remainder, which belongs to the last variable
+    clause[clause.len() - 1].var()
+}
+
+/// probSAT Algorithm for evaluation of propostional formulas
+///
+/// This algorithm generates random interpretations and flips variables of random unsatisfied
+/// clauses, like the [`crate::solvers::wsat::wsat_algorithm`]. The variable to flip is drawn at
+/// random from the clause, with a probability proportional to a [`BreakFunction`] of its break
+/// count, i.e. the number of clauses that flipping it would falsify. Unlike WalkSAT, probSAT
+/// never flips greedily and has no separate noise parameter: the break function alone decides how
+/// strongly variables with a low break count are preferred. If all tries are exhausted, the
+/// result is unknown.
+///
+/// The published parameters (see [`BreakFunction::for_width`]) are tuned for uniform random
+/// k-SAT, on which probSAT usually beats WalkSAT.
+///
+/// # Pseudocode
+/// ```text
+/// procedure probSAT(Set of Clauses S)
+///
+/// input: A set of clauses S
+/// output: a model of S, or unknown
+/// parameters: max_tries, max_flips, break function f
+///
+/// begin
+///   repeat max_tries times
+///     create a random interpretation I
+///     repeat max_flips times
+///       if I satisfies S
+///         then return I
+///       randomly select an unsatisfied clause C
+///       select a variable p from C with probability f(break(p)) / sum of f(break(q)) for q in C
+///       flip the value of p in I
+///   return unknown
+/// end
+/// ```
+///
+/// # Arguments
+/// * `formula` - A [`Formula`] struct
+/// * `max_tries` - The number of times to try to find a satisfying interpretation
+/// * `max_flips` - The number of times to flip the value of a variable in an interpretation
+/// * `function` - The [`BreakFunction`], `None` to use [`BreakFunction::for_width`] of the longest
+///   clause
+/// * `seed` - The seed of the random number generator, `None` to seed from the operating
+///   system
+///
+/// # Returns
+/// * [`SolveResult`] - [`SolveResult::Sat`] with the model, or [`SolveResult::Unknown`]
+///
+/// # Examples
+/// ```
+/// use sat_rs::notation::{Literal, Clause, Formula};
+/// use sat_rs::solvers::probsat;
+///
+/// let mut clause = Clause::new(); // Clause: p v q
+/// clause.literals.push(Literal{ value: 1, negated: false});
+/// clause.literals.push(Literal{ value: 2, negated: false});
+///
+/// let mut formula = Formula::new();
+/// formula.add_clause(clause);
+///
+/// let result = probsat::probsat_algorithm(&mut formula, 10, 10, None, Some(1));
+/// assert_eq!(result.is_sat(), true);
+/// ```
+pub fn probsat_algorithm(
+    formula: &mut Formula,
+    max_tries: u32,
+    max_flips: u32,
+    function: Option<BreakFunction>,
+    seed: Option<u64>,
+) -> SolveResult {
+    ProbsatSolver::new(max_tries, max_flips, function)
+        .with_seed(seed)
+        .search(formula)
+}
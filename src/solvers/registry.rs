@@ -0,0 +1,236 @@
+//! # Solver Registry
+//!
+//! Builds any [`Solver`] from its name and a [`SolverConfig`] shared by all solvers, so that the
+//! algorithm can be selected at runtime, e.g. from the command line.
+use crate::solvers::annealing::{AnnealingConfig, AnnealingSolver};
+use crate::solvers::branching::Branching;
+use crate::solvers::ccanr::{CcanrConfig, CcanrSolver};
+use crate::solvers::cdcl::{CdclConfig, CdclSolver};
+use crate::solvers::chaos::ChaosSolver;
+use crate::solvers::clausedb::ClauseDbConfig;
+use crate::solvers::dpll::DpllSolver;
+use crate::solvers::gsat::GsatSolver;
+use crate::solvers::interactive::InteractiveSolver;
+use crate::solvers::novelty::{NoveltyConfig, NoveltySolver};
+use crate::solvers::probsat::{BreakFunction, ProbsatSolver};
+use crate::solvers::restart::Restart;
+use crate::solvers::solver::Solver;
+use crate::solvers::weighting::{DdfwConfig, DdfwSolver, SapsConfig, SapsSolver};
+use crate::solvers::wsat::{WsatMode, WsatSolver};
+
+/// Names of the solvers known to [`build_solver`]
+pub const SOLVERS: &[&str] = &[
+    "interactive",
+    "chaos",
+    "wsat",
+    "gsat",
+    "probsat",
+    "novelty",
+    "novelty+",
+    "adaptnovelty+",
+    "ddfw",
+    "saps",
+    "ccanr",
+    "annealing",
+    "dpll",
+    "cdcl",
+];
+
+/// Returns `true` if a solver of [`SOLVERS`] makes random choices, so that its run depends on
+/// [`SolverConfig::seed`]
+///
+/// # Examples
+/// ```rust
+/// use sat_rs::solvers::registry::is_randomized;
+///
+/// assert!(is_randomized("wsat"));
+/// assert!(!is_randomized("cdcl"));
+/// ```
+pub fn is_randomized(name: &str) -> bool {
+    SOLVERS.contains(&name) && !matches!(name, "interactive" | "dpll" | "cdcl")
+}
+
+/// Parameters of every solver, each solver only reads the ones that apply to it
+///
+/// # Examples
+/// ```rust
+/// use sat_rs::solvers::registry::SolverConfig;
+///
+/// let config = SolverConfig {
+///     max_tries: 10,
+///     max_flips: 1000,
+///     ..Default::default()
+/// };
+/// ```
+#[derive(Debug, Clone)]
+pub struct SolverConfig {
+    /// Number of random interpretations tried by the local search solvers
+    pub max_tries: u32,
+    /// Number of flips per try of the local search solvers
+    pub max_flips: u32,
+    /// Probability of a greedy flip in GSAT, `None` to always flip greedily
+    pub walk_probability: Option<f32>,
+    /// Variable selection rule of WalkSAT
+    pub wsat_mode: WsatMode,
+    /// Number of steps during which a flipped variable is tabu in GSAT and WalkSAT, `0` to
+    /// disable the tabu list
+    pub tabu_tenure: u64,
+    /// Break count function of probSAT, `None` to pick it from the clause width
+    pub break_function: Option<BreakFunction>,
+    /// Noise of Novelty and Novelty+, AdaptNovelty+ adapts it during the search
+    pub novelty_noise: f64,
+    /// Initial weight and sideways probability of DDFW
+    pub ddfw: DdfwConfig,
+    /// Scaling, smoothing and random walk of SAPS
+    pub saps: SapsConfig,
+    /// Weight smoothing of CCAnr
+    pub ccanr: CcanrConfig,
+    /// Cooling schedule and temperatures of simulated annealing
+    pub annealing: AnnealingConfig,
+    /// Seed of the randomized solvers, so that a run can be reproduced, `None` to seed from the
+    /// operating system
+    pub seed: Option<u64>,
+    /// Variable order of the complete solvers
+    pub branching: Branching,
+    /// Restart policy of the CDCL solver
+    pub restart: Restart,
+    /// Learnt clause reduction schedule of the CDCL solver
+    pub clause_db: ClauseDbConfig,
+}
+
+impl Default for SolverConfig {
+    fn default() -> Self {
+        SolverConfig {
+            max_tries: 100,
+            max_flips: 100,
+            walk_probability: None,
+            wsat_mode: WsatMode::default(),
+            tabu_tenure: 0,
+            break_function: None,
+            novelty_noise: 0.5,
+            ddfw: DdfwConfig::default(),
+            saps: SapsConfig::default(),
+            ccanr: CcanrConfig::default(),
+            annealing: AnnealingConfig::default(),
+            seed: None,
+            branching: Branching::default(),
+            restart: Restart::default(),
+            clause_db: ClauseDbConfig::default(),
+        }
+    }
+}
+
+/// Builds a solver from its name
+///
+/// # Arguments
+/// * `name` - One of the [`SOLVERS`]
+/// * `config` - A [`SolverConfig`] struct
+///
+/// # Returns
+/// * [`Box<dyn Solver>`] - The solver, or an error message if the name is unknown or its
+///   parameters are out of range
+///
+/// # Examples
+/// ```rust
+/// use sat_rs::solvers::registry::{build_solver, SolverConfig, SOLVERS};
+///
+/// for name in SOLVERS {
+///     assert!(build_solver(name, &SolverConfig::default()).is_ok());
+/// }
+/// assert!(build_solver("oracle", &SolverConfig::default()).is_err());
+///
+/// let config = SolverConfig { novelty_noise: 1.5, ..SolverConfig::default() };
+/// assert!(build_solver("novelty", &config).is_err());
+///
+/// let mut config = SolverConfig::default();
+/// config.annealing.min_temperature = 3.0;
+/// assert!(build_solver("annealing", &config).is_err());
+///
+/// let mut config = SolverConfig::default();
+/// config.saps.alpha = 1.0;
+/// assert!(build_solver("saps", &config).is_err());
+/// ```
+pub fn build_solver(name: &str, config: &SolverConfig) -> Result<Box<dyn Solver>, String> {
+    match name {
+        "novelty" | "novelty+" => NoveltyConfig::novelty(config.novelty_noise).validate()?,
+        "ddfw" => config.ddfw.validate()?,
+        "saps" => config.saps.validate()?,
+        "ccanr" => config.ccanr.validate()?,
+        "annealing" => config.annealing.validate()?,
+        _ => (),
+    }
+
+    let solver: Box<dyn Solver> = match name {
+        "interactive" => Box::new(InteractiveSolver::new()),
+        "chaos" => Box::new(ChaosSolver::new(config.max_tries).with_seed(config.seed)),
+        "wsat" => Box::new(
+            WsatSolver::new(
+                config.max_tries,
+                config.max_flips,
+                config.wsat_mode,
+                config.tabu_tenure,
+            )
+            .with_seed(config.seed),
+        ),
+        "gsat" => Box::new(
+            GsatSolver::new(
+                config.max_tries,
+                config.max_flips,
+                config.walk_probability,
+                config.tabu_tenure,
+            )
+            .with_seed(config.seed),
+        ),
+        "probsat" => Box::new(
+            ProbsatSolver::new(config.max_tries, config.max_flips, config.break_function)
+                .with_seed(config.seed),
+        ),
+        "novelty" => Box::new(
+            NoveltySolver::new(
+                config.max_tries,
+                config.max_flips,
+                NoveltyConfig::novelty(config.novelty_noise),
+            )
+            .with_seed(config.seed),
+        ),
+        "novelty+" => Box::new(
+            NoveltySolver::new(
+                config.max_tries,
+                config.max_flips,
+                NoveltyConfig::novelty_plus(config.novelty_noise),
+            )
+            .with_seed(config.seed),
+        ),
+        "adaptnovelty+" => Box::new(
+            NoveltySolver::new(
+                config.max_tries,
+                config.max_flips,
+                NoveltyConfig::adaptive(),
+            )
+            .with_seed(config.seed),
+        ),
+        "ddfw" => Box::new(
+            DdfwSolver::new(config.max_tries, config.max_flips, config.ddfw).with_seed(config.seed),
+        ),
+        "saps" => Box::new(
+            SapsSolver::new(config.max_tries, config.max_flips, config.saps).with_seed(config.seed),
+        ),
+        "ccanr" => Box::new(
+            CcanrSolver::new(config.max_tries, config.max_flips, config.ccanr)
+                .with_seed(config.seed),
+        ),
+        "annealing" => Box::new(
+            AnnealingSolver::new(config.max_tries, config.max_flips, config.annealing)
+                .with_seed(config.seed),
+        ),
+        "dpll" => Box::new(DpllSolver::new(config.branching)),
+        "cdcl" => Box::new(CdclSolver::new(CdclConfig {
+            branching: config.branching,
+            restart: config.restart,
+            clause_db: config.clause_db.clone(),
+        })),
+        _ => return Err(format!("Unknown solver: {}", name)),
+    };
+
+    Ok(solver)
+}
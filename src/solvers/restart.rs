@@ -0,0 +1,321 @@
+//! # Restart Policies
+//!
+//! Policies deciding when a conflict-driven solver abandons its current decisions and restarts
+//! the search from decision level zero, keeping its learnt clauses and heuristic state.
+//!
+//! ## Contents
+//!
+//! * [`RestartPolicy`] - The interface every restart policy implements.
+//! * [`Luby`] - Restarts after a number of conflicts following the Luby sequence.
+//! * [`Geometric`] - Restarts after a geometrically growing number of conflicts.
+//! * [`Glucose`] - Restarts when recent learnt clauses are worse than average.
+//! * [`NoRestarts`] - Never restarts.
+//! * [`Restart`] - Selects one of the above, with its parameters, by name.
+use std::str::FromStr;
+
+/// A policy deciding when to restart the search
+pub trait RestartPolicy {
+    /// Records a conflict whose learnt clause has the given literal block distance
+    fn conflict(&mut self, lbd: usize);
+
+    /// Returns `true` if the solver should restart before its next decision
+    ///
+    /// Returning `true` starts a new restart interval.
+    fn should_restart(&mut self) -> bool;
+}
+
+/// Returns the element at a zero-based index of the Luby sequence `1, 1, 2, 1, 1, 2, 4, 1, ...`
+///
+/// # Examples
+/// ```rust
+/// use sat_rs::solvers::restart::luby;
+///
+/// let sequence: Vec<u64> = (0..7).map(luby).collect();
+/// assert_eq!(sequence, vec![1, 1, 2, 1, 1, 2, 4]);
+/// ```
+pub fn luby(mut index: u64) -> u64 {
+    // Find the finite subsequence containing the index, and its exponent
+    let mut size: u64 = 1;
+    let mut exponent: u32 = 0;
+    while size < index + 1 {
+        exponent += 1;
+        size = 2 * size + 1;
+    }
+
+    while size - 1 != index {
+        size = (size - 1) >> 1;
+        exponent -= 1;
+        index %= size;
+    }
+
+    1 << exponent
+}
+
+/// Restarts after `unit * luby(i)` conflicts in the `i`-th restart interval
+#[derive(Debug, Clone)]
+pub struct Luby {
+    unit: u64,
+    restarts: u64,
+    conflicts: u64,
+}
+
+impl Luby {
+    /// Creates a new [`Luby`] policy with the given number of conflicts per unit of the sequence
+    pub fn new(unit: u64) -> Luby {
+        Luby {
+            unit,
+            restarts: 0,
+            conflicts: 0,
+        }
+    }
+}
+
+impl RestartPolicy for Luby {
+    fn conflict(&mut self, _lbd: usize) {
+        self.conflicts += 1;
+    }
+
+    fn should_restart(&mut self) -> bool {
+        if self.conflicts < self.unit * luby(self.restarts) {
+            return false;
+        }
+        self.restarts += 1;
+        self.conflicts = 0;
+        true
+    }
+}
+
+/// Restarts after `initial * factor^i` conflicts in the `i`-th restart interval
+#[derive(Debug, Clone)]
+pub struct Geometric {
+    limit: f64,
+    factor: f64,
+    conflicts: u64,
+}
+
+impl Geometric {
+    /// Creates a new [`Geometric`] policy
+    ///
+    /// # Arguments
+    /// * `initial` - The number of conflicts before the first restart
+    /// * `factor` - The growth factor of the interval, at least `1.0`
+    pub fn new(initial: u64, factor: f64) -> Geometric {
+        Geometric {
+            limit: initial as f64,
+            factor,
+            conflicts: 0,
+        }
+    }
+}
+
+impl RestartPolicy for Geometric {
+    fn conflict(&mut self, _lbd: usize) {
+        self.conflicts += 1;
+    }
+
+    fn should_restart(&mut self) -> bool {
+        if (self.conflicts as f64) < self.limit {
+            return false;
+        }
+        self.limit *= self.factor;
+        self.conflicts = 0;
+        true
+    }
+}
+
+/// Exponential moving average which starts as a cumulative average
+///
+/// Until `1 / alpha` values have been seen, every value is weighted equally, which avoids the
+/// bias towards the initial value of a plain exponential moving average.
+#[derive(Debug, Clone)]
+struct Ema {
+    value: f64,
+    alpha: f64,
+    count: u64,
+}
+
+impl Ema {
+    fn new(alpha: f64) -> Ema {
+        Ema {
+            value: 0.0,
+            alpha,
+            count: 0,
+        }
+    }
+
+    fn update(&mut self, x: f64) {
+        self.count += 1;
+        let alpha = self.alpha.max(1.0 / self.count as f64);
+        self.value += alpha * (x - self.value);
+    }
+}
+
+/// Glucose-style restarts based on moving averages of the literal block distance
+///
+/// A fast moving average follows the LBD of the most recent learnt clauses, and a slow one the
+/// LBD over the whole run. The solver restarts when the recent clauses are noticeably worse than
+/// the long-term average, i.e. when `fast > margin * slow`, but not before `min_conflicts`
+/// conflicts have passed since the last restart.
+#[derive(Debug, Clone)]
+pub struct Glucose {
+    fast: Ema,
+    slow: Ema,
+    margin: f64,
+    min_conflicts: u64,
+    conflicts: u64,
+}
+
+impl Glucose {
+    /// Creates a new [`Glucose`] policy
+    ///
+    /// # Arguments
+    /// * `margin` - How much worse the recent average must be, Glucose uses `1 / 0.8 = 1.25`
+    /// * `min_conflicts` - The minimum number of conflicts between two restarts
+    pub fn new(margin: f64, min_conflicts: u64) -> Glucose {
+        Glucose {
+            fast: Ema::new(1.0 / 32.0),
+            slow: Ema::new(1.0 / 4096.0),
+            margin,
+            min_conflicts,
+            conflicts: 0,
+        }
+    }
+}
+
+impl RestartPolicy for Glucose {
+    fn conflict(&mut self, lbd: usize) {
+        self.conflicts += 1;
+        self.fast.update(lbd as f64);
+        self.slow.update(lbd as f64);
+    }
+
+    fn should_restart(&mut self) -> bool {
+        if self.conflicts < self.min_conflicts || self.fast.value <= self.margin * self.slow.value {
+            return false;
+        }
+        self.conflicts = 0;
+        true
+    }
+}
+
+/// Never restarts
+#[derive(Debug, Clone)]
+pub struct NoRestarts;
+
+impl RestartPolicy for NoRestarts {
+    fn conflict(&mut self, _lbd: usize) {}
+
+    fn should_restart(&mut self) -> bool {
+        false
+    }
+}
+
+/// A [`RestartPolicy`] with its parameters, used to configure the restarts of a solver
+///
+/// Policies are parsed from their name optionally followed by colon-separated parameters:
+/// `none`, `luby[:unit]`, `geometric[:initial[:factor]]` or `glucose[:margin[:min_conflicts]]`.
+///
+/// # Examples
+/// ```rust
+/// use sat_rs::solvers::restart::Restart;
+///
+/// let restart: Restart = "geometric:50:1.2".parse().unwrap();
+/// assert_eq!(restart, Restart::Geometric { initial: 50, factor: 1.2 });
+///
+/// let restart: Restart = "luby".parse().unwrap();
+/// assert_eq!(restart, Restart::Luby { unit: 100 });
+///
+/// let policy = restart.build();
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Restart {
+    /// [`NoRestarts`]
+    Never,
+    /// [`Luby`], with `100` conflicts per unit by default
+    Luby { unit: u64 },
+    /// [`Geometric`], starting with `100` conflicts and a factor of `1.5` by default
+    Geometric { initial: u64, factor: f64 },
+    /// [`Glucose`], with a margin of `1.25` and at least `50` conflicts by default
+    Glucose { margin: f64, min_conflicts: u64 },
+}
+
+impl Default for Restart {
+    fn default() -> Self {
+        Restart::Luby { unit: 100 }
+    }
+}
+
+impl Restart {
+    /// Creates the restart policy
+    pub fn build(self) -> Box<dyn RestartPolicy> {
+        match self {
+            Restart::Never => Box::new(NoRestarts),
+            Restart::Luby { unit } => Box::new(Luby::new(unit)),
+            Restart::Geometric { initial, factor } => Box::new(Geometric::new(initial, factor)),
+            Restart::Glucose {
+                margin,
+                min_conflicts,
+            } => Box::new(Glucose::new(margin, min_conflicts)),
+        }
+    }
+}
+
+/// Parses the parameter at `index`, or returns `default` if it is not given
+///
+/// Values for which `valid` is `false` are rejected, so that a policy cannot restart before
+/// every decision and keep the search from making progress.
+fn parameter<T: FromStr>(
+    params: &[&str],
+    index: usize,
+    default: T,
+    valid: impl Fn(&T) -> bool,
+) -> Result<T, String> {
+    match params.get(index) {
+        None => Ok(default),
+        Some(param) => match param.parse::<T>() {
+            Ok(value) if valid(&value) => Ok(value),
+            _ => Err(format!("Invalid restart parameter: {}", param)),
+        },
+    }
+}
+
+impl FromStr for Restart {
+    type Err = String;
+
+    fn from_str(spec: &str) -> Result<Restart, String> {
+        let mut tokens = spec.split(':');
+        let name = tokens.next().unwrap_or_default();
+        let params: Vec<&str> = tokens.collect();
+
+        let (restart, max_params) = match name {
+            "none" => (Restart::Never, 0),
+            "luby" => (
+                Restart::Luby {
+                    unit: parameter(&params, 0, 100, |unit| *unit > 0)?,
+                },
+                1,
+            ),
+            "geometric" => (
+                Restart::Geometric {
+                    initial: parameter(&params, 0, 100, |initial| *initial > 0)?,
+                    factor: parameter(&params, 1, 1.5, |factor| *factor >= 1.0)?,
+                },
+                2,
+            ),
+            "glucose" => (
+                Restart::Glucose {
+                    margin: parameter(&params, 0, 1.25, |margin| *margin > 0.0)?,
+                    min_conflicts: parameter(&params, 1, 50, |min| *min > 0)?,
+                },
+                2,
+            ),
+            _ => return Err(format!("Unknown restart policy: {}", name)),
+        };
+
+        if params.len() > max_params {
+            return Err(format!("Too many parameters for restart policy: {}", spec));
+        }
+
+        Ok(restart)
+    }
+}
@@ -0,0 +1,165 @@
+//! # Solver Results
+//!
+//! The outcome of running a solver on a formula.
+//!
+//! ## Contents
+//!
+//! * [`SolveResult`] - Satisfiable with a model, unsatisfiable, or unknown.
+//! * [`Model`] - A total assignment of the variables of a formula.
+use crate::assignment::Assignment;
+use crate::notation::Formula;
+use std::collections::HashMap;
+use std::fmt;
+
+/// A satisfying assignment returned by a solver
+///
+/// Maps every variable of the formula to its value. Models are built from an [`Assignment`] or
+/// from an interpretation in `HashMap` form.
+///
+/// # Examples
+/// ```rust
+/// use sat_rs::notation::{Literal, Clause, Formula};
+/// use sat_rs::solvers::result::Model;
+/// use std::collections::HashMap;
+///
+/// let mut clause = Clause::new();
+/// clause.literals.push(Literal{ value: 1, negated: true}); // Clause: -p
+///
+/// let mut formula = Formula::new();
+/// formula.clauses.push(clause);
+/// formula.vars.push(1);
+///
+/// let model = Model::from(HashMap::from([(1, false)]));
+/// assert_eq!(model.value(1), Some(false));
+/// assert_eq!(model.value(2), None);
+/// assert!(model.satisfies(&formula));
+/// assert_eq!(model.to_string(), "v -1 0");
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct Model {
+    values: Assignment,
+}
+
+impl Model {
+    /// Returns the value of a variable, or `None` if the variable is not part of the model
+    pub fn value(&self, var: i32) -> Option<bool> {
+        self.values.get(var)
+    }
+
+    /// Returns the model as an [`Assignment`]
+    pub fn assignment(&self) -> &Assignment {
+        &self.values
+    }
+
+    /// Returns the model as an interpretation mapping variables to values
+    pub fn interpretation(&self) -> HashMap<i32, bool> {
+        self.values.clone().into()
+    }
+
+    /// Returns the model as signed DIMACS literals, sorted by variable
+    pub fn literals(&self) -> Vec<i32> {
+        self.values
+            .iter()
+            .map(|(var, value)| if value { var } else { -var })
+            .collect()
+    }
+
+    /// Checks that every clause of a formula contains a literal which is true in the model
+    ///
+    /// Variables missing from the model falsify their literals.
+    ///
+    /// # Arguments
+    /// * `formula` - A [`Formula`] struct
+    ///
+    /// # Returns
+    /// * `bool` - Returns `true` if the model satisfies the formula, otherwise `false`
+    pub fn satisfies(&self, formula: &Formula) -> bool {
+        formula.clauses.iter().all(|clause| {
+            clause
+                .literals
+                .iter()
+                .any(|literal| self.value(literal.value) == Some(!literal.negated))
+        })
+    }
+}
+
+impl From<Assignment> for Model {
+    fn from(values: Assignment) -> Self {
+        Model { values }
+    }
+}
+
+impl From<HashMap<i32, bool>> for Model {
+    fn from(values: HashMap<i32, bool>) -> Self {
+        Model {
+            values: Assignment::from(values),
+        }
+    }
+}
+
+/// Formats the model as a SAT competition value line, e.g. `v 1 -2 3 0`
+impl fmt::Display for Model {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "v")?;
+        for lit in self.literals() {
+            write!(f, " {}", lit)?;
+        }
+        write!(f, " 0")
+    }
+}
+
+/// The outcome of a solver
+///
+/// Incomplete solvers, such as the local search solvers, return [`SolveResult::Unknown`] when
+/// they give up, since failing to find a model does not prove that there is none.
+///
+/// # Examples
+/// ```rust
+/// use sat_rs::solvers::result::SolveResult;
+///
+/// let result = SolveResult::Unknown("no model found".to_string());
+/// assert!(!result.is_sat());
+/// assert!(!result.is_unsat());
+/// assert_eq!(result.model(), None);
+/// assert_eq!(result.to_string(), "UNKNOWN");
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub enum SolveResult {
+    /// The formula is satisfiable, with a satisfying model
+    Sat(Model),
+    /// The formula is unsatisfiable
+    Unsat,
+    /// The solver could not decide the formula, with the reason why
+    Unknown(String),
+}
+
+impl SolveResult {
+    /// Returns `true` if the formula was found satisfiable
+    pub fn is_sat(&self) -> bool {
+        matches!(self, SolveResult::Sat(_))
+    }
+
+    /// Returns `true` if the formula was proven unsatisfiable
+    pub fn is_unsat(&self) -> bool {
+        matches!(self, SolveResult::Unsat)
+    }
+
+    /// Returns the model if the formula was found satisfiable
+    pub fn model(&self) -> Option<&Model> {
+        match self {
+            SolveResult::Sat(model) => Some(model),
+            _ => None,
+        }
+    }
+}
+
+/// Formats the result as in a SAT competition status line, without the leading `s`
+impl fmt::Display for SolveResult {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SolveResult::Sat(_) => write!(f, "SATISFIABLE"),
+            SolveResult::Unsat => write!(f, "UNSATISFIABLE"),
+            SolveResult::Unknown(_) => write!(f, "UNKNOWN"),
+        }
+    }
+}
@@ -0,0 +1,175 @@
+//! # Solver Interface
+//!
+//! The [`Solver`] trait implemented by every solver, so that algorithms can be swapped without
+//! changing the code that feeds them clauses and reads their results. Solvers are usually built
+//! by name with [`crate::solvers::registry::build_solver`].
+//!
+//! ## Contents
+//!
+//! * [`Solver`] - The interface every solver implements.
+//! * [`SolverStats`] - Counters describing the work done by a solver.
+use crate::notation::{Clause, Formula, Literal};
+use crate::proof::ProofWriter;
+use crate::solvers::result::{Model, SolveResult};
+use rand::SeedableRng;
+use rand_chacha::ChaCha8Rng;
+use std::fmt;
+use std::io;
+
+/// A SAT solver which is fed clauses and then asked to decide their satisfiability
+///
+/// Clauses accumulate across calls, so a solver can be solved again after adding more clauses.
+///
+/// # Examples
+/// ```rust
+/// use sat_rs::notation::{Literal, Clause};
+/// use sat_rs::solvers::registry::{build_solver, SolverConfig};
+///
+/// let mut solver = build_solver("cdcl", &SolverConfig::default()).unwrap();
+///
+/// let mut clause = Clause::new(); // Clause: p v q
+/// clause.literals.push(Literal{ value: 1, negated: false});
+/// clause.literals.push(Literal{ value: 2, negated: false});
+/// solver.add_clause(clause);
+///
+/// assert!(solver.solve().is_sat());
+///
+/// // Assuming -p and -q contradicts the clause, without adding the assumptions for good
+/// let assumptions = [Literal{ value: 1, negated: true}, Literal{ value: 2, negated: true}];
+/// assert!(solver.solve_with_assumptions(&assumptions).is_unsat());
+/// assert!(solver.solve().is_sat());
+/// ```
+pub trait Solver {
+    /// Adds a clause to the problem
+    fn add_clause(&mut self, clause: Clause);
+
+    /// Adds every clause of a formula to the problem
+    fn add_formula(&mut self, formula: &Formula) {
+        for clause in &formula.clauses {
+            self.add_clause(clause.clone());
+        }
+    }
+
+    /// Decides the satisfiability of the clauses added so far
+    fn solve(&mut self) -> SolveResult {
+        self.solve_with_assumptions(&[])
+    }
+
+    /// Decides the satisfiability of the clauses added so far, with the given literals assumed
+    /// to be true
+    ///
+    /// The assumptions only hold for this call. [`SolveResult::Unsat`] means that there is no
+    /// model in which all the assumptions are true.
+    fn solve_with_assumptions(&mut self, assumptions: &[Literal]) -> SolveResult;
+
+    /// Returns the model found by the last call to solve, if it was satisfiable
+    fn model(&self) -> Option<&Model>;
+
+    /// Returns the work done by the solver so far
+    fn stats(&self) -> &SolverStats;
+
+    /// Streams a proof to the given writer whenever the solver finds the clauses unsatisfiable
+    ///
+    /// Proofs refute the clauses alone, so they are only complete for calls without
+    /// assumptions.
+    ///
+    /// # Returns
+    /// * `bool` - `false` if the solver cannot write proofs, e.g. an incomplete solver
+    fn set_proof(&mut self, _proof: ProofWriter) -> bool {
+        false
+    }
+
+    /// Flushes the proof given to [`Solver::set_proof`], if any
+    ///
+    /// # Returns
+    /// * [`io::Result`] - The first error met while writing the proof, if any
+    fn finish_proof(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+/// Counters describing the work done by a solver
+///
+/// Counters which do not apply to a solver stay at zero, e.g. local search solvers do not make
+/// decisions and complete solvers do not flip variables.
+///
+/// # Examples
+/// ```rust
+/// use sat_rs::solvers::solver::SolverStats;
+///
+/// let stats = SolverStats {
+///     conflicts: 3,
+///     ..Default::default()
+/// };
+/// assert_eq!(stats.to_string(), "c conflicts: 3");
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SolverStats {
+    /// Number of random interpretations a local search solver started from
+    pub tries: u64,
+    /// Number of variables flipped by a local search solver
+    pub flips: u64,
+    /// Number of steps in which the tabu list kept the best variable from being flipped
+    pub tabu_blocked: u64,
+    /// Number of branching decisions
+    pub decisions: u64,
+    /// Number of assignments implied by unit propagation
+    pub propagations: u64,
+    /// Number of falsified clauses encountered during the search
+    pub conflicts: u64,
+    /// Number of restarts of the search
+    pub restarts: u64,
+    /// Number of clauses learnt from conflicts
+    pub learnt_clauses: u64,
+    /// Number of learnt clauses deleted
+    pub deleted_clauses: u64,
+}
+
+/// Formats the non-zero counters as SAT competition comment lines, e.g. `c conflicts: 3`
+impl fmt::Display for SolverStats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let counters = [
+            ("tries", self.tries),
+            ("flips", self.flips),
+            ("tabu blocked steps", self.tabu_blocked),
+            ("decisions", self.decisions),
+            ("propagations", self.propagations),
+            ("conflicts", self.conflicts),
+            ("restarts", self.restarts),
+            ("learnt clauses", self.learnt_clauses),
+            ("deleted clauses", self.deleted_clauses),
+        ];
+
+        let lines: Vec<String> = counters
+            .iter()
+            .filter(|(_, value)| *value > 0)
+            .map(|(name, value)| format!("c {}: {}", name, value))
+            .collect();
+        write!(f, "{}", lines.join("\n"))
+    }
+}
+
+/// Returns a copy of a formula with a unit clause for every assumption
+///
+/// Used by the solvers which do not support assumptions natively.
+pub(crate) fn with_assumptions(formula: &Formula, assumptions: &[Literal]) -> Formula {
+    let mut formula = formula.clone();
+    for assumption in assumptions {
+        let mut clause = Clause::new();
+        clause.literals.push(assumption.clone());
+        formula.add_clause(clause);
+    }
+    formula
+}
+
+/// Returns the random number generator of a randomized solver, seeded from `seed` so that runs
+/// can be reproduced, or from the operating system if there is none
+///
+/// ChaCha8 is used rather than [`rand::rngs::StdRng`], whose algorithm may change between
+/// releases of `rand`, so that a seed gives the same flips with every version of the crate.
+pub(crate) fn seeded_rng(seed: Option<u64>) -> ChaCha8Rng {
+    match seed {
+        Some(seed) => ChaCha8Rng::seed_from_u64(seed),
+        None => rand::make_rng(),
+    }
+}
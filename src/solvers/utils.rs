@@ -5,12 +5,14 @@
 //! ## Contents
 //!
 //! * [`flip`] - Flips the value of a variable in an interpretation.
-use std::collections::HashMap;
+use crate::assignment::Assignment;
 
 /// Flips the value of a variable in an interpretation
 ///
+/// Takes constant time, unassigned variables stay unassigned.
+///
 /// # Arguments
-/// * `interpretation` - The interpretation to flip the value of a variable in, of type [`HashMap<i32, bool>`].
+/// * `interpretation` - The interpretation to flip the value of a variable in, of type [`Assignment`].
 /// * `variable` - The variable to flip the value of, of type [`i32`].
 ///
 /// # Returns
@@ -18,22 +20,17 @@ use std::collections::HashMap;
 ///
 /// # Examples
 /// ```
-/// use std::collections::HashMap;
+/// use sat_rs::assignment::Assignment;
 /// use sat_rs::solvers::utils::flip;
 ///
-/// let mut interpretation: HashMap<i32, bool> = HashMap::new();
-/// interpretation.insert(1, true);
+/// let mut interpretation = Assignment::new(1);
+/// interpretation.set(1, true);
 ///
 /// flip(&mut interpretation, 1);
 ///
-/// assert_eq!(interpretation.get(&1), Some(&false));
+/// assert_eq!(interpretation.get(1), Some(false));
 /// ```
-pub fn flip(interpretation: &mut HashMap<i32, bool>, variable: i32) -> &mut HashMap<i32, bool> {
-    for (key, value) in interpretation.iter_mut() {
-        if *key == variable {
-            *value = !*value;
-        }
-    }
-
-    return interpretation;
+pub fn flip(interpretation: &mut Assignment, variable: i32) -> &mut Assignment {
+    interpretation.flip(variable);
+    interpretation
 }
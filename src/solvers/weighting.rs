@@ -0,0 +1,785 @@
+//! # Clause Weighting
+//!
+//! Dynamic clause weighting local search. Every clause has a weight, which the search raises
+//! for clauses that stay unsatisfied, so that the weighted score of a variable, i.e. the weight
+//! of the clauses flipping it would satisfy minus the weight of the clauses it would falsify,
+//! guides the search out of local minima.
+//!
+//! ## Contents
+//!
+//! * [`ClauseWeights`] - Clause weights with incrementally maintained weighted scores.
+//! * [`DdfwConfig`], [`DdfwSolver`] and [`ddfw_algorithm`] - Divide and distribute fixed
+//!   weights, which moves weight from satisfied to unsatisfied clauses.
+//! * [`SapsConfig`], [`SapsSolver`] and [`saps_algorithm`] - Scaling and probabilistic
+//!   smoothing, which scales the weights of unsatisfied clauses and smooths all weights
+//!   towards their mean.
+use crate::notation::{Formula, Lit};
+use crate::solvers::localsearch::{LocalSearch, LocalSearchAlgorithm, LocalSearchSolver};
+use crate::solvers::result::SolveResult;
+use crate::solvers::solver::SolverStats;
+use rand::{Rng, RngExt};
+
+/// Weighted scores below this value are not considered improving, to absorb rounding errors
+const EPSILON: f64 = 1e-9;
+
+/// Clause weights with the weighted make and break scores of every variable
+///
+/// The scores are maintained incrementally, on top of the unweighted [`LocalSearch`] state,
+/// which must be flipped through [`ClauseWeights::flip`] to keep both in sync.
+///
+/// # Examples
+/// ```rust
+/// use sat_rs::notation::{Literal, Clause, Formula};
+/// use sat_rs::solvers::localsearch::LocalSearch;
+/// use sat_rs::solvers::weighting::ClauseWeights;
+///
+/// let mut formula = Formula::new();
+/// let mut clause = Clause::new(); // Clause: p
+/// clause.literals.push(Literal{ value: 1, negated: false});
+/// formula.add_clause(clause);
+///
+/// let mut clause = Clause::new(); // Clause: -p
+/// clause.literals.push(Literal{ value: 1, negated: true});
+/// formula.add_clause(clause);
+///
+/// // Starts with p false, so only the first clause is unsatisfied
+/// let mut state = LocalSearch::new(&formula);
+/// let mut weights = ClauseWeights::new(&state, 1.0);
+/// assert_eq!(weights.score(0), 0.0);
+///
+/// // A heavier first clause makes flipping p worthwhile
+/// weights.add(&state, 0, 2.0);
+/// assert_eq!(weights.score(0), 2.0);
+///
+/// weights.flip(&mut state, 0);
+/// assert_eq!(weights.score(0), -2.0);
+/// ```
+#[derive(Debug, Clone)]
+pub struct ClauseWeights {
+    weights: Vec<f64>,
+    /// Sum of the weights
+    total: f64,
+    make: Vec<f64>,
+    breaks: Vec<f64>,
+}
+
+impl ClauseWeights {
+    /// Creates the weights of the clauses of a state, all equal to `initial`
+    pub fn new(state: &LocalSearch, initial: f64) -> ClauseWeights {
+        let mut weights = ClauseWeights {
+            weights: vec![initial; state.num_clauses()],
+            total: initial * state.num_clauses() as f64,
+            make: vec![0.0; state.num_vars()],
+            breaks: vec![0.0; state.num_vars()],
+        };
+        weights.recompute(state);
+        weights
+    }
+
+    /// Returns the weight of a clause
+    pub fn weight(&self, clause: usize) -> f64 {
+        self.weights[clause]
+    }
+
+    /// Returns the mean weight of the clauses, `0` if there are no clauses
+    pub fn mean(&self) -> f64 {
+        if self.weights.is_empty() {
+            0.0
+        } else {
+            self.total / self.weights.len() as f64
+        }
+    }
+
+    /// Returns the weight of the unsatisfied clauses that flipping a variable would satisfy
+    pub fn make(&self, var: usize) -> f64 {
+        self.make[var]
+    }
+
+    /// Returns the weight of the satisfied clauses that flipping a variable would falsify
+    pub fn breaks(&self, var: usize) -> f64 {
+        self.breaks[var]
+    }
+
+    /// Returns the decrease in the weight of the unsatisfied clauses if a variable is flipped
+    pub fn score(&self, var: usize) -> f64 {
+        self.make[var] - self.breaks[var]
+    }
+
+    /// Sets every weight to `initial`
+    pub fn reset(&mut self, state: &LocalSearch, initial: f64) {
+        self.weights.fill(initial);
+        self.total = initial * self.weights.len() as f64;
+        self.recompute(state);
+    }
+
+    /// Adds `delta` to the weight of a clause
+    pub fn add(&mut self, state: &LocalSearch, clause: usize, delta: f64) {
+        self.weights[clause] += delta;
+        self.total += delta;
+        if state.true_count(clause) == 0 {
+            for lit in state.clause(clause) {
+                self.make[lit.var()] += delta;
+            }
+        } else if let Some(var) = state.critical_var(clause) {
+            self.breaks[var] += delta;
+        }
+    }
+
+    /// Multiplies the weight of every unsatisfied clause by `factor`
+    pub fn scale_unsat(&mut self, state: &LocalSearch, factor: f64) {
+        for &clause in state.unsat_clauses() {
+            self.add(state, clause, (factor - 1.0) * self.weights[clause]);
+        }
+    }
+
+    /// Moves every weight towards the mean weight, `w := rho * w + (1 - rho) * mean`
+    pub fn smooth(&mut self, state: &LocalSearch, rho: f64) {
+        self.smooth_towards(state, rho, self.mean());
+    }
+
+    /// Moves every weight towards a target weight, `w := rho * w + (1 - rho) * target`
+    ///
+    /// As the transformation is affine, the scores are updated from the unweighted make and break
+    /// counts without visiting the clauses.
+    pub fn smooth_towards(&mut self, state: &LocalSearch, rho: f64, target: f64) {
+        let shift = (1.0 - rho) * target;
+        for weight in &mut self.weights {
+            *weight = rho * *weight + shift;
+        }
+        self.total = rho * self.total + shift * self.weights.len() as f64;
+        for var in 0..self.make.len() {
+            self.make[var] = rho * self.make[var] + shift * state.make(var) as f64;
+            self.breaks[var] = rho * self.breaks[var] + shift * state.breaks(var) as f64;
+        }
+    }
+
+    /// Flips a variable of the state, updating the weighted scores
+    pub fn flip(&mut self, state: &mut LocalSearch, var: usize) {
+        // The literal of the variable which becomes true
+        let made_true = Lit::new(var, state.value(var));
+
+        // Clauses gaining a true literal, before the flip
+        for &clause in state.occurrences(made_true) {
+            let weight = self.weights[clause];
+            match state.true_count(clause) {
+                0 => {
+                    for lit in state.clause(clause) {
+                        self.make[lit.var()] -= weight;
+                    }
+                    self.breaks[var] += weight;
+                }
+                1 => self.breaks[state.critical_var(clause).unwrap()] -= weight,
+                _ => (),
+            }
+        }
+
+        state.flip(var);
+
+        // Clauses losing a true literal, after the flip
+        for &clause in state.occurrences(!made_true) {
+            let weight = self.weights[clause];
+            match state.true_count(clause) {
+                0 => {
+                    for lit in state.clause(clause) {
+                        self.make[lit.var()] += weight;
+                    }
+                    self.breaks[var] -= weight;
+                }
+                1 => self.breaks[state.critical_var(clause).unwrap()] += weight,
+                _ => (),
+            }
+        }
+    }
+
+    /// Computes the weighted scores of the current interpretation from scratch
+    fn recompute(&mut self, state: &LocalSearch) {
+        self.make.fill(0.0);
+        self.breaks.fill(0.0);
+        for clause in 0..self.weights.len() {
+            let weight = self.weights[clause];
+            if state.true_count(clause) == 0 {
+                for lit in state.clause(clause) {
+                    self.make[lit.var()] += weight;
+                }
+            } else if let Some(var) = state.critical_var(clause) {
+                self.breaks[var] += weight;
+            }
+        }
+    }
+}
+
+/// Returns a variable of the unsatisfied clauses with the highest weighted score, ties broken
+/// uniformly at random, along with its score
+///
+/// Only variables of unsatisfied clauses can have a positive score. A variable is visited once
+/// per call, by marking it in `seen` with a `stamp` which must differ from the previous calls.
+fn best_variable(
+    state: &LocalSearch,
+    weights: &ClauseWeights,
+    seen: &mut [u64],
+    stamp: u64,
+    rng: &mut impl Rng,
+) -> (usize, f64) {
+    let mut best = 0;
+    let mut best_score = f64::NEG_INFINITY;
+    let mut ties = 0;
+    for &clause in state.unsat_clauses() {
+        for lit in state.clause(clause) {
+            let var = lit.var();
+            if seen[var] == stamp {
+                continue;
+            }
+            seen[var] = stamp;
+
+            let score = weights.score(var);
+            if score > best_score + EPSILON {
+                best = var;
+                best_score = score;
+                ties = 1;
+            } else if score >= best_score - EPSILON {
+                ties += 1;
+                if rng.random_range(0..ties) == 0 {
+                    best = var;
+                }
+            }
+        }
+    }
+    (best, best_score)
+}
+
+/// Parameters of the [`ddfw_algorithm`]
+///
+/// # Examples
+/// ```rust
+/// use sat_rs::solvers::weighting::DdfwConfig;
+///
+/// let config = DdfwConfig::default();
+/// assert_eq!(config.initial_weight, 8.0);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DdfwConfig {
+    /// Weight of every clause at the start of a try
+    pub initial_weight: f64,
+    /// Probability of a flip which does not change the weighted score when there is no
+    /// improving flip
+    pub sideways_probability: f64,
+}
+
+impl DdfwConfig {
+    /// Checks that the initial weight is finite and at least `2`, so that a donor never gives
+    /// more weight than it has, and that the sideways probability is a probability
+    ///
+    /// # Returns
+    /// * `Result<(), String>` - `Ok` if the configuration is valid, otherwise why it is not
+    ///
+    /// # Examples
+    /// ```rust
+    /// use sat_rs::solvers::weighting::DdfwConfig;
+    ///
+    /// assert!(DdfwConfig::default().validate().is_ok());
+    ///
+    /// let config = DdfwConfig { initial_weight: 1.0, ..Default::default() };
+    /// assert!(config.validate().is_err());
+    /// ```
+    pub fn validate(&self) -> Result<(), String> {
+        if !(self.initial_weight >= 2.0 && self.initial_weight.is_finite()) {
+            Err(format!(
+                "Invalid DDFW initial weight: {}",
+                self.initial_weight
+            ))
+        } else if !(0.0..=1.0).contains(&self.sideways_probability) {
+            Err(format!(
+                "Invalid DDFW sideways probability: {}",
+                self.sideways_probability
+            ))
+        } else {
+            Ok(())
+        }
+    }
+}
+
+impl Default for DdfwConfig {
+    fn default() -> Self {
+        DdfwConfig {
+            initial_weight: 8.0,
+            sideways_probability: 0.15,
+        }
+    }
+}
+
+/// Moves weight from satisfied clauses to every unsatisfied clause
+///
+/// Each unsatisfied clause takes weight from the heaviest satisfied clause sharing one of its
+/// literals, or from a random satisfied clause if that one is lighter than the initial weight.
+/// Clauses heavier than the initial weight give `2`, the others give `1`.
+///
+/// # Arguments
+/// * `state` - The interpretation, whose unsatisfied clauses receive weight
+/// * `weights` - The [`ClauseWeights`] of the clauses of `state`
+/// * `initial_weight` - The weight of every clause at the start of a try
+/// * `rng` - The random number generator picking a donor when no neighbour can give
+pub fn distribute(
+    state: &LocalSearch,
+    weights: &mut ClauseWeights,
+    initial_weight: f64,
+    rng: &mut impl Rng,
+) {
+    let unsat = state.unsat_clauses().to_vec();
+    for clause in unsat {
+        let mut donor: Option<usize> = None;
+        for lit in state.clause(clause) {
+            for &neighbour in state.occurrences(*lit) {
+                if state.true_count(neighbour) > 0
+                    && donor.is_none_or(|donor| weights.weight(neighbour) > weights.weight(donor))
+                {
+                    donor = Some(neighbour);
+                }
+            }
+        }
+
+        if donor.is_none_or(|donor| weights.weight(donor) < initial_weight) {
+            donor = (0..state.num_clauses())
+                .map(|_| rng.random_range(0..state.num_clauses()))
+                .find(|candidate| {
+                    state.true_count(*candidate) > 0 && weights.weight(*candidate) >= initial_weight
+                });
+        }
+
+        if let Some(donor) = donor {
+            let amount = if weights.weight(donor) > initial_weight {
+                2.0
+            } else {
+                1.0
+            };
+            weights.add(state, donor, -amount);
+            weights.add(state, clause, amount);
+        }
+    }
+}
+
+/// [`Solver`](crate::solvers::solver::Solver) running the [`ddfw_algorithm`]
+///
+/// # Examples
+/// ```rust
+/// use sat_rs::notation::{Literal, Clause};
+/// use sat_rs::solvers::solver::Solver;
+/// use sat_rs::solvers::weighting::{DdfwConfig, DdfwSolver};
+///
+/// let mut clause = Clause::new(); // Clause: p v q
+/// clause.literals.push(Literal{ value: 1, negated: false});
+/// clause.literals.push(Literal{ value: 2, negated: false});
+///
+/// let mut solver = DdfwSolver::new(10, 10, DdfwConfig::default());
+/// solver.add_clause(clause);
+/// assert!(solver.solve().is_sat());
+/// ```
+pub type DdfwSolver = LocalSearchSolver<Ddfw>;
+
+/// The search of the [`ddfw_algorithm`], run by a [`DdfwSolver`]
+#[derive(Debug, Clone)]
+pub struct Ddfw {
+    max_tries: u32,
+    max_flips: u32,
+    config: DdfwConfig,
+}
+
+impl DdfwSolver {
+    /// Creates a new [`DdfwSolver`]
+    ///
+    /// # Arguments
+    /// * `max_tries` - The number of times to try to find a satisfying interpretation
+    /// * `max_flips` - The number of times to flip the value of a variable in an interpretation
+    /// * `config` - A [`DdfwConfig`] struct
+    pub fn new(max_tries: u32, max_flips: u32, config: DdfwConfig) -> DdfwSolver {
+        LocalSearchSolver::from_algorithm(Ddfw {
+            max_tries,
+            max_flips,
+            config,
+        })
+    }
+}
+
+impl LocalSearchAlgorithm for Ddfw {
+    fn search(
+        &self,
+        formula: &Formula,
+        rng: &mut impl Rng,
+        stats: &mut SolverStats,
+    ) -> SolveResult {
+        let mut state = LocalSearch::new(formula);
+        if state.has_empty_clause() {
+            return SolveResult::Unsat;
+        }
+        let mut weights = ClauseWeights::new(&state, self.config.initial_weight);
+        let mut seen = vec![0; state.num_vars()];
+        let mut steps = 0;
+
+        for _ in 0..self.max_tries {
+            stats.tries += 1;
+
+            // Create a random interpretation with uniform weights
+            state.randomize(rng);
+            weights.reset(&state, self.config.initial_weight);
+
+            for _ in 0..self.max_flips {
+                // Check if the interpretation satisfies the formula
+                if state.is_satisfied() {
+                    break;
+                }
+
+                // Flip the best variable if it improves, or leaves unchanged with some
+                // probability, the weighted score, and move weight to the unsatisfied clauses
+                // otherwise
+                steps += 1;
+                let (var, score) = best_variable(&state, &weights, &mut seen, steps, rng);
+                if score > EPSILON
+                    || (score > -EPSILON && rng.random_bool(self.config.sideways_probability))
+                {
+                    weights.flip(&mut state, var);
+                    stats.flips += 1;
+                } else {
+                    distribute(&state, &mut weights, self.config.initial_weight, rng);
+                }
+            }
+
+            if state.is_satisfied() {
+                return SolveResult::Sat(state.model(&formula.vars));
+            }
+        }
+
+        SolveResult::Unknown(format!(
+            "no model found in {} tries of {} flips",
+            self.max_tries, self.max_flips
+        ))
+    }
+}
+
+/// DDFW Algorithm for evaluation of propostional formulas
+///
+/// Divide and Distribute Fixed Weights gives every clause the same initial weight and greedily
+/// flips the variable with the highest weighted score. When no flip improves the weighted score,
+/// a flip leaving it unchanged is made with a small probability. Otherwise every unsatisfied
+/// clause takes some weight from a satisfied neighbour, i.e. a clause sharing one of its
+/// literals, so the total weight stays fixed and the unsatisfied clauses eventually become worth
+/// satisfying. If all tries are exhausted, the result is unknown.
+///
+/// Every step counts towards `max_flips`, whether it flips a variable or moves weights.
+///
+/// # Pseudocode
+/// ```text
+/// procedure DDFW(Set of Clauses S)
+///
+/// input: A set of clauses S
+/// output: a model of S, or unknown
+/// parameters: max_tries, max_flips, initial_weight, sideways_probability
+///
+/// begin
+///   repeat max_tries times
+///     create a random interpretation I
+///     set the weight of every clause to initial_weight
+///     repeat max_flips times
+///       if I satisfies S
+///         then return I
+///       p := variable of an unsatisfied clause with the highest weighted score
+///       if score(p) > 0, or score(p) = 0 with probability sideways_probability
+///         then flip the value of p in I
+///       else
+///         for each unsatisfied clause C
+///           D := heaviest satisfied clause sharing a literal with C
+///           if weight(D) < initial_weight
+///             then D := a random satisfied clause with weight(D) >= initial_weight
+///           move 2 from D to C if weight(D) > initial_weight, else move 1
+///   return unknown
+/// end
+/// ```
+///
+/// # Arguments
+/// * `formula` - A [`Formula`] struct
+/// * `max_tries` - The number of times to try to find a satisfying interpretation
+/// * `max_flips` - The number of steps in an interpretation
+/// * `config` - A [`DdfwConfig`] struct
+/// * `seed` - The seed of the random number generator, `None` to seed from the operating
+///   system
+///
+/// # Returns
+/// * [`SolveResult`] - [`SolveResult::Sat`] with the model, or [`SolveResult::Unknown`]
+///
+/// # Examples
+/// ```
+/// use sat_rs::notation::{Literal, Clause, Formula};
+/// use sat_rs::solvers::weighting::{self, DdfwConfig};
+///
+/// let mut clause = Clause::new(); // Clause: p v q
+/// clause.literals.push(Literal{ value: 1, negated: false});
+/// clause.literals.push(Literal{ value: 2, negated: false});
+///
+/// let mut formula = Formula::new();
+/// formula.add_clause(clause);
+///
+/// let result = weighting::ddfw_algorithm(&mut formula, 10, 10, DdfwConfig::default(), Some(1));
+/// assert_eq!(result.is_sat(), true);
+/// ```
+pub fn ddfw_algorithm(
+    formula: &mut Formula,
+    max_tries: u32,
+    max_flips: u32,
+    config: DdfwConfig,
+    seed: Option<u64>,
+) -> SolveResult {
+    DdfwSolver::new(max_tries, max_flips, config)
+        .with_seed(seed)
+        .search(formula)
+}
+
+/// Parameters of the [`saps_algorithm`]
+///
+/// # Examples
+/// ```rust
+/// use sat_rs::solvers::weighting::SapsConfig;
+///
+/// let config = SapsConfig {
+///     alpha: 1.2,
+///     ..Default::default()
+/// };
+/// assert_eq!(config.rho, 0.8);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SapsConfig {
+    /// Factor by which the weights of the unsatisfied clauses are scaled in a local minimum
+    pub alpha: f64,
+    /// Share of its own weight a clause keeps when weights are smoothed
+    pub rho: f64,
+    /// Probability of smoothing the weights after scaling them
+    pub smooth_probability: f64,
+    /// Probability of flipping a random variable in a local minimum
+    pub walk_probability: f64,
+}
+
+impl SapsConfig {
+    /// Checks that `alpha` is finite and greater than `1`, so that scaling makes the unsatisfied
+    /// clauses heavier, and that `rho` and the probabilities are between `0` and `1`
+    ///
+    /// # Returns
+    /// * `Result<(), String>` - `Ok` if the configuration is valid, otherwise why it is not
+    ///
+    /// # Examples
+    /// ```rust
+    /// use sat_rs::solvers::weighting::SapsConfig;
+    ///
+    /// assert!(SapsConfig::default().validate().is_ok());
+    ///
+    /// let config = SapsConfig { alpha: 0.5, ..Default::default() };
+    /// assert!(config.validate().is_err());
+    /// ```
+    pub fn validate(&self) -> Result<(), String> {
+        let probability = |value: f64| (0.0..=1.0).contains(&value);
+        if !(self.alpha > 1.0 && self.alpha.is_finite()) {
+            Err(format!("Invalid SAPS alpha: {}", self.alpha))
+        } else if !probability(self.rho) {
+            Err(format!("Invalid SAPS rho: {}", self.rho))
+        } else if !probability(self.smooth_probability) {
+            Err(format!(
+                "Invalid SAPS smooth probability: {}",
+                self.smooth_probability
+            ))
+        } else if !probability(self.walk_probability) {
+            Err(format!(
+                "Invalid SAPS walk probability: {}",
+                self.walk_probability
+            ))
+        } else {
+            Ok(())
+        }
+    }
+}
+
+impl Default for SapsConfig {
+    fn default() -> Self {
+        SapsConfig {
+            alpha: 1.3,
+            rho: 0.8,
+            smooth_probability: 0.05,
+            walk_probability: 0.01,
+        }
+    }
+}
+
+/// [`Solver`](crate::solvers::solver::Solver) running the [`saps_algorithm`]
+///
+/// # Examples
+/// ```rust
+/// use sat_rs::notation::{Literal, Clause};
+/// use sat_rs::solvers::solver::Solver;
+/// use sat_rs::solvers::weighting::{SapsConfig, SapsSolver};
+///
+/// let mut clause = Clause::new(); // Clause: p v q
+/// clause.literals.push(Literal{ value: 1, negated: false});
+/// clause.literals.push(Literal{ value: 2, negated: false});
+///
+/// let mut solver = SapsSolver::new(10, 10, SapsConfig::default());
+/// solver.add_clause(clause);
+/// assert!(solver.solve().is_sat());
+/// ```
+pub type SapsSolver = LocalSearchSolver<Saps>;
+
+/// The search of the [`saps_algorithm`], run by a [`SapsSolver`]
+#[derive(Debug, Clone)]
+pub struct Saps {
+    max_tries: u32,
+    max_flips: u32,
+    config: SapsConfig,
+}
+
+impl SapsSolver {
+    /// Creates a new [`SapsSolver`]
+    ///
+    /// # Arguments
+    /// * `max_tries` - The number of times to try to find a satisfying interpretation
+    /// * `max_flips` - The number of times to flip the value of a variable in an interpretation
+    /// * `config` - A [`SapsConfig`] struct
+    pub fn new(max_tries: u32, max_flips: u32, config: SapsConfig) -> SapsSolver {
+        LocalSearchSolver::from_algorithm(Saps {
+            max_tries,
+            max_flips,
+            config,
+        })
+    }
+}
+
+impl LocalSearchAlgorithm for Saps {
+    fn search(
+        &self,
+        formula: &Formula,
+        rng: &mut impl Rng,
+        stats: &mut SolverStats,
+    ) -> SolveResult {
+        let mut state = LocalSearch::new(formula);
+        if state.has_empty_clause() {
+            return SolveResult::Unsat;
+        }
+        let mut weights = ClauseWeights::new(&state, 1.0);
+        let mut seen = vec![0; state.num_vars()];
+        let mut steps = 0;
+
+        for _ in 0..self.max_tries {
+            stats.tries += 1;
+
+            // Create a random interpretation with uniform weights
+            state.randomize(rng);
+            weights.reset(&state, 1.0);
+
+            for _ in 0..self.max_flips {
+                // Check if the interpretation satisfies the formula
+                if state.is_satisfied() {
+                    break;
+                }
+
+                // Flip the best variable if it improves the weighted score
+                steps += 1;
+                let (var, score) = best_variable(&state, &weights, &mut seen, steps, rng);
+                if score > EPSILON {
+                    weights.flip(&mut state, var);
+                    stats.flips += 1;
+                    continue;
+                }
+
+                // In a local minimum, possibly flip a random variable, then scale the weights of
+                // the unsatisfied clauses and possibly smooth all weights
+                if rng.random_bool(self.config.walk_probability) {
+                    let var = state.vars()[rng.random_range(0..state.vars().len())];
+                    weights.flip(&mut state, var);
+                    stats.flips += 1;
+                }
+                weights.scale_unsat(&state, self.config.alpha);
+                if rng.random_bool(self.config.smooth_probability) {
+                    weights.smooth(&state, self.config.rho);
+                }
+            }
+
+            if state.is_satisfied() {
+                return SolveResult::Sat(state.model(&formula.vars));
+            }
+        }
+
+        SolveResult::Unknown(format!(
+            "no model found in {} tries of {} flips",
+            self.max_tries, self.max_flips
+        ))
+    }
+}
+
+/// SAPS Algorithm for evaluation of propostional formulas
+///
+/// Scaling And Probabilistic Smoothing starts with every clause weight equal to `1` and greedily
+/// flips the variable with the highest weighted score as long as it improves. In a local minimum,
+/// a random variable is flipped with a small probability, then the weights of the unsatisfied
+/// clauses are multiplied by `alpha`. With a small probability, all weights are then smoothed
+/// towards their mean, so that the search forgets old local minima. If all tries are exhausted,
+/// the result is unknown.
+///
+/// Every step counts towards `max_flips`, whether it flips a variable or updates weights.
+///
+/// # Pseudocode
+/// ```text
+/// procedure SAPS(Set of Clauses S)
+///
+/// input: A set of clauses S
+/// output: a model of S, or unknown
+/// parameters: max_tries, max_flips, alpha, rho, smooth_probability, walk_probability
+///
+/// begin
+///   repeat max_tries times
+///     create a random interpretation I
+///     set the weight of every clause to 1
+///     repeat max_flips times
+///       if I satisfies S
+///         then return I
+///       p := variable of an unsatisfied clause with the highest weighted score
+///       if score(p) > 0
+///         then flip the value of p in I
+///       else
+///         with probability walk_probability flip a random variable in I
+///         multiply the weight of every unsatisfied clause by alpha
+///         with probability smooth_probability
+///           w := rho * w + (1 - rho) * mean weight, for every weight w
+///   return unknown
+/// end
+/// ```
+///
+/// # Arguments
+/// * `formula` - A [`Formula`] struct
+/// * `max_tries` - The number of times to try to find a satisfying interpretation
+/// * `max_flips` - The number of steps in an interpretation
+/// * `config` - A [`SapsConfig`] struct
+/// * `seed` - The seed of the random number generator, `None` to seed from the operating
+///   system
+///
+/// # Returns
+/// * [`SolveResult`] - [`SolveResult::Sat`] with the model, or [`SolveResult::Unknown`]
+///
+/// # Examples
+/// ```
+/// use sat_rs::notation::{Literal, Clause, Formula};
+/// use sat_rs::solvers::weighting::{self, SapsConfig};
+///
+/// let mut clause = Clause::new(); // Clause: p v q
+/// clause.literals.push(Literal{ value: 1, negated: false});
+/// clause.literals.push(Literal{ value: 2, negated: false});
+///
+/// let mut formula = Formula::new();
+/// formula.add_clause(clause);
+///
+/// let result = weighting::saps_algorithm(&mut formula, 10, 10, SapsConfig::default(), Some(1));
+/// assert_eq!(result.is_sat(), true);
+/// ```
+pub fn saps_algorithm(
+    formula: &mut Formula,
+    max_tries: u32,
+    max_flips: u32,
+    config: SapsConfig,
+    seed: Option<u64>,
+) -> SolveResult {
+    SapsSolver::new(max_tries, max_flips, config)
+        .with_seed(seed)
+        .search(formula)
+}
@@ -1,41 +1,271 @@
-use crate::notation::{Clause, Formula};
-use crate::solvers::utils::flip;
-use rand::prelude::IndexedRandom;
-use rand::RngExt;
-use std::collections::HashMap;
+use crate::notation::Formula;
+use crate::solvers::localsearch::{LocalSearch, LocalSearchAlgorithm, LocalSearchSolver};
+use crate::solvers::result::SolveResult;
+use crate::solvers::solver::SolverStats;
+use rand::{Rng, RngExt};
+use std::str::FromStr;
+
+/// Variable selection rule of the [`wsat_algorithm`]
+///
+/// Both rules flip a variable of a random unsatisfied clause, they differ in how the variable is
+/// chosen within the clause.
+///
+/// # Examples
+/// ```rust
+/// use sat_rs::solvers::wsat::WsatMode;
+///
+/// let mode: WsatMode = "skc:0.3".parse().unwrap();
+/// assert_eq!(mode, WsatMode::Skc { noise: 0.3 });
+///
+/// let mode: WsatMode = "random-walk".parse().unwrap();
+/// assert_eq!(mode, WsatMode::RandomWalk);
+///
+/// assert!("skc:1.5".parse::<WsatMode>().is_err());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum WsatMode {
+    /// The WalkSAT/SKC rule: flip a variable with a break count of zero if there is one,
+    /// otherwise flip a random variable with probability `noise` and a variable with the minimum
+    /// break count otherwise. The noise is `0.5` by default.
+    Skc { noise: f64 },
+    /// Flip a random variable of the clause
+    RandomWalk,
+}
+
+impl Default for WsatMode {
+    fn default() -> Self {
+        WsatMode::Skc { noise: 0.5 }
+    }
+}
+
+impl FromStr for WsatMode {
+    type Err = String;
+
+    fn from_str(spec: &str) -> Result<WsatMode, String> {
+        match spec.split_once(':') {
+            None if spec == "skc" => Ok(WsatMode::default()),
+            None if spec == "random-walk" => Ok(WsatMode::RandomWalk),
+            Some(("skc", noise)) => match noise.parse::<f64>() {
+                Ok(noise) if (0.0..=1.0).contains(&noise) => Ok(WsatMode::Skc { noise }),
+                _ => Err(format!("Invalid WalkSAT noise: {}", noise)),
+            },
+            _ => Err(format!("Unknown WalkSAT mode: {}", spec)),
+        }
+    }
+}
+
+/// Selects the variable to flip among the candidate variables of an unsatisfied clause with the
+/// WalkSAT/SKC rule
+///
+/// Ties between variables with the same break count are broken uniformly at random.
+///
+/// # Arguments
+/// * `state` - The [`LocalSearch`] state
+/// * `candidates` - The variables of the clause which may be flipped, at least one
+/// * `noise` - The probability of a random candidate when no candidate has a break count of 0
+/// * `rng` - The random number generator
+///
+/// # Returns
+/// * `usize` - The variable to flip
+pub fn select_skc(
+    state: &LocalSearch,
+    candidates: &[usize],
+    noise: f64,
+    rng: &mut impl Rng,
+) -> usize {
+    let mut best = Vec::with_capacity(candidates.len());
+    let mut min_breaks = u32::MAX;
+    for &var in candidates {
+        let breaks = state.breaks(var);
+        if breaks < min_breaks {
+            min_breaks = breaks;
+            best.clear();
+        }
+        if breaks == min_breaks {
+            best.push(var);
+        }
+    }
+
+    // A freebie move never makes another clause unsatisfied, so it is taken regardless of noise
+    if min_breaks > 0 && rng.random_bool(noise) {
+        candidates[rng.random_range(0..candidates.len())]
+    } else {
+        best[rng.random_range(0..best.len())]
+    }
+}
+
+/// [`Solver`](crate::solvers::solver::Solver) running the [`wsat_algorithm`]
+///
+/// # Examples
+/// ```rust
+/// use sat_rs::notation::{Literal, Clause};
+/// use sat_rs::solvers::solver::Solver;
+/// use sat_rs::solvers::wsat::{WsatMode, WsatSolver};
+///
+/// let mut clause = Clause::new(); // Clause: p v q
+/// clause.literals.push(Literal{ value: 1, negated: false});
+/// clause.literals.push(Literal{ value: 2, negated: false});
+///
+/// let mut solver = WsatSolver::new(10, 10, WsatMode::default(), 0);
+/// solver.add_clause(clause);
+/// assert!(solver.solve().is_sat());
+/// ```
+pub type WsatSolver = LocalSearchSolver<Wsat>;
+
+/// The search of the [`wsat_algorithm`], run by a [`WsatSolver`]
+#[derive(Debug, Clone)]
+pub struct Wsat {
+    max_tries: u32,
+    max_flips: u32,
+    mode: WsatMode,
+    tabu_tenure: u64,
+}
+
+impl WsatSolver {
+    /// Creates a new [`WsatSolver`]
+    ///
+    /// # Arguments
+    /// * `max_tries` - The number of times to try to find a satisfying interpretation
+    /// * `max_flips` - The number of times to flip the value of a variable in an interpretation
+    /// * `mode` - The [`WsatMode`] selecting the variable to flip in an unsatisfied clause
+    /// * `tabu_tenure` - The number of steps during which a flipped variable may not be flipped
+    ///   again, unless flipping it leads to the fewest unsatisfied clauses of the try, `0` to
+    ///   disable the tabu list
+    pub fn new(max_tries: u32, max_flips: u32, mode: WsatMode, tabu_tenure: u64) -> WsatSolver {
+        LocalSearchSolver::from_algorithm(Wsat {
+            max_tries,
+            max_flips,
+            mode,
+            tabu_tenure,
+        })
+    }
+}
+
+impl LocalSearchAlgorithm for Wsat {
+    fn search(
+        &self,
+        formula: &Formula,
+        rng: &mut impl Rng,
+        stats: &mut SolverStats,
+    ) -> SolveResult {
+        let mut state = LocalSearch::new(formula);
+        if state.has_empty_clause() {
+            return SolveResult::Unsat;
+        }
+        let mut candidates = Vec::new();
+
+        for _ in 0..self.max_tries {
+            stats.tries += 1;
+
+            // Create a random interpretation
+            state.randomize(rng);
+            let mut best_unsat = state.num_unsat();
+
+            for _ in 0..self.max_flips {
+                // Check if the interpretation satisfies the formula
+                if state.is_satisfied() {
+                    break;
+                }
+
+                // Randomly select a clause that is not satisfied by the interpretation
+                let unsat = state.unsat_clauses();
+                let clause = state.clause(unsat[rng.random_range(0..unsat.len())]);
+
+                // Leave out the tabu variables, unless flipping them leads to a new best. The step
+                // counts as blocked if a variable left out breaks fewer clauses than every
+                // candidate, or if no candidate is left
+                candidates.clear();
+                let mut min_tabu_breaks = u32::MAX;
+                let mut min_breaks = u32::MAX;
+                for lit in clause {
+                    let var = lit.var();
+                    let unsat_after = state.num_unsat() as i64 - state.score(var);
+                    if state.is_tabu(var, self.tabu_tenure) && unsat_after >= best_unsat as i64 {
+                        min_tabu_breaks = min_tabu_breaks.min(state.breaks(var));
+                    } else {
+                        min_breaks = min_breaks.min(state.breaks(var));
+                        candidates.push(var);
+                    }
+                }
+                if min_tabu_breaks < min_breaks {
+                    stats.tabu_blocked += 1;
+                }
+                if candidates.is_empty() {
+                    state.skip();
+                    continue;
+                }
+
+                // Select a variable from the clause
+                let var = match self.mode {
+                    WsatMode::Skc { noise } => select_skc(&state, &candidates, noise, rng),
+                    WsatMode::RandomWalk => candidates[rng.random_range(0..candidates.len())],
+                };
+
+                state.flip(var);
+                stats.flips += 1;
+                best_unsat = best_unsat.min(state.num_unsat());
+            }
+
+            if state.is_satisfied() {
+                return SolveResult::Sat(state.model(&formula.vars));
+            }
+        }
+
+        SolveResult::Unknown(format!(
+            "no model found in {} tries of {} flips",
+            self.max_tries, self.max_flips
+        ))
+    }
+}
 
 /// WSAT Algorithm for evaluation of propostional formulas
 ///
 /// This algorithm generates random interpretations and checks if the formula is satisfied by the
-/// interpretation. If the formula is satisfied, the algorithm returns true. If the formula is not
-/// satisfied, the algorithm selects a random unsatisfied clause and a random variable from the
-/// clause and flips the value of the variable in the interpretation. The algorithm then checks if
-/// the formula is satisfied by the new interpretation. If the formula is satisfied, the algorithm
-/// returns true. If the formula is not satisfied, the algorithm repeats the process until the
-/// formula is satisfied or the maximum number of flips is reached.
+/// interpretation. If the formula is satisfied, the algorithm returns the interpretation as a
+/// model. If the formula is not satisfied, the algorithm selects a random unsatisfied clause and a
+/// variable from the clause, chosen by the [`WsatMode`], and flips the value of the variable in
+/// the interpretation. The algorithm then checks if the formula is satisfied by the new
+/// interpretation. If the formula is satisfied, the algorithm returns the model. If the formula
+/// is not satisfied, the algorithm repeats the process until the formula is satisfied or the
+/// maximum number of flips is reached. If all tries are exhausted, the result is unknown.
+///
+/// With a `tabu_tenure`, a flipped variable is tabu for that many steps: it is not considered,
+/// unless flipping it would leave fewer unsatisfied clauses than ever before in the try. When
+/// every variable of the clause is tabu, nothing is flipped during the step.
+///
+/// The unsatisfied clauses are maintained incrementally (see [`crate::solvers::localsearch`]),
+/// so a flip only visits the clauses containing the flipped variable. A formula containing the
+/// empty clause is reported as unsatisfiable.
 ///
 /// # Pseudocode
 /// ```text
 /// procedure WSAT(Set of Clauses S)
 ///
 /// input: A set of clauses S
-/// output: true if S is satisfiable, false otherwise
-/// parameters: max_tries, max_flips
+/// output: a model of S, or unknown
+/// parameters: max_tries, max_flips, noise
 ///
 /// begin
 ///   repeat max_tries times
 ///     create a random interpretation I
 ///     if I satisfies S
-///       then return true
+///       then return I
 ///     else
 ///       repeat max_flips times
 ///         randomly select an unsatisfied clause C
-///         randomly select a variable p from C
+///         leave out the tabu variables of C
+///         if some variable of C has a break count of 0
+///           then p := a variable of C with a break count of 0
+///         else with probability noise
+///           p := a random variable of C
+///         else
+///           p := a variable of C with the minimum break count
 ///         flip the value of p in I
 ///         if I satisfies S
-///           then return true
+///           then return I
 ///         else
 ///          continue
+///   return unknown
 /// end
 /// ```
 ///
@@ -43,14 +273,20 @@ use std::collections::HashMap;
 /// * `formula` - A [`Formula`] struct
 /// * `max_tries` - The number of times to try to find a satisfying interpretation
 /// * `max_flips` - The number of times to flip the value of a variable in an interpretation
+/// * `mode` - The [`WsatMode`], [`WsatMode::RandomWalk`] always flips a random variable of the
+///   clause
+/// * `tabu_tenure` - The number of steps during which a flipped variable is tabu, `0` to disable
+///   the tabu list
+/// * `seed` - The seed of the random number generator, `None` to seed from the operating
+///   system
 ///
 /// # Returns
-/// * `bool` - The value of the formula
+/// * [`SolveResult`] - [`SolveResult::Sat`] with the model, or [`SolveResult::Unknown`]
 ///
 /// # Examples
 /// ```
 /// use sat_rs::notation::{Literal, Clause, Formula};
-/// use sat_rs::solvers::wsat;
+/// use sat_rs::solvers::wsat::{self, WsatMode};
 ///
 /// let mut clause = Clause::new(); // Clause: p v q
 /// let p = Literal{ value: 1, negated: false};
@@ -58,63 +294,22 @@ use std::collections::HashMap;
 /// clause.literals.push(p);
 /// clause.literals.push(q);
 ///
-/// let mut formula = Formula {
-///    clauses: vec![clause],
-///    literals: vec![Literal{ value: 1, negated: false}, Literal{ value: 2, negated: false}],
-///    vars: vec![1, 2],
-///    num_clauses: 1,
-///    num_vars: 2,
-/// };
+/// let mut formula = Formula::new();
+/// formula.add_clause(clause);
 ///
-/// let result = wsat::wsat_algorithm(&mut formula, 10, 10);
-/// assert_eq!(result, true);
+/// let mode = WsatMode::Skc { noise: 0.5 };
+/// let result = wsat::wsat_algorithm(&mut formula, 10, 10, mode, 0, Some(1));
+/// assert_eq!(result.is_sat(), true);
 /// ```
-pub fn wsat_algorithm(formula: &mut Formula, max_tries: u32, max_flips: u32) -> bool {
-    let mut value: bool = false;
-
-    for _ in 0..max_tries {
-        // Create a random interpretation
-        let mut interpretation: HashMap<i32, bool> = HashMap::new();
-
-        for var in &formula.vars {
-            interpretation.insert(*var, rand::random::<bool>());
-        }
-
-        // Check if the interpretation satisfies the formula
-        if formula.evaluate(&interpretation) {
-            value = true;
-            println!(
-                "Formula is satisfied by the interpretation: {:?}",
-                interpretation
-            );
-            return value;
-        } else {
-            for _ in 0..max_flips {
-                // Randomly select a clause that is not satisfied by the interpretation
-                let unsatisfied_clauses: Vec<Clause> = formula.get_unsatisfied_clauses();
-                let clause = unsatisfied_clauses.choose(&mut rand::rng());
-
-                // Randomly select a variable from the clause
-                let clausal_variables: Vec<i32> = formula.get_clausal_variables(&clause.unwrap());
-                let variable =
-                    clausal_variables[rand::rng().random_range(0..clausal_variables.len())];
-
-                interpretation = flip(&mut interpretation, variable).clone();
-
-                // Check if the interpretation satisfies the formula
-                if formula.evaluate(&interpretation) {
-                    value = true;
-                    println!(
-                        "Formula is satisfied by the interpretation: {:?}",
-                        interpretation
-                    );
-                    return value;
-                } else {
-                    continue;
-                }
-            }
-        }
-    }
-
-    return value;
+pub fn wsat_algorithm(
+    formula: &mut Formula,
+    max_tries: u32,
+    max_flips: u32,
+    mode: WsatMode,
+    tabu_tenure: u64,
+    seed: Option<u64>,
+) -> SolveResult {
+    WsatSolver::new(max_tries, max_flips, mode, tabu_tenure)
+        .with_seed(seed)
+        .search(formula)
 }
@@ -8,9 +8,8 @@ pub fn read_file(path: &std::path::PathBuf) -> String {
         Ok(file) => file,
     };
 
-    match file.read_to_string(&mut buffer) {
-        Err(why) => panic!("couldn't read {}: {}", path.display(), why),
-        Ok(_) => (),
+    if let Err(why) = file.read_to_string(&mut buffer) {
+        panic!("couldn't read {}: {}", path.display(), why)
     }
 
     buffer
@@ -0,0 +1,261 @@
+//! # Model Verification
+//!
+//! Checks models against the clauses of a formula as they were parsed, independently of the
+//! solvers and of [`Formula::evaluate`]: the model is read as signed DIMACS literals, and every
+//! clause must contain one of them. Solutions printed by any SAT solver in the SAT competition
+//! output format are checked the same way.
+//!
+//! ## Contents
+//!
+//! * [`FalsifiedClause`] - A clause of the formula which a model falsifies.
+//! * [`verify_model`] - Checks that a model satisfies every clause of a formula.
+//! * [`parse_solution`] - Reads the model of a solution in the SAT competition output format.
+//! * [`SolutionReport`] - What is wrong with a solution.
+//! * [`verify_solution`] - Checks the model of a solution against a formula.
+use crate::notation::{Clause, Formula};
+use crate::solvers::result::Model;
+use std::fmt;
+
+/// A clause of a formula which a model falsifies
+///
+/// # Examples
+/// ```rust
+/// use sat_rs::verify::FalsifiedClause;
+///
+/// let falsified = FalsifiedClause { index: 2, literals: vec![1, -3] };
+/// assert_eq!(falsified.to_string(), "clause 3 is falsified: 1 -3 0");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FalsifiedClause {
+    /// Index of the clause in [`Formula::clauses`], the clause being numbered `index + 1` in
+    /// the CNF file
+    pub index: usize,
+    /// Literals of the clause, as signed DIMACS literals
+    pub literals: Vec<i32>,
+}
+
+/// Formats the clause by its number in the CNF file and its DIMACS line
+impl fmt::Display for FalsifiedClause {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "clause {} is falsified:", self.index + 1)?;
+        for lit in &self.literals {
+            write!(f, " {}", lit)?;
+        }
+        write!(f, " 0")
+    }
+}
+
+/// Checks that a model satisfies every clause of a formula
+///
+/// Variables missing from the model falsify their literals.
+///
+/// # Arguments
+/// * `formula` - The [`Formula`] as parsed, before any solver ran on it
+/// * `model` - The [`Model`] returned by a solver
+///
+/// # Returns
+/// * `Result<(), FalsifiedClause>` - `Ok` if every clause is satisfied, otherwise the first
+///   falsified clause
+///
+/// # Examples
+/// ```rust
+/// use sat_rs::cnfparser;
+/// use sat_rs::solvers::result::Model;
+/// use sat_rs::verify::verify_model;
+/// use std::collections::HashMap;
+///
+/// let formula = cnfparser::parse_cnf("p cnf 2 2\n1 2 0\n-1 0\n").unwrap();
+///
+/// let model = Model::from(HashMap::from([(1, false), (2, true)]));
+/// assert!(verify_model(&formula, &model).is_ok());
+///
+/// let model = Model::from(HashMap::from([(1, true), (2, true)]));
+/// assert_eq!(verify_model(&formula, &model).unwrap_err().index, 1);
+/// ```
+pub fn verify_model(formula: &Formula, model: &Model) -> Result<(), FalsifiedClause> {
+    let values = values(&model.literals());
+    match formula
+        .clauses
+        .iter()
+        .position(|clause| !satisfies(&values, clause))
+    {
+        Some(index) => Err(falsified(formula, index)),
+        None => Ok(()),
+    }
+}
+
+/// Reads the model of a solution in the SAT competition output format
+///
+/// The solution must have the status line `s SATISFIABLE`, and the model is given by the value
+/// lines `v ...`, which may be split over several lines and end with `0`. Comment lines `c ...`
+/// are ignored.
+///
+/// # Arguments
+/// * `text` - The output of a SAT solver
+///
+/// # Returns
+/// * [`Vec<i32>`] - The literals of the value lines, or why the solution could not be read
+///
+/// # Examples
+/// ```rust
+/// use sat_rs::verify::parse_solution;
+///
+/// let literals = parse_solution("c found\ns SATISFIABLE\nv 1 -2\nv 3 0\n").unwrap();
+/// assert_eq!(literals, vec![1, -2, 3]);
+///
+/// assert!(parse_solution("s UNSATISFIABLE\n").is_err());
+/// assert!(parse_solution("s SATISFIABLE\nv 1 -2\n").is_err());
+/// ```
+pub fn parse_solution(text: &str) -> Result<Vec<i32>, String> {
+    let mut status = None;
+    let mut literals = Vec::new();
+    let mut ended = false;
+
+    for line in text.lines().map(str::trim) {
+        let mut tokens = line.split_whitespace();
+        match tokens.next() {
+            Some("s") => {
+                if status.is_some() {
+                    return Err("more than one status line".to_string());
+                }
+                status = Some(tokens.collect::<Vec<_>>().join(" "));
+            }
+            Some("v") => {
+                for token in tokens {
+                    if ended {
+                        return Err(format!("value after the final 0: {}", token));
+                    }
+                    match token.parse::<i32>() {
+                        Ok(0) => ended = true,
+                        Ok(lit) => literals.push(lit),
+                        Err(_) => return Err(format!("invalid literal: {}", token)),
+                    }
+                }
+            }
+            Some("c") | None => (),
+            Some(_) => return Err(format!("invalid line: {}", line)),
+        }
+    }
+
+    match status.as_deref() {
+        Some("SATISFIABLE") if ended => Ok(literals),
+        Some("SATISFIABLE") => Err("the value lines do not end with 0".to_string()),
+        Some(status) => Err(format!("the status is not SATISFIABLE: {}", status)),
+        None => Err("no status line".to_string()),
+    }
+}
+
+/// What is wrong with the model of a solution, if anything
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SolutionReport {
+    /// Clauses of the formula which contain no true literal
+    pub falsified: Vec<FalsifiedClause>,
+    /// Variables of the formula which the model does not assign
+    pub unassigned: Vec<i32>,
+    /// Variables which the model makes both true and false, and which count as unassigned for
+    /// the clauses
+    pub contradictory: Vec<i32>,
+}
+
+impl SolutionReport {
+    /// Returns `true` if the model assigns every variable once and satisfies every clause
+    pub fn is_valid(&self) -> bool {
+        self.falsified.is_empty() && self.unassigned.is_empty() && self.contradictory.is_empty()
+    }
+}
+
+/// Checks the model of a solution against the clauses of a formula
+///
+/// # Arguments
+/// * `formula` - The [`Formula`] as parsed
+/// * `literals` - The literals of the model, e.g. from [`parse_solution`]
+///
+/// # Returns
+/// * [`SolutionReport`] - The falsified clauses, unassigned variables and contradictory
+///   literals, in order
+///
+/// # Examples
+/// ```rust
+/// use sat_rs::cnfparser;
+/// use sat_rs::verify::verify_solution;
+///
+/// let formula = cnfparser::parse_cnf("p cnf 3 2\n1 -3 0\n2 3 -1 0\n").unwrap();
+/// assert!(verify_solution(&formula, &[-1, 2, -3]).is_valid());
+///
+/// let report = verify_solution(&formula, &[1, -1, 2]);
+/// assert_eq!(report.falsified[0].index, 0);
+/// assert_eq!(report.unassigned, vec![3]);
+/// assert_eq!(report.contradictory, vec![1]);
+/// ```
+pub fn verify_solution(formula: &Formula, literals: &[i32]) -> SolutionReport {
+    let mut values = values(literals);
+    let mut contradictory = Vec::new();
+    for lit in literals {
+        if values[lit.unsigned_abs() as usize] == Some(*lit < 0) {
+            contradictory.push(lit.abs());
+        }
+    }
+    contradictory.sort_unstable();
+    contradictory.dedup();
+    for var in &contradictory {
+        values[*var as usize] = None;
+    }
+
+    let mut unassigned: Vec<i32> = formula
+        .vars
+        .iter()
+        .filter(|var| {
+            let given = values.get(**var as usize).is_some_and(Option::is_some);
+            !given && contradictory.binary_search(var).is_err()
+        })
+        .copied()
+        .collect();
+    unassigned.sort_unstable();
+
+    let falsified = (0..formula.clauses.len())
+        .filter(|index| !satisfies(&values, &formula.clauses[*index]))
+        .map(|index| falsified(formula, index))
+        .collect();
+
+    SolutionReport {
+        falsified,
+        unassigned,
+        contradictory,
+    }
+}
+
+/// Returns the value of each variable given as a literal, indexed by the variable
+fn values(literals: &[i32]) -> Vec<Option<bool>> {
+    let mut values = Vec::new();
+    for lit in literals {
+        let var = lit.unsigned_abs() as usize;
+        if var >= values.len() {
+            values.resize(var + 1, None);
+        }
+        values[var] = Some(*lit > 0);
+    }
+    values
+}
+
+/// Returns `true` if one of the literals of the clause has its value
+fn satisfies(values: &[Option<bool>], clause: &Clause) -> bool {
+    clause.literals.iter().any(|literal| {
+        let value = values.get(literal.value as usize).copied().flatten();
+        value == Some(!literal.negated)
+    })
+}
+
+fn falsified(formula: &Formula, index: usize) -> FalsifiedClause {
+    let literals = formula.clauses[index]
+        .literals
+        .iter()
+        .map(|literal| {
+            if literal.negated {
+                -literal.value
+            } else {
+                literal.value
+            }
+        })
+        .collect();
+    FalsifiedClause { index, literals }
+}
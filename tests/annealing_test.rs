@@ -0,0 +1,45 @@
+use sat_rs::cnfparser;
+use sat_rs::solvers::annealing::{AnnealingConfig, AnnealingSolver, Cooling};
+use sat_rs::solvers::result::SolveResult;
+use sat_rs::solvers::solver::Solver;
+
+#[test]
+fn test_annealing_acceptance() {
+    // Create a buffer of type &str using file at bin/pigeonhole.cnf
+    let buffer = include_str!("../bin/pigeonhole.cnf");
+    let formula = cnfparser::parse_cnf(buffer).unwrap();
+
+    let flips = |temperature: f64| {
+        let config = AnnealingConfig {
+            cooling: Cooling::Linear,
+            max_temperature: temperature,
+            min_temperature: temperature,
+        };
+        let mut solver = AnnealingSolver::new(1, 200, config).with_seed(Some(1));
+        solver.add_formula(&formula);
+        assert!(matches!(solver.solve(), SolveResult::Unknown(_)));
+        solver.stats().flips
+    };
+
+    // Worsening flips are accepted when hot, and rejected when almost frozen
+    assert_eq!(flips(1e9), 200);
+    assert!(flips(1e-9) < 100, "{}", flips(1e-9));
+}
+
+#[test]
+fn test_cooling() {
+    // Every schedule starts at the maximum and ends at the minimum temperature
+    for cooling in [Cooling::Geometric, Cooling::Linear] {
+        assert_eq!(cooling.temperature(2.0, 0.5, 0.0), 2.0);
+        assert!((cooling.temperature(2.0, 0.5, 1.0) - 0.5).abs() < 1e-12);
+    }
+    assert_eq!(Cooling::Geometric.temperature(2.0, 0.5, 0.5), 1.0);
+
+    assert_eq!("geometric".parse::<Cooling>(), Ok(Cooling::Geometric));
+    assert_eq!(
+        "adaptive".parse::<Cooling>(),
+        Ok(Cooling::Adaptive { patience: 1000 })
+    );
+    assert!("adaptive:0".parse::<Cooling>().is_err());
+    assert!("exponential".parse::<Cooling>().is_err());
+}
@@ -0,0 +1,49 @@
+use sat_rs::cnfparser;
+use sat_rs::notation::{ClauseArena, Lit, Literal};
+
+#[test]
+fn test_arena() {
+    // Create a buffer of type &str using file at bin/problem.cnf
+    let buffer = include_str!("../bin/problem.cnf");
+    let formula = cnfparser::parse_cnf(buffer).unwrap();
+
+    // Clauses: 1 v -3 and 2 v 3 v -1
+    let mut arena = ClauseArena::from(&formula);
+    let crefs: Vec<_> = arena.iter().collect();
+    assert_eq!(arena.len(), 2);
+    assert_eq!(arena.size(), 2 + 2 + 3);
+    let literals: Vec<Literal> = arena
+        .lits(crefs[1])
+        .iter()
+        .map(|lit| Literal::from(*lit))
+        .collect();
+    assert_eq!(literals, formula.clauses[1].literals);
+
+    // Interleave learnt clauses and delete every other one
+    let mut learnt = Vec::new();
+    for var in 4..20 {
+        let lits = [Lit::from_dimacs(var), Lit::from_dimacs(-(var - 1))];
+        learnt.push((arena.alloc(&lits, true), lits));
+    }
+    for (cref, _) in learnt.iter().step_by(2) {
+        arena.delete(*cref);
+    }
+    assert_eq!(arena.len(), 2 + 8);
+    assert_eq!(arena.wasted(), 8 * 3);
+
+    // Garbage collection keeps the order of the remaining clauses and their literals
+    let relocation = arena.collect_garbage();
+    assert_eq!(arena.size(), 2 + 2 + 3 + 8 * 3);
+    assert_eq!(relocation.get(crefs[0]), Some(crefs[0]));
+    for (k, (cref, lits)) in learnt.iter().enumerate() {
+        match relocation.get(*cref) {
+            Some(moved) => {
+                assert_eq!(k % 2, 1);
+                assert_eq!(arena.lits(moved), lits);
+                assert!(arena.is_learnt(moved));
+            }
+            None => assert_eq!(k % 2, 0),
+        }
+    }
+    assert_eq!(arena.iter().count(), arena.len());
+}
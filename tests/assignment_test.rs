@@ -0,0 +1,44 @@
+use sat_rs::assignment::Assignment;
+use sat_rs::notation::Literal;
+use std::collections::HashMap;
+
+#[test]
+fn test_assignment() {
+    let mut assignment = Assignment::new(2);
+    assert_eq!(assignment.len(), 2);
+    assert_eq!(assignment.num_assigned(), 0);
+
+    // Assigning a variable beyond the length grows the assignment
+    assignment.set(2, true);
+    assignment.set(5, false);
+    assert_eq!(assignment.len(), 5);
+    assert_eq!(assignment.num_assigned(), 2);
+    assert!(!assignment.is_assigned(1));
+    assert_eq!(assignment.get(6), None);
+    assert_eq!(assignment.get(0), None);
+    assert_eq!(assignment.get(-2), None);
+
+    // Literals are three-valued
+    let literal = Literal {
+        value: 5,
+        negated: true,
+    };
+    assert_eq!(assignment.literal_value(&literal), Some(true));
+    assert_eq!(assignment.literal_value(&Literal::from_value(1)), None);
+
+    // Flipping negates assigned variables only
+    assignment.flip(2);
+    assignment.flip(1);
+    assert_eq!(assignment.get(2), Some(false));
+    assert_eq!(assignment.get(1), None);
+
+    assert_eq!(assignment.unassign(5), Some(false));
+    assert_eq!(assignment.unassign(5), None);
+    assert_eq!(assignment.iter().collect::<Vec<_>>(), vec![(2, false)]);
+
+    // Round trip through the map form
+    let interpretation = HashMap::from([(1, true), (4, false)]);
+    let assignment = Assignment::from(&interpretation);
+    assert_eq!(assignment.len(), 4);
+    assert_eq!(HashMap::from(assignment), interpretation);
+}
@@ -0,0 +1,45 @@
+use sat_rs::solvers::branching::{Branching, Phases, VariableOrder, Vsids};
+
+#[test]
+fn test_vsids_order() {
+    let mut order = Vsids::new(4, 0.5);
+
+    // Later conflicts weigh more than earlier ones
+    order.bump(1);
+    order.bump(3);
+    order.decay();
+    order.bump(2);
+    order.decay();
+    order.bump(3);
+
+    assert_eq!(order.next_var(&|_| false), Some(3));
+    assert_eq!(order.next_var(&|_| false), Some(2));
+    assert_eq!(order.next_var(&|_| false), Some(1));
+
+    // Unassigned variables are reinserted
+    order.unassigned(3);
+    assert_eq!(order.next_var(&|_| false), Some(3));
+    assert_eq!(order.next_var(&|var| var == 0), None);
+}
+
+#[test]
+fn test_branching_orders() {
+    for branching in [Branching::Vsids, Branching::Vmtf, Branching::Static] {
+        let mut order = branching.build(3);
+        let mut picked = Vec::new();
+        while let Some(var) = order.next_var(&|var| picked.contains(&var)) {
+            picked.push(var);
+        }
+
+        // Every variable is picked exactly once
+        picked.sort();
+        assert_eq!(picked, vec![0, 1, 2], "{:?}", branching);
+    }
+
+    assert_eq!("static".parse::<Branching>(), Ok(Branching::Static));
+    assert!("random".parse::<Branching>().is_err());
+
+    let mut phases = Phases::new(1);
+    phases.save(0, true);
+    assert!(phases.get(0));
+}
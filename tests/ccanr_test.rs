@@ -0,0 +1,30 @@
+use sat_rs::cnfparser;
+use sat_rs::solvers::ccanr::select_decreasing;
+use sat_rs::solvers::localsearch::LocalSearch;
+use sat_rs::solvers::weighting::ClauseWeights;
+
+#[test]
+fn test_select_decreasing() {
+    // Every clause is unsatisfied, the scores of the variables are 3, 2 and 1
+    let buffer = "p cnf 3 4\n1 0\n1 2 0\n1 3 0\n2 0\n";
+    let formula = cnfparser::parse_cnf(buffer).unwrap();
+    let state = LocalSearch::new(&formula);
+    let weights = ClauseWeights::new(&state, 1.0);
+
+    // The best variable whose configuration changed is preferred over better ones
+    assert_eq!(
+        select_decreasing(&state, &weights, &[true, true, true]),
+        Some(0)
+    );
+    assert_eq!(
+        select_decreasing(&state, &weights, &[false, true, true]),
+        Some(1)
+    );
+    assert_eq!(
+        select_decreasing(&state, &weights, &[false, false, true]),
+        Some(2)
+    );
+
+    // Without one, the best variable decreasing the weight by more than the mean
+    assert_eq!(select_decreasing(&state, &weights, &[false; 3]), Some(0));
+}
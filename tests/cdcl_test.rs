@@ -0,0 +1,102 @@
+mod common;
+
+use common::{random_formula, Lcg};
+use sat_rs::cnfparser;
+use sat_rs::notation::Formula;
+use sat_rs::solvers::branching::Branching;
+use sat_rs::solvers::cdcl::CdclConfig;
+use sat_rs::solvers::clausedb::ClauseDbConfig;
+use sat_rs::solvers::restart::Restart;
+use sat_rs::solvers::result::SolveResult;
+use sat_rs::solvers::{cdcl, dpll};
+
+/// Checks that a result is definitive, and that models satisfy the formula
+fn check(result: SolveResult, formula: &Formula, expected_sat: bool, context: &str) {
+    match result {
+        SolveResult::Sat(model) => {
+            assert!(expected_sat, "{}: unexpected model", context);
+            assert!(model.satisfies(formula), "{}: invalid model", context);
+        }
+        SolveResult::Unsat => assert!(!expected_sat, "{}: unexpected UNSAT", context),
+        SolveResult::Unknown(reason) => panic!("{}: unknown result: {}", context, reason),
+    }
+}
+
+#[test]
+fn test_cdcl() {
+    let config = CdclConfig::default();
+
+    // Check that the unsatisfiable formulas are unsatisfiable
+    let buffer = include_str!("../bin/unsatisfiable.cnf");
+    let formula = cnfparser::parse_cnf(buffer);
+    let result = cdcl::cdcl_algorithm(&mut formula.unwrap(), &config);
+    assert_eq!(result, SolveResult::Unsat);
+
+    let buffer = include_str!("../bin/pigeonhole.cnf");
+    let formula = cnfparser::parse_cnf(buffer);
+    let result = cdcl::cdcl_algorithm(&mut formula.unwrap(), &config);
+    assert_eq!(result, SolveResult::Unsat);
+
+    // Check that the formula is satisfiable
+    let buffer = include_str!("../bin/problem.cnf");
+    let formula = cnfparser::parse_cnf(buffer).unwrap();
+    let result = cdcl::cdcl_algorithm(&mut formula.clone(), &config);
+    check(result, &formula, true, "problem.cnf");
+}
+
+#[test]
+fn test_cdcl_agrees_with_dpll() {
+    // Random 3-SAT near the phase transition has a mix of satisfiable and unsatisfiable formulas
+    for seed in 0..50 {
+        let formula = random_formula(&mut Lcg::new(seed), 12, 51, 3..=3);
+        let result = dpll::dpll_algorithm(&mut formula.clone(), Branching::Static);
+        let expected = result.is_sat();
+        check(result, &formula, expected, "dpll");
+
+        for restart in ["none", "luby:1", "geometric:2:1.1", "glucose:1.0:2"] {
+            let config = CdclConfig {
+                restart: restart.parse::<Restart>().unwrap(),
+                ..Default::default()
+            };
+            let result = cdcl::cdcl_algorithm(&mut formula.clone(), &config);
+            check(
+                result,
+                &formula,
+                expected,
+                &format!("seed {} with {}", seed, restart),
+            );
+        }
+
+        // Reduce the learnt clauses after every conflict
+        let config = CdclConfig {
+            clause_db: ClauseDbConfig {
+                reduce_interval: 1,
+                reduce_increment: 0,
+                core_lbd: 0,
+                tier2_lbd: 0,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let result = cdcl::cdcl_algorithm(&mut formula.clone(), &config);
+        check(
+            result,
+            &formula,
+            expected,
+            &format!("seed {} with reductions", seed),
+        );
+
+        for branching in [Branching::Vsids, Branching::Vmtf, Branching::Static] {
+            let context = format!("seed {} with {:?}", seed, branching);
+            let config = CdclConfig {
+                branching,
+                ..Default::default()
+            };
+            let result = cdcl::cdcl_algorithm(&mut formula.clone(), &config);
+            check(result, &formula, expected, &context);
+
+            let result = dpll::dpll_algorithm(&mut formula.clone(), branching);
+            check(result, &formula, expected, &context);
+        }
+    }
+}
@@ -1,5 +1,6 @@
 use sat_rs::cnfparser;
 use sat_rs::solvers::chaos;
+use sat_rs::solvers::result::SolveResult;
 
 #[test]
 fn test_chaos() {
@@ -7,10 +8,10 @@ fn test_chaos() {
     let buffer = include_str!("../bin/unsatisfiable.cnf");
 
     // Parse the CNF file
-    let formula = cnfparser::parse_cnf(&buffer);
+    let formula = cnfparser::parse_cnf(buffer);
 
-    // Check that the formula is unsatisfiable
-    let result: bool = chaos::chaos_algorithm(&mut formula.unwrap(), 10);
+    // Check that no model is found for the unsatisfiable formula
+    let result: SolveResult = chaos::chaos_algorithm(&mut formula.unwrap(), 10, Some(1));
 
-    assert_eq!(result, false);
+    assert!(matches!(result, SolveResult::Unknown(_)));
 }
@@ -0,0 +1,56 @@
+use sat_rs::solvers::clausedb::{ClauseDb, ClauseDbConfig, Tier};
+
+#[test]
+fn test_clausedb_reduce() {
+    let config = ClauseDbConfig {
+        reduce_interval: 10,
+        reduce_increment: 5,
+        tier2_window: 3,
+        ..Default::default()
+    };
+    let mut db = ClauseDb::new(config);
+
+    // Clause 0 is core, 1 is tier 2, 2 to 6 are local
+    db.add(0, 2);
+    db.add(1, 5);
+    for cref in 2..7 {
+        db.add(cref, 10 + cref);
+    }
+    assert_eq!(db.tier(1), Some(Tier::Tier2));
+
+    // Using clause 4 lowers its LBD and makes it the most active local clause
+    db.bump(4, 8);
+    assert_eq!(db.lbd(4), Some(8));
+
+    for _ in 0..10 {
+        db.conflict();
+    }
+    assert!(db.should_reduce());
+
+    // Clause 1 has not been used recently, so it is handled as a local clause
+    assert_eq!(db.tier(1), Some(Tier::Local));
+
+    // Clause 6 is locked, of the 5 other local clauses the 2 with the highest LBD are deleted
+    let mut deleted = db.reduce(&|cref| cref == 6);
+    deleted.sort();
+    assert_eq!(deleted, vec![3, 5]);
+    assert!(!db.is_learnt(3));
+    assert!(db.is_learnt(6));
+    assert!(!db.should_reduce());
+    assert_eq!(db.reductions(), 1);
+
+    // Relocating moves the scores, clauses mapped to nothing are unregistered
+    db.relocate(&|cref| if cref == 0 { None } else { Some(cref + 100) });
+    assert!(!db.is_learnt(0));
+    assert_eq!(db.lbd(104), Some(8));
+    assert!(db.is_learnt(106));
+    assert!(!db.is_learnt(6));
+
+    // The next reduction is 10 + 5 conflicts later
+    for _ in 0..14 {
+        db.conflict();
+    }
+    assert!(!db.should_reduce());
+    db.conflict();
+    assert!(db.should_reduce());
+}
@@ -6,9 +6,27 @@ fn test_parse_cnf() {
     let buffer = include_str!("../bin/problem.cnf");
 
     // Parse the CNF file
-    let formula = cnfparser::parse_cnf(&buffer);
+    let formula = cnfparser::parse_cnf(buffer);
 
     // Check properties of the formula
     assert_eq!(formula.as_ref().unwrap().num_clauses, 2);
     assert_eq!(formula.as_ref().unwrap().num_vars, 3);
 }
+
+#[test]
+fn test_parse_invalid_cnf() {
+    // The header must match the clauses, and every token must be a literal
+    assert!(cnfparser::parse_cnf("p cnf 5 1\n1 2 0\n").is_err());
+    assert!(cnfparser::parse_cnf("p cnf 2 2\n1 2 0\n").is_err());
+    assert!(cnfparser::parse_cnf("p cnf 2 1\n1 x 0\n").is_err());
+
+    // The smallest 32-bit integer has no variable
+    assert_eq!(
+        cnfparser::parse_cnf("p cnf 1 1\n-2147483648 0\n").err(),
+        Some("Literal out of range: -2147483648".to_string())
+    );
+
+    // The header may give the largest variable instead of the number of variables
+    let formula = cnfparser::parse_cnf("p cnf 5 2\n1 0\n-5 0\n").unwrap();
+    assert_eq!(formula.num_vars, 2);
+}
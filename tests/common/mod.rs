@@ -0,0 +1,47 @@
+use sat_rs::notation::{Clause, Formula, Literal};
+use std::ops::RangeInclusive;
+
+/// Linear congruential generator, so that the random formulas of the tests do not depend on the
+/// random number generators of the solvers
+pub struct Lcg(u64);
+
+impl Lcg {
+    pub fn new(seed: u64) -> Lcg {
+        Lcg(seed)
+    }
+
+    /// Returns a number below `bound`
+    pub fn next(&mut self, bound: u64) -> u64 {
+        self.0 = self
+            .0
+            .wrapping_mul(6364136223846793005)
+            .wrapping_add(1442695040888963407);
+        (self.0 >> 33) % bound
+    }
+}
+
+/// Builds a random formula over `num_vars` variables, the length of every clause being drawn
+/// from `lengths`, so that clauses may contain duplicate literals and tautologies
+pub fn random_formula(
+    lcg: &mut Lcg,
+    num_vars: u64,
+    num_clauses: usize,
+    lengths: RangeInclusive<u64>,
+) -> Formula {
+    let mut formula = Formula::new();
+    for _ in 0..num_clauses {
+        let mut clause = Clause::new();
+        let mut length = *lengths.start();
+        if lengths.end() > lengths.start() {
+            length += lcg.next(lengths.end() - lengths.start() + 1);
+        }
+        for _ in 0..length {
+            clause.literals.push(Literal {
+                value: lcg.next(num_vars) as i32 + 1,
+                negated: lcg.next(2) == 1,
+            });
+        }
+        formula.add_clause(clause);
+    }
+    formula
+}
@@ -0,0 +1,34 @@
+use sat_rs::cnfparser;
+use sat_rs::solvers::branching::Branching;
+use sat_rs::solvers::dpll;
+use sat_rs::solvers::result::SolveResult;
+
+#[test]
+fn test_dpll() {
+    // Create a buffer of type &str using file at bin/unsatisfiable.cnf
+    let buffer = include_str!("../bin/unsatisfiable.cnf");
+
+    // Parse the CNF file
+    let formula = cnfparser::parse_cnf(buffer);
+
+    // Check that the formula is unsatisfiable
+    let result: SolveResult = dpll::dpll_algorithm(&mut formula.unwrap(), Branching::Vsids);
+
+    assert_eq!(result, SolveResult::Unsat);
+
+    // Check that the pigeonhole formula is unsatisfiable, which requires backtracking
+    let buffer = include_str!("../bin/pigeonhole.cnf");
+    let formula = cnfparser::parse_cnf(buffer);
+    let result: SolveResult = dpll::dpll_algorithm(&mut formula.unwrap(), Branching::Vsids);
+
+    assert_eq!(result, SolveResult::Unsat);
+
+    // Create a buffer of type &str using file at bin/problem.cnf
+    let buffer = include_str!("../bin/problem.cnf");
+
+    // Check that the formula is satisfiable
+    let formula = cnfparser::parse_cnf(buffer).unwrap();
+    let result: SolveResult = dpll::dpll_algorithm(&mut formula.clone(), Branching::Vsids);
+
+    assert!(result.model().unwrap().satisfies(&formula));
+}
@@ -0,0 +1,58 @@
+use sat_rs::cnfparser;
+use sat_rs::proof::drat::{check_drat, is_binary, parse_drat};
+use sat_rs::proof::lrat::{check_lrat, LratStep};
+use sat_rs::proof::{ProofFormat, ProofWriter};
+use sat_rs::solvers::registry::{build_solver, SolverConfig};
+
+#[test]
+fn test_drat_checker() {
+    // Clauses: 1 v 2, 1 v -2, -1 v 2, -1 v -2 and -4 v 5
+    let formula =
+        cnfparser::parse_cnf("p cnf 4 5\n1 2 0\n1 -2 0\n-1 2 0\n-1 -2 0\n-4 5 0\n").unwrap();
+
+    // The unit clause 3 is not implied, but it is a RAT on 3 once -3 v 1 is added
+    let proof = parse_drat(b"-3 1 0\n3 0\nd 1 2 0\n-3 -1 0\n0\n", false).unwrap();
+    let trimmed = check_drat(&formula, &proof).unwrap();
+    assert_eq!(trimmed.core, vec![0, 1, 2, 3]);
+    assert!(trimmed.lrat.contains(&LratStep::Add {
+        id: 7,
+        clause: vec![3],
+        hints: vec![-6, 1, 2],
+    }));
+    assert!(trimmed.lrat.contains(&LratStep::Delete { ids: vec![1] }));
+    assert_eq!(check_lrat(&formula, &trimmed.lrat), Ok(()));
+
+    // Without the clause 1 v 2, 3 is neither RUP nor RAT on 3
+    let proof = parse_drat(b"-3 1 0\nd 1 2 0\n3 0\n-3 -1 0\n0\n", false).unwrap();
+    assert_eq!(check_drat(&formula, &proof).unwrap_err().step, 3);
+
+    // A proof must refute the formula, without the empty clause by unit propagation at its end
+    let proof = parse_drat(b"1 0\n", false).unwrap();
+    assert!(check_drat(&formula, &proof).is_ok());
+    let proof = parse_drat(b"4 0\n", false).unwrap();
+    let error = check_drat(&formula, &proof).unwrap_err();
+    assert_eq!(error.message, "the proof does not derive the empty clause");
+}
+
+#[test]
+fn test_check_solver_proof() {
+    // Create a buffer of type &str using file at bin/pigeonhole.cnf
+    let buffer = include_str!("../bin/pigeonhole.cnf");
+    let formula = cnfparser::parse_cnf(buffer).unwrap();
+
+    for format in [ProofFormat::Drat, ProofFormat::BinaryDrat] {
+        let path = std::env::temp_dir().join(format!("sat-rs-check-{:?}.drat", format));
+        let mut solver = build_solver("cdcl", &SolverConfig::default()).unwrap();
+        assert!(solver.set_proof(ProofWriter::create(&path, format).unwrap()));
+        solver.add_formula(&formula);
+        assert!(solver.solve().is_unsat());
+        solver.finish_proof().unwrap();
+
+        let bytes = std::fs::read(&path).unwrap();
+        assert_eq!(is_binary(&bytes), format.is_binary());
+        let proof = parse_drat(&bytes, is_binary(&bytes)).unwrap();
+        let trimmed = check_drat(&formula, &proof).unwrap();
+        assert!(!trimmed.core.is_empty());
+        assert_eq!(check_lrat(&formula, &trimmed.lrat), Ok(()), "{:?}", format);
+    }
+}
@@ -1,5 +1,6 @@
 use std::collections::HashMap;
 
+use sat_rs::assignment::Assignment;
 use sat_rs::cnfparser;
 
 #[test]
@@ -8,7 +9,7 @@ fn test_evaluate() {
     let buffer = include_str!("../bin/problem.cnf");
 
     // Parse the CNF file
-    let formula = cnfparser::parse_cnf(&buffer);
+    let formula = cnfparser::parse_cnf(buffer).unwrap();
 
     // Check using a known solution)
     // TODO(SauravMaheshkar): Replace with terminal simulation?
@@ -17,5 +18,36 @@ fn test_evaluate() {
     interpretation.insert(2, false);
     interpretation.insert(3, false);
 
-    assert_eq!(formula.unwrap().evaluate(&interpretation), false);
+    assert_eq!(
+        formula.evaluate(&Assignment::from(&interpretation)),
+        Some(true)
+    );
+}
+
+#[test]
+fn test_evaluate_partial() {
+    // Clauses: 1 v -3 and 2 v 3 v -1
+    let buffer = include_str!("../bin/problem.cnf");
+    let formula = cnfparser::parse_cnf(buffer).unwrap();
+
+    // Nothing is determined by the empty interpretation
+    let mut interpretation = Assignment::new(3);
+    assert_eq!(formula.evaluate(&interpretation), None);
+    assert_eq!(formula.unsatisfied_clauses(&interpretation), vec![0, 1]);
+    assert!(formula.falsified_clauses(&interpretation).is_empty());
+
+    // 1 satisfies the first clause and leaves the second one undetermined
+    interpretation.set(1, true);
+    assert_eq!(formula.evaluate(&interpretation), None);
+    assert_eq!(formula.unsatisfied_clauses(&interpretation), vec![1]);
+
+    // Evaluation does not depend on earlier evaluations
+    interpretation.set(2, false);
+    interpretation.set(3, false);
+    assert_eq!(formula.evaluate(&interpretation), Some(false));
+    assert_eq!(formula.falsified_clauses(&interpretation), vec![1]);
+
+    interpretation.flip(3);
+    assert_eq!(formula.evaluate(&interpretation), Some(true));
+    assert!(formula.unsatisfied_clauses(&interpretation).is_empty());
 }
@@ -1,5 +1,11 @@
+use rand::SeedableRng;
+use rand_chacha::ChaCha8Rng;
 use sat_rs::cnfparser;
-use sat_rs::solvers::gsat;
+use sat_rs::solvers::gsat::{self, GsatSolver};
+use sat_rs::solvers::localsearch::LocalSearch;
+use sat_rs::solvers::result::SolveResult;
+use sat_rs::solvers::solver::{Solver, SolverStats};
+use std::collections::BTreeSet;
 
 #[test]
 fn test_gsat() {
@@ -7,13 +13,102 @@ fn test_gsat() {
     let buffer = include_str!("../bin/unsatisfiable.cnf");
 
     // Parse the CNF file
-    let formula = cnfparser::parse_cnf(&buffer);
+    let formula = cnfparser::parse_cnf(buffer);
 
-    // Check that the formula is unsatisfiable
-    let result: bool = gsat::gsat_algorithm(&mut formula.clone().unwrap(), 10, 10, None);
-    let result_with_walk_probability: bool =
-        gsat::gsat_algorithm(&mut formula.clone().unwrap(), 10, 10, Some(0.5));
+    // Check that no model is found for the unsatisfiable formula
+    let result: SolveResult =
+        gsat::gsat_algorithm(&mut formula.clone().unwrap(), 10, 10, None, 0, Some(1));
+    let result_with_walk_probability: SolveResult =
+        gsat::gsat_algorithm(&mut formula.clone().unwrap(), 10, 10, Some(0.5), 0, Some(1));
 
-    assert_eq!(result, false);
-    assert_eq!(result_with_walk_probability, false);
+    assert!(matches!(result, SolveResult::Unknown(_)));
+    assert!(matches!(
+        result_with_walk_probability,
+        SolveResult::Unknown(_)
+    ));
+}
+
+#[test]
+fn test_gsat_greedy() {
+    // Create a buffer of type &str using file at bin/problem.cnf
+    let buffer = include_str!("../bin/problem.cnf");
+    let mut formula = cnfparser::parse_cnf(buffer).unwrap();
+
+    // Greedy flips find a model of the satisfiable formula, with or without random walk
+    for walk_probability in [None, Some(0.5)] {
+        let result = gsat::gsat_algorithm(&mut formula, 10, 100, walk_probability, 0, Some(1));
+        assert!(formula.evaluate(result.model().unwrap().assignment()) == Some(true));
+    }
+}
+
+#[test]
+fn test_gsat_best_variable() {
+    // With every variable false, p and q satisfy two clauses each and r breaks -r
+    let buffer = "p cnf 3 4\n1 0\n1 2 0\n2 3 0\n-3 0\n";
+    let formula = cnfparser::parse_cnf(buffer).unwrap();
+    let mut state = LocalSearch::new(&formula);
+    assert_eq!((state.score(0), state.score(1), state.score(2)), (2, 2, 0));
+
+    // The tie between p and q is broken at random
+    let mut rng = ChaCha8Rng::seed_from_u64(1);
+    let mut stats = SolverStats::default();
+    let chosen: BTreeSet<usize> = (0..100)
+        .map(|_| gsat::best_variable(&state, 3, 0, &mut rng, &mut stats).unwrap())
+        .collect();
+    assert_eq!(chosen, BTreeSet::from([0, 1]));
+
+    // Once q is true, p has the highest score
+    state.flip(1);
+    assert_eq!(
+        (state.score(0), state.score(1), state.score(2)),
+        (1, -2, -1)
+    );
+    for _ in 0..10 {
+        assert_eq!(
+            gsat::best_variable(&state, 1, 0, &mut rng, &mut stats),
+            Some(0)
+        );
+    }
+    assert_eq!(stats.tabu_blocked, 0);
+
+    // Flipping p twice makes it tabu, so q is chosen unless p leads to a new best
+    let mut state = LocalSearch::new(&formula);
+    state.flip(0);
+    state.flip(0);
+    for _ in 0..10 {
+        assert_eq!(
+            gsat::best_variable(&state, 1, 5, &mut rng, &mut stats),
+            Some(1)
+        );
+    }
+    assert_eq!(stats.tabu_blocked, 0);
+}
+
+#[test]
+fn test_gsat_tabu() {
+    // Create a buffer of type &str using file at bin/unsatisfiable.cnf
+    let buffer = include_str!("../bin/unsatisfiable.cnf");
+    let formula = cnfparser::parse_cnf(buffer).unwrap();
+
+    // Flipping the only variable back never improves on the first interpretation, so it stays
+    // tabu for the 5 steps after each flip
+    let mut solver = GsatSolver::new(1, 12, None, 5);
+    solver.add_formula(&formula);
+    assert!(matches!(solver.solve(), SolveResult::Unknown(_)));
+    assert_eq!(solver.stats().flips, 2);
+    assert_eq!(solver.stats().tabu_blocked, 10);
+}
+
+#[test]
+fn test_gsat_tabu_blocked_steps() {
+    // Create a buffer of type &str using file at bin/pigeonhole.cnf
+    let buffer = include_str!("../bin/pigeonhole.cnf");
+    let formula = cnfparser::parse_cnf(buffer).unwrap();
+
+    // Several variables are tabu at every step, but a step is blocked at most once
+    let mut solver = GsatSolver::new(1, 50, None, 5).with_seed(Some(1));
+    solver.add_formula(&formula);
+    assert!(matches!(solver.solve(), SolveResult::Unknown(_)));
+    assert!(solver.stats().tabu_blocked > 0);
+    assert!(solver.stats().tabu_blocked <= 50);
 }
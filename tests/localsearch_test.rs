@@ -0,0 +1,90 @@
+mod common;
+
+use common::{random_formula, Lcg};
+use sat_rs::assignment::Assignment;
+use sat_rs::notation::Lit;
+use sat_rs::solvers::localsearch::LocalSearch;
+
+#[test]
+fn test_localsearch_incremental() {
+    // Random formula with clauses of 1 to 4 literals, including duplicates and tautologies
+    let mut lcg = Lcg::new(7);
+    let num_vars = 15;
+    let formula = random_formula(&mut lcg, num_vars, 60, 1..=4);
+
+    let mut search = LocalSearch::new(&formula);
+    let mut interpretation = Assignment::new(num_vars as usize);
+    for var in 1..=num_vars as i32 {
+        interpretation.set(var, false);
+    }
+
+    for _ in 0..500 {
+        let var = lcg.next(num_vars) as usize;
+        search.flip(var);
+        interpretation.flip(var as i32 + 1);
+
+        // The unsatisfied clauses match a full evaluation
+        let mut unsat: Vec<&[Lit]> = search
+            .unsat_clauses()
+            .iter()
+            .map(|clause| search.clause(*clause))
+            .collect();
+        unsat.sort();
+        let mut expected: Vec<Vec<Lit>> = formula
+            .unsatisfied_clauses(&interpretation)
+            .into_iter()
+            .map(|index| {
+                let mut lits: Vec<Lit> = formula.clauses[index]
+                    .literals
+                    .iter()
+                    .map(Lit::from)
+                    .collect();
+                lits.sort();
+                lits.dedup();
+                lits
+            })
+            .collect();
+        expected.sort();
+        assert_eq!(unsat, expected);
+
+        // Make and break counts match the effect of flipping each variable
+        let before = formula.unsatisfied_clauses(&interpretation).len() as i64;
+        for v in 0..num_vars as usize {
+            let mut flipped = interpretation.clone();
+            flipped.flip(v as i32 + 1);
+            let after = formula.unsatisfied_clauses(&flipped).len() as i64;
+            assert_eq!(search.score(v), before - after, "score of {}", v);
+
+            let made = formula
+                .unsatisfied_clauses(&interpretation)
+                .into_iter()
+                .filter(|index| formula.clauses[*index].evaluate(&flipped) == Some(true))
+                .count() as u32;
+            assert_eq!(search.make(v), made, "make of {}", v);
+        }
+
+        // The variables grouped by score match the scores
+        let max_score = search.vars().iter().map(|v| search.score(*v)).max();
+        assert_eq!(search.max_score(), max_score);
+        let mut grouped = Vec::new();
+        for score in -60..=60 {
+            for v in search.vars_with_score(score) {
+                assert_eq!(search.score(*v), score, "score of {}", v);
+                grouped.push(*v);
+            }
+        }
+        grouped.sort();
+        assert_eq!(grouped, search.vars());
+    }
+}
+
+#[test]
+fn test_localsearch_vars() {
+    // Variables 2 and 4 are unused, and 3 only occurs in a tautology
+    let buffer = "p cnf 5 3\n1 -5 0\n3 -3 0\n5 0\n";
+    let formula = sat_rs::cnfparser::parse_cnf(buffer).unwrap();
+
+    let search = LocalSearch::new(&formula);
+    assert_eq!(search.num_vars(), 5);
+    assert_eq!(search.vars(), &[0, 4]);
+}
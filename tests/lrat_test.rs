@@ -0,0 +1,59 @@
+use sat_rs::cnfparser;
+use sat_rs::proof::lrat::{check_lrat, parse_lrat, LratStep};
+use sat_rs::proof::{ProofFormat, ProofWriter};
+use sat_rs::solvers::registry::{build_solver, SolverConfig};
+
+#[test]
+fn test_lrat_checker() {
+    // Clauses: 1 v 2, 1 v -2, -1 v 2 and -1 v -2
+    let formula = cnfparser::parse_cnf("p cnf 2 4\n1 2 0\n1 -2 0\n-1 2 0\n-1 -2 0\n").unwrap();
+
+    // The unit clause 1 is a RAT on 1, the only clauses with -1 being resolved to tautologies or
+    // refuted by their hints
+    let proof = parse_lrat(b"5 1 0 -3 1 -4 2 0\n6 0 5 3 4 0\n", false).unwrap();
+    assert!(check_lrat(&formula, &proof).is_ok());
+
+    // A hint which is not unit is rejected, as is a proof without the empty clause
+    let proof = parse_lrat(b"5 1 0 3 0\n", false).unwrap();
+    assert_eq!(check_lrat(&formula, &proof).unwrap_err().step, 1);
+    let proof = parse_lrat(b"5 1 0 1 2 0\n5 d 1 0\n", false).unwrap();
+    assert_eq!(check_lrat(&formula, &proof).unwrap_err().step, 2);
+
+    // Binary proofs encode every number like a literal
+    let steps = parse_lrat(&[b'a', 10, 2, 0, 2, 4, 0, b'd', 2, 0], true).unwrap();
+    assert_eq!(
+        steps,
+        vec![
+            LratStep::Add {
+                id: 5,
+                clause: vec![1],
+                hints: vec![1, 2]
+            },
+            LratStep::Delete { ids: vec![1] },
+        ]
+    );
+}
+
+#[test]
+fn test_cdcl_lrat() {
+    // Create a buffer of type &str using file at bin/pigeonhole.cnf
+    let buffer = include_str!("../bin/pigeonhole.cnf");
+    let formula = cnfparser::parse_cnf(buffer).unwrap();
+
+    for format in [ProofFormat::Lrat, ProofFormat::BinaryLrat] {
+        let path = std::env::temp_dir().join(format!("sat-rs-proof-{:?}.lrat", format));
+        let mut solver = build_solver("cdcl", &SolverConfig::default()).unwrap();
+        assert!(solver.set_proof(ProofWriter::create(&path, format).unwrap()));
+        solver.add_formula(&formula);
+
+        assert!(solver.solve().is_unsat());
+        solver.finish_proof().unwrap();
+        let steps = parse_lrat(&std::fs::read(&path).unwrap(), format.is_binary()).unwrap();
+        assert_eq!(check_lrat(&formula, &steps), Ok(()), "{:?}", format);
+    }
+
+    // DPLL does not keep track of the antecedents of its lemmas
+    let mut solver = build_solver("dpll", &SolverConfig::default()).unwrap();
+    let proof = ProofWriter::new(Vec::new(), ProofFormat::Lrat);
+    assert!(!solver.set_proof(proof));
+}
@@ -0,0 +1,58 @@
+use rand::SeedableRng;
+use rand_chacha::ChaCha8Rng;
+use sat_rs::cnfparser;
+use sat_rs::solvers::localsearch::LocalSearch;
+use sat_rs::solvers::novelty::{self, AdaptiveNoise, NoveltyConfig, NoveltySolver};
+
+#[test]
+fn test_adaptive_noise() {
+    // 60 clauses, so the noise is raised after more than 10 flips without improvement
+    let mut adaptive = AdaptiveNoise::new(60, 5);
+    for step in 1..=10 {
+        adaptive.update(step, 5);
+        assert_eq!(adaptive.noise(), 0.0);
+    }
+    adaptive.update(11, 5);
+    assert!((adaptive.noise() - 0.2).abs() < 1e-12);
+
+    // Stagnating again raises it by a fifth of the way to 1
+    for step in 12..=22 {
+        adaptive.update(step, 6);
+    }
+    assert!((adaptive.noise() - 0.36).abs() < 1e-12);
+
+    // An improvement lowers it by a tenth
+    adaptive.update(23, 4);
+    assert!((adaptive.noise() - 0.324).abs() < 1e-12);
+}
+
+#[test]
+fn test_select_novelty() {
+    let mut rng = ChaCha8Rng::seed_from_u64(1);
+
+    // With every variable false, flipping p satisfies both clauses and q only the first one
+    let formula = cnfparser::parse_cnf("p cnf 2 2\n1 2 0\n1 0\n").unwrap();
+    let mut state = LocalSearch::new(&formula);
+    assert_eq!((state.score(0), state.score(1)), (2, 1));
+
+    // Before any flip, the best variable is taken whatever the noise
+    let clause = state.clause(0).to_vec();
+    assert_eq!(novelty::select_novelty(&state, &clause, 1.0, &mut rng), 0);
+
+    // Flipping p twice makes it the most recently flipped variable of the clause
+    state.flip(0);
+    state.flip(0);
+    assert_eq!((state.score(0), state.score(1)), (2, 1));
+
+    // Then the noise decides between the best and the second best variable
+    for _ in 0..10 {
+        assert_eq!(novelty::select_novelty(&state, &clause, 0.0, &mut rng), 0);
+        assert_eq!(novelty::select_novelty(&state, &clause, 1.0, &mut rng), 1);
+    }
+}
+
+#[test]
+#[should_panic(expected = "Invalid Novelty noise: 1.5")]
+fn test_novelty_invalid_noise() {
+    NoveltySolver::new(10, 10, NoveltyConfig::novelty(1.5));
+}
@@ -0,0 +1,45 @@
+use sat_rs::cnfparser;
+use sat_rs::solvers::probsat::{self, BreakFunction};
+use sat_rs::solvers::result::SolveResult;
+
+#[test]
+fn test_probsat() {
+    // Create a buffer of type &str using file at bin/unsatisfiable.cnf
+    let buffer = include_str!("../bin/unsatisfiable.cnf");
+    let formula = cnfparser::parse_cnf(buffer);
+
+    // Check that no model is found for the unsatisfiable formula
+    let result: SolveResult =
+        probsat::probsat_algorithm(&mut formula.unwrap(), 10, 10, None, Some(1));
+    assert!(matches!(result, SolveResult::Unknown(_)));
+
+    // Both break functions find a model of a satisfiable formula
+    let buffer = include_str!("../bin/problem.cnf");
+    let mut formula = cnfparser::parse_cnf(buffer).unwrap();
+    for function in ["poly:2.38", "exp:2.5"] {
+        let function: BreakFunction = function.parse().unwrap();
+        let result = probsat::probsat_algorithm(&mut formula, 10, 100, Some(function), Some(1));
+        assert!(formula.evaluate(result.model().unwrap().assignment()) == Some(true));
+    }
+}
+
+#[test]
+fn test_break_function() {
+    assert_eq!(
+        "poly:2:0.5".parse::<BreakFunction>(),
+        Ok(BreakFunction::Poly { cb: 2.0, eps: 0.5 })
+    );
+    assert!("poly".parse::<BreakFunction>().is_err());
+    assert!("exp:3:1".parse::<BreakFunction>().is_err());
+    assert!("linear:1".parse::<BreakFunction>().is_err());
+
+    // Published defaults by clause width
+    assert_eq!(BreakFunction::for_width(4), BreakFunction::Exp { cb: 3.0 });
+    assert_eq!(BreakFunction::for_width(6), BreakFunction::Exp { cb: 5.1 });
+    assert_eq!(BreakFunction::for_width(9), BreakFunction::Exp { cb: 5.4 });
+
+    // Variables breaking fewer clauses are more likely to be flipped
+    let function = BreakFunction::for_width(3);
+    assert_eq!(function.probability(1), 2.0f64.powf(-2.38));
+    assert!(function.probability(0) > function.probability(1));
+}
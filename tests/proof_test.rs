@@ -0,0 +1,56 @@
+use sat_rs::cnfparser;
+use sat_rs::proof::drat::{check_drat, parse_drat};
+use sat_rs::proof::{ProofFormat, ProofWriter};
+use sat_rs::solvers::registry::{build_solver, SolverConfig};
+
+#[test]
+fn test_proof_writer() {
+    let path = std::env::temp_dir().join("sat-rs-proof-writer.drat");
+    let mut proof = ProofWriter::create(&path, ProofFormat::BinaryDrat).unwrap();
+    proof.add(1, &[1, -2], &[]);
+    proof.delete(1, &[-100]);
+    proof.add(2, &[], &[]);
+    proof.finish().unwrap();
+
+    // Literals are encoded as 2 * v or 2 * v + 1 in 7-bit groups, lowest group first
+    let bytes = std::fs::read(&path).unwrap();
+    assert_eq!(bytes, vec![b'a', 2, 5, 0, b'd', 0xc9, 0x01, 0, b'a', 0]);
+}
+
+#[test]
+fn test_solver_proofs() {
+    // Create a buffer of type &str using file at bin/pigeonhole.cnf
+    let buffer = include_str!("../bin/pigeonhole.cnf");
+    let formula = cnfparser::parse_cnf(buffer).unwrap();
+
+    // The complete solvers end their proof of the unsatisfiable formula with the empty clause,
+    // and the proof is checked in both formats
+    for name in ["dpll", "cdcl"] {
+        for format in [ProofFormat::Drat, ProofFormat::BinaryDrat] {
+            let path =
+                std::env::temp_dir().join(format!("sat-rs-proof-{}-{:?}.drat", name, format));
+            let mut solver = build_solver(name, &SolverConfig::default()).unwrap();
+            let proof = ProofWriter::create(&path, format).unwrap();
+            assert!(solver.set_proof(proof), "{}: no proof", name);
+            solver.add_formula(&formula);
+
+            assert!(solver.solve().is_unsat(), "{}", name);
+            solver.finish_proof().unwrap();
+            let bytes = std::fs::read(&path).unwrap();
+            if !format.is_binary() {
+                let text = String::from_utf8(bytes.clone()).unwrap();
+                assert!(text.lines().all(|line| line.ends_with('0')), "{}", name);
+                assert_eq!(text.lines().last(), Some("0"), "{}", name);
+            }
+
+            let steps = parse_drat(&bytes, format.is_binary()).unwrap();
+            let checked = check_drat(&formula, &steps);
+            assert!(checked.is_ok(), "{} {:?}", name, format);
+        }
+    }
+
+    // The local search solvers cannot refute a formula
+    let mut solver = build_solver("wsat", &SolverConfig::default()).unwrap();
+    let proof = ProofWriter::new(Vec::new(), ProofFormat::Drat);
+    assert!(!solver.set_proof(proof));
+}
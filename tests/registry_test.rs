@@ -0,0 +1,129 @@
+use sat_rs::cnfparser;
+use sat_rs::notation::Literal;
+use sat_rs::solvers::registry::{build_solver, SolverConfig, SOLVERS};
+use sat_rs::solvers::result::SolveResult;
+
+#[test]
+fn test_registry() {
+    let config = SolverConfig::default();
+
+    // Create a buffer of type &str using file at bin/problem.cnf
+    let buffer = include_str!("../bin/problem.cnf");
+    let formula = cnfparser::parse_cnf(buffer).unwrap();
+
+    // Every solver except the interactive one finds a model of the formula
+    for name in ["chaos", "wsat", "gsat", "dpll", "cdcl"] {
+        let mut solver = build_solver(name, &config).unwrap();
+        solver.add_formula(&formula);
+
+        let result = solver.solve();
+        let model = result
+            .model()
+            .unwrap_or_else(|| panic!("{}: no model", name));
+        assert!(model.satisfies(&formula), "{}: invalid model", name);
+        assert_eq!(solver.model(), Some(model), "{}: model not kept", name);
+    }
+
+    // The complete solvers refute the pigeonhole formula
+    let buffer = include_str!("../bin/pigeonhole.cnf");
+    let formula = cnfparser::parse_cnf(buffer).unwrap();
+    for name in ["dpll", "cdcl"] {
+        let mut solver = build_solver(name, &config).unwrap();
+        solver.add_formula(&formula);
+
+        assert_eq!(solver.solve(), SolveResult::Unsat, "{}", name);
+        assert_eq!(solver.model(), None, "{}", name);
+        assert!(solver.stats().conflicts > 0, "{}: no conflicts", name);
+    }
+
+    assert!(build_solver("oracle", &config).is_err());
+}
+
+#[test]
+fn test_assumptions() {
+    // Clauses: 1 v -3 and 2 v 3 v -1
+    let buffer = include_str!("../bin/problem.cnf");
+    let formula = cnfparser::parse_cnf(buffer).unwrap();
+
+    for name in ["dpll", "cdcl"] {
+        let mut solver = build_solver(name, &SolverConfig::default()).unwrap();
+        solver.add_formula(&formula);
+
+        // Assuming 1 and -2 forces 3, which contradicts the assumption -3
+        let assumptions = [
+            Literal {
+                value: 1,
+                negated: false,
+            },
+            Literal {
+                value: 2,
+                negated: true,
+            },
+            Literal {
+                value: 3,
+                negated: true,
+            },
+        ];
+        assert!(
+            solver.solve_with_assumptions(&assumptions).is_unsat(),
+            "{}",
+            name
+        );
+
+        let result = solver.solve_with_assumptions(&assumptions[..2]);
+        let model = result.model().unwrap();
+        assert_eq!(model.value(3), Some(true), "{}", name);
+
+        // Assumptions do not persist, and new clauses with new variables are taken into account
+        let mut clause = sat_rs::notation::Clause::new();
+        clause.literals.push(Literal {
+            value: 4,
+            negated: true,
+        });
+        clause.literals.push(Literal {
+            value: 3,
+            negated: true,
+        });
+        solver.add_clause(clause);
+
+        let result = solver.solve_with_assumptions(&[Literal {
+            value: 4,
+            negated: false,
+        }]);
+        let model = result.model().unwrap();
+        assert_eq!(model.value(3), Some(false), "{}", name);
+        assert!(model.satisfies(&formula), "{}", name);
+    }
+}
+
+#[test]
+fn test_seed() {
+    let config = SolverConfig {
+        max_tries: 5,
+        max_flips: 50,
+        seed: Some(42),
+        ..Default::default()
+    };
+
+    // With the same seed, every randomized solver finds the same model of the satisfiable
+    // formula, and does the same work on the unsatisfiable one
+    for buffer in [
+        include_str!("../bin/problem.cnf"),
+        include_str!("../bin/pigeonhole.cnf"),
+    ] {
+        let formula = cnfparser::parse_cnf(buffer).unwrap();
+        for name in SOLVERS
+            .iter()
+            .filter(|name| !["interactive", "dpll", "cdcl"].contains(name))
+        {
+            let runs: Vec<_> = (0..2)
+                .map(|_| {
+                    let mut solver = build_solver(name, &config).unwrap();
+                    solver.add_formula(&formula);
+                    (solver.solve(), solver.stats().clone())
+                })
+                .collect();
+            assert_eq!(runs[0], runs[1], "{}: not reproducible", name);
+        }
+    }
+}
@@ -0,0 +1,63 @@
+use sat_rs::solvers::restart::{luby, Glucose, Restart, RestartPolicy};
+
+/// Returns the number of conflicts in each of the first `count` restart intervals
+fn intervals(policy: &mut dyn RestartPolicy, count: usize) -> Vec<u64> {
+    let mut intervals = Vec::new();
+    let mut conflicts = 0;
+    while intervals.len() < count {
+        policy.conflict(2);
+        conflicts += 1;
+        if policy.should_restart() {
+            intervals.push(conflicts);
+            conflicts = 0;
+        }
+    }
+    intervals
+}
+
+#[test]
+fn test_restart_policies() {
+    let sequence: Vec<u64> = (0..15).map(luby).collect();
+    assert_eq!(sequence, vec![1, 1, 2, 1, 1, 2, 4, 1, 1, 2, 1, 1, 2, 4, 8]);
+
+    let mut policy = Restart::Luby { unit: 10 }.build();
+    assert_eq!(intervals(&mut *policy, 7), vec![10, 10, 20, 10, 10, 20, 40]);
+
+    let mut policy = Restart::Geometric {
+        initial: 10,
+        factor: 2.0,
+    }
+    .build();
+    assert_eq!(intervals(&mut *policy, 4), vec![10, 20, 40, 80]);
+
+    // A constant LBD never exceeds its own average
+    let mut policy = Glucose::new(1.25, 5);
+    for _ in 0..100 {
+        policy.conflict(4);
+        assert!(!policy.should_restart());
+    }
+
+    // A sudden increase of the LBD triggers a restart
+    for _ in 0..5 {
+        policy.conflict(20);
+    }
+    assert!(policy.should_restart());
+    assert!(!policy.should_restart());
+
+    assert_eq!("none".parse::<Restart>(), Ok(Restart::Never));
+    assert!("luby:1:2".parse::<Restart>().is_err());
+    assert!("luby:x".parse::<Restart>().is_err());
+
+    // Parameters which would restart before every decision are rejected
+    for spec in [
+        "luby:0",
+        "geometric:0",
+        "geometric:10:0.5",
+        "glucose:0",
+        "glucose:-1.5",
+        "glucose:1.25:0",
+    ] {
+        assert!(spec.parse::<Restart>().is_err(), "{}", spec);
+    }
+    assert!("geometric:1:1".parse::<Restart>().is_ok());
+}
@@ -0,0 +1,29 @@
+use sat_rs::cnfparser;
+use sat_rs::solvers::result::{Model, SolveResult};
+use std::collections::HashMap;
+
+#[test]
+fn test_model() {
+    // Create a buffer of type &str using file at bin/problem.cnf
+    let buffer = include_str!("../bin/problem.cnf");
+    let formula = cnfparser::parse_cnf(buffer).unwrap();
+
+    // Clauses: 1 v -3 and 2 v 3 v -1
+    let model = Model::from(HashMap::from([(1, true), (2, false), (3, true)]));
+    assert!(model.satisfies(&formula));
+    assert_eq!(model.literals(), vec![1, -2, 3]);
+    assert_eq!(model.to_string(), "v 1 -2 3 0");
+
+    let model = Model::from(HashMap::from([(1, true), (2, false), (3, false)]));
+    assert!(!model.satisfies(&formula));
+
+    // Missing variables falsify their literals
+    let model = Model::from(HashMap::from([(1, true)]));
+    assert!(!model.satisfies(&formula));
+
+    let result = SolveResult::Sat(model.clone());
+    assert!(result.is_sat());
+    assert_eq!(result.model(), Some(&model));
+    assert_eq!(result.to_string(), "SATISFIABLE");
+    assert_eq!(SolveResult::Unsat.to_string(), "UNSATISFIABLE");
+}
@@ -0,0 +1,74 @@
+use sat_rs::cnfparser;
+use sat_rs::solvers::registry::{build_solver, SolverConfig, SOLVERS};
+use sat_rs::solvers::result::Model;
+use sat_rs::verify::{
+    parse_solution, verify_model, verify_solution, FalsifiedClause, SolutionReport,
+};
+use std::collections::HashMap;
+
+#[test]
+fn test_verify_model() {
+    // Create a buffer of type &str using file at bin/problem.cnf
+    let buffer = include_str!("../bin/problem.cnf");
+    let formula = cnfparser::parse_cnf(buffer).unwrap();
+
+    // Every model a solver returns satisfies the clauses as parsed
+    let config = SolverConfig {
+        seed: Some(7),
+        ..SolverConfig::default()
+    };
+    for name in SOLVERS.iter().filter(|name| **name != "interactive") {
+        let mut solver = build_solver(name, &config).unwrap();
+        solver.add_formula(&formula);
+        if let Some(model) = solver.solve().model() {
+            assert_eq!(verify_model(&formula, model), Ok(()), "{}", name);
+        }
+    }
+
+    // Clauses: 1 v -3 and 2 v 3 v -1, the second one falsified
+    let model = Model::from(HashMap::from([(1, true), (2, false), (3, false)]));
+    assert_eq!(
+        verify_model(&formula, &model),
+        Err(FalsifiedClause {
+            index: 1,
+            literals: vec![2, 3, -1],
+        })
+    );
+
+    // A variable missing from the model falsifies its literals
+    let model = Model::from(HashMap::from([(2, true), (3, true)]));
+    assert_eq!(verify_model(&formula, &model).unwrap_err().index, 0);
+}
+
+#[test]
+fn test_verify_solution() {
+    // Create a buffer of type &str using file at bin/problem.cnf
+    let buffer = include_str!("../bin/problem.cnf");
+    let formula = cnfparser::parse_cnf(buffer).unwrap();
+
+    // The model printed by the CLI is read back from its value lines
+    let mut solver = build_solver("cdcl", &SolverConfig::default()).unwrap();
+    solver.add_formula(&formula);
+    let output = format!("s SATISFIABLE\n{}\n", solver.solve().model().unwrap());
+    let literals = parse_solution(&output).unwrap();
+    assert!(verify_solution(&formula, &literals).is_valid());
+
+    // Clauses: 1 v -3 and 2 v 3 v -1, the second one falsified as 1 is contradictory
+    let literals = parse_solution("c external\ns SATISFIABLE\nv -1 1\nv -3 0\n").unwrap();
+    let report = verify_solution(&formula, &literals);
+    assert_eq!(
+        report,
+        SolutionReport {
+            falsified: vec![FalsifiedClause {
+                index: 1,
+                literals: vec![2, 3, -1],
+            }],
+            unassigned: vec![2],
+            contradictory: vec![1],
+        }
+    );
+    assert!(!report.is_valid());
+
+    assert!(parse_solution("s SATISFIABLE\nv 1 x 0\n").is_err());
+    assert!(parse_solution("v 1 2 0\n").is_err());
+}
@@ -0,0 +1,92 @@
+mod common;
+
+use common::{random_formula, Lcg};
+use rand::SeedableRng;
+use rand_chacha::ChaCha8Rng;
+use sat_rs::cnfparser;
+use sat_rs::solvers::localsearch::LocalSearch;
+use sat_rs::solvers::weighting::{self, ClauseWeights};
+
+#[test]
+fn test_saps_weights() {
+    // With p false, p is unsatisfied and -p v q is satisfied by p alone
+    let formula = cnfparser::parse_cnf("p cnf 2 2\n1 0\n-1 2 0\n").unwrap();
+    let state = LocalSearch::new(&formula);
+    let mut weights = ClauseWeights::new(&state, 1.0);
+
+    // Scaling only raises the weight of the unsatisfied clause
+    weights.scale_unsat(&state, 1.3);
+    assert!((weights.weight(0) - 1.3).abs() < 1e-12);
+    assert_eq!(weights.weight(1), 1.0);
+
+    // Smoothing moves both weights a fifth of the way to their mean of 1.15
+    weights.smooth(&state, 0.8);
+    assert!((weights.weight(0) - 1.27).abs() < 1e-12);
+    assert!((weights.weight(1) - 1.03).abs() < 1e-12);
+    assert!((weights.score(0) - 0.24).abs() < 1e-12);
+}
+
+#[test]
+fn test_ddfw_distribute() {
+    // With every variable false, p v q is unsatisfied and p v -r is its satisfied neighbour
+    let formula = cnfparser::parse_cnf("p cnf 3 3\n1 2 0\n1 -3 0\n-1 0\n").unwrap();
+    let state = LocalSearch::new(&formula);
+    let mut weights = ClauseWeights::new(&state, 8.0);
+    let mut rng = ChaCha8Rng::seed_from_u64(1);
+
+    // A neighbour heavier than the initial weight gives 2
+    weights.add(&state, 1, 2.0);
+    weighting::distribute(&state, &mut weights, 8.0, &mut rng);
+    assert_eq!(weights.weight(0), 10.0);
+    assert_eq!(weights.weight(1), 8.0);
+
+    // Otherwise it gives 1, and the total weight is unchanged
+    weighting::distribute(&state, &mut weights, 8.0, &mut rng);
+    assert_eq!(weights.weight(0), 11.0);
+    assert_eq!(weights.weight(1), 7.0);
+    assert_eq!(weights.weight(2), 8.0);
+    assert_eq!(weights.mean(), 26.0 / 3.0);
+}
+
+#[test]
+fn test_weighted_scores() {
+    // Random 3-SAT formula
+    let mut lcg = Lcg::new(11);
+    let num_vars = 12;
+    let formula = random_formula(&mut lcg, num_vars, 50, 3..=3);
+
+    let mut state = LocalSearch::new(&formula);
+    let mut weights = ClauseWeights::new(&state, 1.0);
+    for round in 0..300 {
+        // Interleave flips with weight updates and smoothing
+        weights.flip(&mut state, lcg.next(num_vars) as usize);
+        let clause = lcg.next(state.num_clauses() as u64) as usize;
+        weights.add(&state, clause, lcg.next(5) as f64);
+        if round % 50 == 0 {
+            weights.smooth(&state, 0.8);
+        }
+
+        // The weighted scores match the weights of the clauses made and broken by each flip
+        for var in 0..state.num_vars() {
+            let mut make = 0.0;
+            let mut breaks = 0.0;
+            for clause in 0..state.num_clauses() {
+                let lits = state.clause(clause);
+                if !lits.iter().any(|lit| lit.var() == var) {
+                    continue;
+                }
+                if state.true_count(clause) == 0 {
+                    make += weights.weight(clause);
+                } else if state.critical_var(clause) == Some(var) {
+                    breaks += weights.weight(clause);
+                }
+            }
+            assert!((weights.make(var) - make).abs() < 1e-6, "make of {}", var);
+            assert!(
+                (weights.breaks(var) - breaks).abs() < 1e-6,
+                "break of {}",
+                var
+            );
+        }
+    }
+}
@@ -1,5 +1,10 @@
+use rand::SeedableRng;
+use rand_chacha::ChaCha8Rng;
 use sat_rs::cnfparser;
-use sat_rs::solvers::wsat;
+use sat_rs::solvers::localsearch::LocalSearch;
+use sat_rs::solvers::result::SolveResult;
+use sat_rs::solvers::solver::Solver;
+use sat_rs::solvers::wsat::{self, WsatMode, WsatSolver};
 
 #[test]
 fn test_wsat() {
@@ -7,10 +12,79 @@ fn test_wsat() {
     let buffer = include_str!("../bin/unsatisfiable.cnf");
 
     // Parse the CNF file
-    let formula = cnfparser::parse_cnf(&buffer);
+    let formula = cnfparser::parse_cnf(buffer);
 
-    // Check that the formula is unsatisfiable
-    let result: bool = wsat::wsat_algorithm(&mut formula.unwrap(), 10, 10);
+    // Check that no model is found for the unsatisfiable formula
+    let result: SolveResult = wsat::wsat_algorithm(
+        &mut formula.clone().unwrap(),
+        10,
+        10,
+        WsatMode::default(),
+        0,
+        Some(1),
+    );
+    let result_random_walk: SolveResult = wsat::wsat_algorithm(
+        &mut formula.clone().unwrap(),
+        10,
+        10,
+        WsatMode::RandomWalk,
+        0,
+        Some(1),
+    );
 
-    assert_eq!(result, false);
+    assert!(matches!(result, SolveResult::Unknown(_)));
+    assert!(matches!(result_random_walk, SolveResult::Unknown(_)));
+}
+
+#[test]
+fn test_wsat_skc() {
+    // Create a buffer of type &str using file at bin/problem.cnf
+    let buffer = include_str!("../bin/problem.cnf");
+    let mut formula = cnfparser::parse_cnf(buffer).unwrap();
+
+    // Whatever the noise, the SKC rule finds a model of the satisfiable formula
+    for noise in [0.0, 0.5, 1.0] {
+        let result =
+            wsat::wsat_algorithm(&mut formula, 10, 100, WsatMode::Skc { noise }, 0, Some(1));
+        assert!(formula.evaluate(result.model().unwrap().assignment()) == Some(true));
+    }
+}
+
+#[test]
+fn test_select_skc() {
+    let mut rng = ChaCha8Rng::seed_from_u64(1);
+
+    // With every variable false, flipping p breaks -p while flipping q breaks nothing
+    let formula = cnfparser::parse_cnf("p cnf 2 2\n1 2 0\n-1 0\n").unwrap();
+    let state = LocalSearch::new(&formula);
+    assert_eq!((state.breaks(0), state.breaks(1)), (1, 0));
+
+    // The freebie is taken even with the highest noise
+    for _ in 0..10 {
+        assert_eq!(wsat::select_skc(&state, &[0, 1], 1.0, &mut rng), 1);
+    }
+
+    // Without a freebie, flipping p breaks -p and flipping q breaks -q and -q v r
+    let formula = cnfparser::parse_cnf("p cnf 3 4\n1 2 0\n-1 0\n-2 0\n-2 3 0\n").unwrap();
+    let state = LocalSearch::new(&formula);
+    assert_eq!((state.breaks(0), state.breaks(1)), (1, 2));
+
+    // Without noise, the variable with the fewest breaks is taken
+    for _ in 0..10 {
+        assert_eq!(wsat::select_skc(&state, &[0, 1], 0.0, &mut rng), 0);
+    }
+}
+
+#[test]
+fn test_wsat_tabu_blocked_steps() {
+    // Create a buffer of type &str using file at bin/pigeonhole.cnf
+    let buffer = include_str!("../bin/pigeonhole.cnf");
+    let formula = cnfparser::parse_cnf(buffer).unwrap();
+
+    // A step is blocked at most once, however many variables of the clause are tabu
+    let mut solver = WsatSolver::new(1, 50, WsatMode::Skc { noise: 0.0 }, 5).with_seed(Some(1));
+    solver.add_formula(&formula);
+    assert!(matches!(solver.solve(), SolveResult::Unknown(_)));
+    assert!(solver.stats().tabu_blocked > 0);
+    assert!(solver.stats().tabu_blocked <= 50);
 }